@@ -1,5 +1,6 @@
 //! Custom error types for the Balancer maths library
 
+use alloy_primitives::U256;
 use std::fmt;
 
 /// Errors that can occur during pool operations
@@ -88,6 +89,54 @@ pub enum PoolError {
     StableInvariantDidntConverge,
 
     TokenAmountOutIsGreaterThanBalance,
+
+    /// Initialization that would mint less BPT than the minimum total supply
+    PoolTotalSupplyTooLow,
+
+    /// Operation on a paused pool
+    PoolPaused,
+
+    /// Recovery mode operation on a pool that is not in recovery mode
+    PoolNotInRecoveryMode,
+
+    /// QuantAMM trade larger than the max trade size ratio of the balance it trades against
+    MaxTradeSizeRatioExceeded {
+        /// Amount traded against the balance (scaled 18)
+        amount_scaled_18: U256,
+        /// Largest amount allowed by the max trade size ratio (scaled 18)
+        max_amount_scaled_18: U256,
+    },
+
+    /// Swap on a FixedPriceLBP outside its sale window
+    SaleWindowClosed {
+        current_timestamp: U256,
+        start_time: U256,
+        end_time: U256,
+    },
+
+    /// ERC4626 deposit of more assets than the vault's max deposit
+    ERC4626ExceededMaxDeposit {
+        assets: U256,
+        max_assets: U256,
+    },
+
+    /// ERC4626 mint of more shares than the vault's max mint
+    ERC4626ExceededMaxMint {
+        shares: U256,
+        max_shares: U256,
+    },
+
+    /// ERC4626 redeem of more shares than the vault's max redeem
+    ERC4626ExceededMaxRedeem {
+        shares: U256,
+        max_shares: U256,
+    },
+
+    /// ERC4626 withdraw of more assets than the vault's max withdraw
+    ERC4626ExceededMaxWithdraw {
+        assets: U256,
+        max_assets: U256,
+    },
 }
 
 impl fmt::Display for PoolError {
@@ -135,6 +184,38 @@ impl fmt::Display for PoolError {
             PoolError::TokenAmountOutIsGreaterThanBalance => {
                 write!(f, "Token amount out is greater than balance")
             }
+            PoolError::PoolTotalSupplyTooLow => write!(f, "PoolTotalSupplyTooLow"),
+            PoolError::PoolPaused => write!(f, "PoolPaused"),
+            PoolError::PoolNotInRecoveryMode => write!(f, "PoolNotInRecoveryMode"),
+            PoolError::MaxTradeSizeRatioExceeded {
+                amount_scaled_18,
+                max_amount_scaled_18,
+            } => write!(
+                f,
+                "Max trade size ratio exceeded: {} > {}",
+                amount_scaled_18, max_amount_scaled_18
+            ),
+            PoolError::SaleWindowClosed {
+                current_timestamp,
+                start_time,
+                end_time,
+            } => write!(
+                f,
+                "Sale window closed: {} outside [{}, {}]",
+                current_timestamp, start_time, end_time
+            ),
+            PoolError::ERC4626ExceededMaxDeposit { assets, max_assets } => {
+                write!(f, "ERC4626ExceededMaxDeposit {} {}", assets, max_assets)
+            }
+            PoolError::ERC4626ExceededMaxMint { shares, max_shares } => {
+                write!(f, "ERC4626ExceededMaxMint {} {}", shares, max_shares)
+            }
+            PoolError::ERC4626ExceededMaxRedeem { shares, max_shares } => {
+                write!(f, "ERC4626ExceededMaxRedeem {} {}", shares, max_shares)
+            }
+            PoolError::ERC4626ExceededMaxWithdraw { assets, max_assets } => {
+                write!(f, "ERC4626ExceededMaxWithdraw {} {}", assets, max_assets)
+            }
         }
     }
 }
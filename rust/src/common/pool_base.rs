@@ -5,7 +5,9 @@ use crate::common::types::*;
 use alloy_primitives::U256;
 
 /// Trait for pool implementations (matches TypeScript PoolBase interface and Python PoolBase abstract class)
-pub trait PoolBase {
+///
+/// Pools are `Send + Sync` so they can be shared across threads when quoting in parallel.
+pub trait PoolBase: Send + Sync {
     /// Perform swap operation
     fn on_swap(&self, swap_params: &SwapParams) -> Result<U256, PoolError>;
 
@@ -20,6 +20,10 @@ pub enum AddLiquidityKind {
     Unbalanced = 0,
     /// Add liquidity with exact BPT output for single token
     SingleTokenExactOut = 1,
+    /// Add liquidity with exact BPT output and proportional amounts in
+    Proportional = 2,
+    /// Donate amounts to the pool without minting BPT
+    Donation = 3,
 }
 
 /// Kind of remove liquidity operation
@@ -31,6 +35,8 @@ pub enum RemoveLiquidityKind {
     SingleTokenExactIn = 1,
     /// Remove liquidity with exact token output for single token
     SingleTokenExactOut = 2,
+    /// Remove liquidity with exact token outputs for any subset of tokens (unbalanced)
+    Custom = 3,
 }
 
 /// Input for swap operations
@@ -72,6 +78,17 @@ pub struct RemoveLiquidityInput {
     pub kind: RemoveLiquidityKind,
 }
 
+/// Input for pool initialization
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InitializeInput {
+    /// Pool address
+    pub pool: String,
+    /// Initial amounts for each token (raw, not scaled)
+    pub exact_amounts_in_raw: Vec<U256>,
+    /// Initial price range and target price, required for ReClamm and ReClammV2 pools
+    pub reclamm_price_params: Option<crate::pools::reclamm::reclamm_data::ReClammPriceParams>,
+}
+
 /// Base pool state shared by all pool types
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BasePoolState {
@@ -95,8 +112,18 @@ pub struct BasePoolState {
     pub total_supply: U256,
     /// Whether pool supports unbalanced liquidity
     pub supports_unbalanced_liquidity: bool,
+    /// Whether pool accepts donations
+    #[serde(default)]
+    pub supports_donation: bool,
     /// Optional hook type
     pub hook_type: Option<String>,
+    /// Whether the pool is paused: swaps and liquidity operations revert
+    #[serde(default)]
+    pub is_pool_paused: bool,
+    /// Whether the pool is in recovery mode: aggregate swap fees are not charged and recovery
+    /// exits are allowed
+    #[serde(default)]
+    pub is_pool_in_recovery_mode: bool,
 }
 
 /// Pool state - can be any specific pool type
@@ -140,6 +167,15 @@ pub struct SwapResult {
     pub fee_amount_raw: U256,
 }
 
+/// Virtual balances of a ReClamm pool set by a liquidity operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VirtualBalancesUpdate {
+    /// New last virtual balances (scaled 18)
+    pub virtual_balances: Vec<U256>,
+    /// New last timestamp, the current timestamp of the pool
+    pub last_timestamp: U256,
+}
+
 /// Result of an add liquidity operation
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AddLiquidityResult {
@@ -147,6 +183,41 @@ pub struct AddLiquidityResult {
     pub bpt_amount_out_raw: U256,
     /// Amounts added (raw, not scaled)
     pub amounts_in_raw: Vec<U256>,
+    /// Virtual balances of ReClamm and ReClammV2 pools, which scale with the BPT supply
+    pub virtual_balances_update: Option<VirtualBalancesUpdate>,
+}
+
+/// How a single token exact in add liquidity is solved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SingleTokenExactInMethod {
+    /// Add the exact amount as an unbalanced add, so all of it enters the pool
+    Unbalanced = 0,
+    /// Find the largest exact BPT out whose single token amount in fits in the exact amount
+    InverseExactOut = 1,
+}
+
+/// Result of a single token exact in add liquidity query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddLiquiditySingleTokenExactInResult {
+    /// BPT amount minted (raw, not scaled)
+    pub bpt_amount_out_raw: U256,
+    /// Amount of the token added (raw, not scaled)
+    pub amount_in_raw: U256,
+    /// Swap fee charged on the non-proportional part of the amount in (raw, not scaled)
+    pub swap_fee_amount_raw: U256,
+    /// Protocol and pool creator part of the swap fee (raw, not scaled)
+    pub aggregate_swap_fee_amount_raw: U256,
+}
+
+/// Result of a pool initialization
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InitializeResult {
+    /// BPT amount minted to the initializer, after locking the minimum total supply (raw, not scaled)
+    pub bpt_amount_out_raw: U256,
+    /// Initial virtual balances (scaled 18), for ReClamm and ReClammV2 pools
+    pub virtual_balances: Option<Vec<U256>>,
+    /// Pool state after initialization
+    pub pool_state: PoolState,
 }
 
 /// Result of a remove liquidity operation
@@ -156,6 +227,17 @@ pub struct RemoveLiquidityResult {
     pub bpt_amount_in_raw: U256,
     /// Amounts removed (raw, not scaled)
     pub amounts_out_raw: Vec<U256>,
+    /// Virtual balances of ReClamm and ReClammV2 pools, which scale with the BPT supply
+    pub virtual_balances_update: Option<VirtualBalancesUpdate>,
+}
+
+/// Result of a swap to a target price
+#[derive(Debug, Clone)]
+pub struct SwapToTargetPriceResult {
+    pub token_in_index: usize,
+    pub token_out_index: usize,
+    pub amount_in_raw: U256,
+    pub amount_out_raw: U256,
 }
 
 /// Swap parameters
@@ -204,6 +286,23 @@ impl PoolState {
         }
     }
 
+    /// Get the base pool state mutably, e.g. to apply balance changes after an operation
+    pub fn base_mut(&mut self) -> &mut BasePoolState {
+        match self {
+            PoolState::Base(base) => base,
+            PoolState::Weighted(weighted) => &mut weighted.base,
+            PoolState::Stable(stable) => &mut stable.base,
+            PoolState::GyroECLP(gyro_eclp) => &mut gyro_eclp.base,
+            PoolState::ReClamm(re_clamm) => &mut re_clamm.base,
+            PoolState::ReClammV2(re_clamm_v2) => &mut re_clamm_v2.base,
+            PoolState::QuantAmm(quant_amm) => &mut quant_amm.base,
+            PoolState::LiquidityBootstrapping(liquidity_bootstrapping) => {
+                &mut liquidity_bootstrapping.base
+            }
+            PoolState::FixedPriceLBP(fixed_price_lbp) => &mut fixed_price_lbp.base,
+        }
+    }
+
     /// Get the pool type
     pub fn pool_type(&self) -> &str {
         &self.base().pool_type
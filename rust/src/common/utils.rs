@@ -3,7 +3,7 @@
 use crate::common::errors::PoolError;
 use crate::common::maths::{div_down_fixed, div_up_fixed, mul_down_fixed, mul_up_fixed};
 use crate::common::types::PoolState;
-use alloy_primitives::U256;
+use alloy_primitives::{I256, U256};
 
 /// Find case insensitive index in list
 pub fn find_case_insensitive_index_in_list(strings: &[String], target: &str) -> Option<usize> {
@@ -18,6 +18,37 @@ pub fn find_case_insensitive_index_in_list(strings: &[String], target: &str) ->
     None
 }
 
+/// Convert U256 to f64, rounding to the nearest representable value
+pub fn u256_to_f64(value: &U256) -> f64 {
+    // For values that fit in u128, use direct conversion
+    if let Ok(val) = u128::try_from(*value) {
+        return val as f64;
+    }
+
+    // Otherwise accumulate the limbs, most significant first
+    value
+        .as_limbs()
+        .iter()
+        .rev()
+        .fold(0.0, |acc, limb| acc * 18446744073709551616.0 + *limb as f64)
+}
+
+/// Convert f64 to U256, rounding to the nearest integer. Negative, non-finite and out of range
+/// values fail with `MathOverflow` instead of saturating.
+pub fn f64_to_u256(value: f64) -> Result<U256, PoolError> {
+    U256::try_from(value.round()).map_err(|_| PoolError::MathOverflow)
+}
+
+/// Convert I256 to f64, rounding to the nearest representable value
+pub fn i256_to_f64(value: &I256) -> f64 {
+    let magnitude = u256_to_f64(&value.unsigned_abs());
+    if value.is_negative() {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
 /// Convert to scaled 18 with rate applied, rounding down
 pub fn to_scaled_18_apply_rate_round_down(
     amount: &U256,
@@ -157,6 +188,32 @@ pub fn get_single_input_index(max_amounts_in: &[U256]) -> Result<usize, PoolErro
     Ok(input_index)
 }
 
+/// Require the pool not to be paused
+pub fn require_unpaused(pool_state: &PoolState) -> Result<(), PoolError> {
+    if pool_state.base().is_pool_paused {
+        return Err(PoolError::PoolPaused);
+    }
+    Ok(())
+}
+
+/// Require the pool to be in recovery mode
+pub fn require_recovery_mode(pool_state: &PoolState) -> Result<(), PoolError> {
+    if !pool_state.base().is_pool_in_recovery_mode {
+        return Err(PoolError::PoolNotInRecoveryMode);
+    }
+    Ok(())
+}
+
+/// Get the aggregate swap fee percentage charged by the vault, which is zero in recovery mode
+pub fn aggregate_swap_fee_percentage(pool_state: &PoolState) -> U256 {
+    let base_state = pool_state.base();
+    if base_state.is_pool_in_recovery_mode {
+        U256::ZERO
+    } else {
+        base_state.aggregate_swap_fee
+    }
+}
+
 /// Require unbalanced liquidity to be enabled
 pub fn require_unbalanced_liquidity_enabled(pool_state: &PoolState) -> Result<(), PoolError> {
     if !pool_state.base().supports_unbalanced_liquidity {
@@ -166,3 +223,11 @@ pub fn require_unbalanced_liquidity_enabled(pool_state: &PoolState) -> Result<()
     }
     Ok(())
 }
+
+/// Require donations to be enabled
+pub fn require_donation_enabled(pool_state: &PoolState) -> Result<(), PoolError> {
+    if !pool_state.base().supports_donation {
+        return Err(PoolError::Custom("DoesNotSupportDonation".to_string()));
+    }
+    Ok(())
+}
@@ -41,7 +41,9 @@ pub struct HookConfig {
 }
 
 /// Trait for pool hooks (matches Python HookBase interface exactly)
-pub trait HookBase {
+///
+/// Hooks are `Send + Sync` so they can be shared across threads when quoting in parallel.
+pub trait HookBase: Send + Sync {
     /// Get the hook type
     fn hook_type(&self) -> &str;
 
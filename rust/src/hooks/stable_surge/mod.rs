@@ -11,7 +11,7 @@ use crate::hooks::types::{
     HookState,
 };
 use crate::hooks::{DefaultHook, HookBase, HookConfig};
-use crate::pools::stable::stable_data::StableMutable;
+use crate::pools::stable::stable_data::{AmpUpdate, StableMutable};
 use crate::pools::stable::StablePool;
 use alloy_primitives::U256;
 use serde::{Deserialize, Serialize};
@@ -50,6 +50,10 @@ impl Default for StableSurgeHookState {
 /// This hook implements the StableSurgeHook found in mono-repo: https://github.com/balancer/balancer-v3-monorepo/blob/main/pkg/pool-hooks/contracts/StableSurgeHook.sol
 pub struct StableSurgeHook {
     config: HookConfig,
+    /// Amplification parameter update of the pool, if one was started
+    amp_update: Option<AmpUpdate>,
+    /// Current timestamp of the pool, used to interpolate `amp_update`
+    current_timestamp: U256,
 }
 
 impl StableSurgeHook {
@@ -61,7 +65,20 @@ impl StableSurgeHook {
             ..Default::default()
         };
 
-        Self { config }
+        Self {
+            config,
+            amp_update: None,
+            current_timestamp: U256::ZERO,
+        }
+    }
+
+    /// Create a hook for a stable pool, following the pool's amplification parameter update
+    pub fn for_pool(stable_state: &StableMutable) -> Self {
+        Self {
+            amp_update: stable_state.amp_update.clone(),
+            current_timestamp: stable_state.current_timestamp,
+            ..Self::new()
+        }
     }
 
     /// Get surge fee percentage based on imbalance
@@ -73,9 +90,11 @@ impl StableSurgeHook {
         static_fee_percentage: &U256,
         hook_state: &StableSurgeHookState,
     ) -> Result<U256, PoolError> {
-        // Create a temporary stable pool for swap simulation
+        // Create a temporary stable pool for swap simulation, with the same live amp as the pool
         let stable_state = StableMutable {
             amp: hook_state.amp,
+            amp_update: self.amp_update.clone(),
+            current_timestamp: self.current_timestamp,
         };
         let stable_pool = StablePool::new(stable_state);
 
@@ -238,7 +257,7 @@ impl HookBase for StableSurgeHook {
 
     fn on_after_add_liquidity(
         &self,
-        _kind: AddLiquidityKind,
+        kind: AddLiquidityKind,
         amounts_in_scaled_18: &[U256],
         amounts_in_raw: &[U256],
         _bpt_amount_out: &U256,
@@ -247,6 +266,14 @@ impl HookBase for StableSurgeHook {
     ) -> AfterAddLiquidityResult {
         match hook_state {
             HookState::StableSurge(state) => {
+                // Proportional add is always fine
+                if kind == AddLiquidityKind::Proportional {
+                    return AfterAddLiquidityResult {
+                        success: true,
+                        hook_adjusted_amounts_in_raw: amounts_in_raw.to_vec(),
+                    };
+                }
+
                 // Rebuild old balances before adding liquidity
                 let mut old_balances_scaled_18 = vec![U256::ZERO; balances_scaled_18.len()];
                 for i in 0..balances_scaled_18.len() {
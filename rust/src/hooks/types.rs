@@ -11,6 +11,7 @@ use serde::{Deserialize, Serialize};
 /// Hook state - can be any specific hook type
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
 pub enum HookState {
     /// Akron hook state
     Akron(AkronHookState),
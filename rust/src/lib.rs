@@ -20,4 +20,4 @@ pub use hooks::{DefaultHook, HookBase};
 // Re-export pool implementations
 pub use pools::weighted::{WeightedPool, WeightedState};
 
-pub use vault::Vault;
+pub use vault::{PreparedPool, Vault};
@@ -1,4 +1,5 @@
 use crate::common::types::BasePoolState;
+use crate::pools::buffer::enums::BufferWrapOrUnwrapPath;
 use alloy_primitives::U256;
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +9,18 @@ pub struct BufferMutable {
     pub rate: U256,
     pub max_deposit: Option<U256>,
     pub max_mint: Option<U256>,
+    /// Most underlying tokens the ERC4626 vault lets the Vault withdraw (raw)
+    #[serde(default)]
+    pub max_withdraw: Option<U256>,
+    /// Most wrapped tokens the ERC4626 vault lets the Vault redeem (raw)
+    #[serde(default)]
+    pub max_redeem: Option<U256>,
+    /// Underlying tokens held by the buffer (raw)
+    #[serde(default)]
+    pub underlying_balance: U256,
+    /// Wrapped tokens held by the buffer (raw)
+    #[serde(default)]
+    pub wrapped_balance: U256,
 }
 
 /// Buffer immutable state
@@ -15,6 +28,26 @@ pub struct BufferMutable {
 pub struct BufferImmutable {
     pub pool_address: String,
     pub tokens: Vec<String>,
+    /// Decimals of the wrapped token. With the underlying decimals, lets `rate` be the value of
+    /// one whole wrapped token in whole underlying tokens when the two differ, such as for
+    /// vaults with a decimals offset
+    #[serde(default)]
+    pub wrapped_decimals: Option<u8>,
+    /// Decimals of the underlying token
+    #[serde(default)]
+    pub underlying_decimals: Option<u8>,
+}
+
+impl BufferImmutable {
+    /// Wrapped token decimals less underlying token decimals, zero unless both are known
+    pub fn decimals_offset(&self) -> i32 {
+        match (self.wrapped_decimals, self.underlying_decimals) {
+            (Some(wrapped_decimals), Some(underlying_decimals)) => {
+                wrapped_decimals as i32 - underlying_decimals as i32
+            }
+            _ => 0,
+        }
+    }
 }
 
 /// Buffer pool state
@@ -24,3 +57,14 @@ pub struct BufferState {
     pub mutable: BufferMutable,
     pub immutable: BufferImmutable,
 }
+
+/// Result of a buffer wrap or unwrap, with the buffer state it leaves
+#[derive(Debug, Clone, PartialEq)]
+pub struct BufferWrapOrUnwrapResult {
+    /// Amount out for `GivenIn`, amount in for `GivenOut` (raw)
+    pub amount_calculated_raw: U256,
+    /// Whether the buffer served the operation or the Vault went through the ERC4626 vault
+    pub path: BufferWrapOrUnwrapPath,
+    /// Buffer state after the operation
+    pub buffer_state: BufferState,
+}
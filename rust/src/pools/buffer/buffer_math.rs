@@ -1,4 +1,5 @@
-use crate::common::maths::{div_down_fixed, div_up_fixed, mul_down_fixed, mul_up_fixed};
+use crate::common::constants::WAD;
+use crate::common::errors::PoolError;
 use crate::common::types::{Rounding, SwapKind};
 use crate::pools::buffer::enums::WrappingDirection;
 use alloy_primitives::U256;
@@ -10,45 +11,63 @@ use alloy_primitives::U256;
 /// * `kind` - Swap kind (GivenIn or GivenOut)
 /// * `amount_raw` - Raw amount to convert
 /// * `rate` - Exchange rate (scaled 18)
+/// * `decimals_offset` - Wrapped token decimals less underlying token decimals
 /// * `max_deposit` - Maximum deposit limit (optional)
 /// * `max_mint` - Maximum mint limit (optional)
+/// * `max_withdraw` - Maximum withdraw limit (optional)
+/// * `max_redeem` - Maximum redeem limit (optional)
 ///
 /// # Returns
 /// Converted amount
+#[allow(clippy::too_many_arguments)]
 pub fn calculate_buffer_amounts(
     direction: WrappingDirection,
     kind: SwapKind,
     amount_raw: &U256,
     rate: &U256,
+    decimals_offset: i32,
     max_deposit: Option<&U256>,
     max_mint: Option<&U256>,
-) -> Result<U256, String> {
+    max_withdraw: Option<&U256>,
+    max_redeem: Option<&U256>,
+) -> Result<U256, PoolError> {
     match direction {
         WrappingDirection::Wrap => {
             // Amount in is underlying tokens, amount out is wrapped tokens
             match kind {
                 SwapKind::GivenIn => {
                     // previewDeposit
-                    let max_assets = max_deposit.unwrap_or(&U256::MAX);
-                    if amount_raw > max_assets {
-                        return Err(format!(
-                            "ERC4626ExceededMaxDeposit {} {}",
-                            amount_raw, max_assets
-                        ));
+                    if let Some(max_assets) = max_deposit {
+                        if amount_raw > max_assets {
+                            return Err(PoolError::ERC4626ExceededMaxDeposit {
+                                assets: *amount_raw,
+                                max_assets: *max_assets,
+                            });
+                        }
                     }
-                    Ok(_convert_to_shares(amount_raw, rate, Rounding::RoundDown))
+                    Ok(_convert_to_shares(
+                        amount_raw,
+                        rate,
+                        decimals_offset,
+                        Rounding::RoundDown,
+                    ))
                 }
                 SwapKind::GivenOut => {
                     // previewMint
-                    let max_shares = max_mint.unwrap_or(&U256::MAX);
-                    if amount_raw > max_shares {
-                        return Err(format!(
-                            "ERC4626ExceededMaxMint {} {}",
-                            amount_raw,
-                            max_mint.unwrap_or(&U256::ZERO)
-                        ));
+                    if let Some(max_shares) = max_mint {
+                        if amount_raw > max_shares {
+                            return Err(PoolError::ERC4626ExceededMaxMint {
+                                shares: *amount_raw,
+                                max_shares: *max_shares,
+                            });
+                        }
                     }
-                    Ok(_convert_to_assets(amount_raw, rate, Rounding::RoundUp))
+                    Ok(_convert_to_assets(
+                        amount_raw,
+                        rate,
+                        decimals_offset,
+                        Rounding::RoundUp,
+                    ))
                 }
             }
         }
@@ -57,11 +76,37 @@ pub fn calculate_buffer_amounts(
             match kind {
                 SwapKind::GivenIn => {
                     // previewRedeem
-                    Ok(_convert_to_assets(amount_raw, rate, Rounding::RoundDown))
+                    if let Some(max_shares) = max_redeem {
+                        if amount_raw > max_shares {
+                            return Err(PoolError::ERC4626ExceededMaxRedeem {
+                                shares: *amount_raw,
+                                max_shares: *max_shares,
+                            });
+                        }
+                    }
+                    Ok(_convert_to_assets(
+                        amount_raw,
+                        rate,
+                        decimals_offset,
+                        Rounding::RoundDown,
+                    ))
                 }
                 SwapKind::GivenOut => {
                     // previewWithdraw
-                    Ok(_convert_to_shares(amount_raw, rate, Rounding::RoundUp))
+                    if let Some(max_assets) = max_withdraw {
+                        if amount_raw > max_assets {
+                            return Err(PoolError::ERC4626ExceededMaxWithdraw {
+                                assets: *amount_raw,
+                                max_assets: *max_assets,
+                            });
+                        }
+                    }
+                    Ok(_convert_to_shares(
+                        amount_raw,
+                        rate,
+                        decimals_offset,
+                        Rounding::RoundUp,
+                    ))
                 }
             }
         }
@@ -69,17 +114,67 @@ pub fn calculate_buffer_amounts(
 }
 
 /// Convert assets to shares
-fn _convert_to_shares(assets: &U256, rate: &U256, rounding: Rounding) -> U256 {
-    match rounding {
-        Rounding::RoundUp => div_up_fixed(assets, rate).unwrap_or(U256::ZERO),
-        Rounding::RoundDown => div_down_fixed(assets, rate).unwrap_or(U256::ZERO),
-    }
+///
+/// `rate` is the value of one wrapped token in underlying tokens (scaled 18), and
+/// `decimals_offset` the wrapped token decimals less the underlying token decimals. The
+/// conversion rounds once, as the ERC4626 previews do.
+pub(crate) fn _convert_to_shares(
+    assets: &U256,
+    rate: &U256,
+    decimals_offset: i32,
+    rounding: Rounding,
+) -> U256 {
+    // shares = assets * 1e18 * 10^wrapped_decimals / (rate * 10^underlying_decimals)
+    decimals_scales(decimals_offset)
+        .and_then(|(wrapped_scale, underlying_scale)| {
+            mul_div(
+                assets,
+                &WAD.checked_mul(wrapped_scale)?,
+                &rate.checked_mul(underlying_scale)?,
+                rounding,
+            )
+        })
+        .unwrap_or(U256::ZERO)
+}
+
+/// Convert shares to assets, rounding once as the ERC4626 previews do
+pub(crate) fn _convert_to_assets(
+    shares: &U256,
+    rate: &U256,
+    decimals_offset: i32,
+    rounding: Rounding,
+) -> U256 {
+    // assets = shares * rate * 10^underlying_decimals / (1e18 * 10^wrapped_decimals)
+    decimals_scales(decimals_offset)
+        .and_then(|(wrapped_scale, underlying_scale)| {
+            mul_div(
+                shares,
+                &rate.checked_mul(underlying_scale)?,
+                &WAD.checked_mul(wrapped_scale)?,
+                rounding,
+            )
+        })
+        .unwrap_or(U256::ZERO)
+}
+
+/// Powers of ten for the wrapped and underlying decimals, after cancelling the common part
+fn decimals_scales(decimals_offset: i32) -> Option<(U256, U256)> {
+    let scale = U256::from(10).checked_pow(U256::from(decimals_offset.unsigned_abs()))?;
+    Some(if decimals_offset >= 0 {
+        (scale, U256::ONE)
+    } else {
+        (U256::ONE, scale)
+    })
 }
 
-/// Convert shares to assets
-fn _convert_to_assets(shares: &U256, rate: &U256, rounding: Rounding) -> U256 {
-    match rounding {
-        Rounding::RoundUp => mul_up_fixed(shares, rate).unwrap_or(U256::ZERO),
-        Rounding::RoundDown => mul_down_fixed(shares, rate).unwrap_or(U256::ZERO),
+/// `a * b / c` with a single rounding, or `None` if it overflows or `c` is zero
+fn mul_div(a: &U256, b: &U256, c: &U256, rounding: Rounding) -> Option<U256> {
+    let product = a.checked_mul(*b)?;
+    if c.is_zero() {
+        return None;
     }
+    Some(match rounding {
+        Rounding::RoundUp => product.div_ceil(*c),
+        Rounding::RoundDown => product / c,
+    })
 }
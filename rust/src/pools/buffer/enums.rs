@@ -6,3 +6,13 @@ pub enum WrappingDirection {
     /// Unwrap wrapped tokens to underlying tokens
     Unwrap = 1,
 }
+
+/// How the Vault settles a buffer wrap or unwrap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferWrapOrUnwrapPath {
+    /// The buffer holds enough of the token out and trades it for the token in
+    Buffer,
+    /// The buffer cannot cover the amount out, so the Vault wraps or unwraps through the
+    /// ERC4626 vault, moving half of the buffer's surplus along with it
+    Vault,
+}
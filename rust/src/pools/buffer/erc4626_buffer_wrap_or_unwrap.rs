@@ -1,9 +1,12 @@
 //! ERC4626 Buffer wrap or unwrap function
 
-use crate::common::types::SwapInput;
-use crate::pools::buffer::buffer_data::BufferState;
-use crate::pools::buffer::buffer_math::calculate_buffer_amounts;
-use crate::pools::buffer::enums::WrappingDirection;
+use crate::common::errors::PoolError;
+use crate::common::types::{Rounding, SwapInput, SwapKind};
+use crate::pools::buffer::buffer_data::{BufferState, BufferWrapOrUnwrapResult};
+use crate::pools::buffer::buffer_math::{
+    _convert_to_assets, _convert_to_shares, calculate_buffer_amounts,
+};
+use crate::pools::buffer::enums::{BufferWrapOrUnwrapPath, WrappingDirection};
 use alloy_primitives::{uint, U256};
 
 pub const _MINIMUM_WRAP_AMOUNT: U256 = uint!(1000_U256);
@@ -19,12 +22,37 @@ pub const _MINIMUM_WRAP_AMOUNT: U256 = uint!(1000_U256);
 pub fn erc4626_buffer_wrap_or_unwrap(
     swap_input: &SwapInput,
     pool_state: &BufferState,
-) -> Result<U256, String> {
+) -> Result<U256, PoolError> {
+    erc4626_buffer_wrap_or_unwrap_with_state(swap_input, pool_state)
+        .map(|result| result.amount_calculated_raw)
+}
+
+/// ERC4626 Buffer wrap or unwrap function, returning the buffer state it leaves
+///
+/// If the buffer holds enough of the token out, it serves the operation from its balances at
+/// the preview amounts, without ERC4626 limits. Otherwise the Vault wraps or unwraps through the
+/// ERC4626 vault, adding half of the buffer's surplus of the token in so the buffer moves
+/// towards holding equal values of both tokens. That deposit, mint, redeem or withdraw is
+/// subject to `max_deposit`, `max_mint`, `max_redeem` or `max_withdraw` respectively. The user
+/// still gets the preview amounts, and the buffer keeps the rounding difference. A buffer
+/// without balances always takes the second path and gives the same amounts as the ERC4626
+/// previews.
+///
+/// # Arguments
+/// * `swap_input` - Swap input parameters
+/// * `pool_state` - Buffer pool state
+///
+/// # Returns
+/// Calculated amount, the path taken and the buffer state after the operation
+pub fn erc4626_buffer_wrap_or_unwrap_with_state(
+    swap_input: &SwapInput,
+    pool_state: &BufferState,
+) -> Result<BufferWrapOrUnwrapResult, PoolError> {
     if swap_input.amount_raw < _MINIMUM_WRAP_AMOUNT {
         // If amount given is too small, rounding issues can be introduced that favors the user and can drain
         // the buffer. _MINIMUM_WRAP_AMOUNT prevents it. Most tokens have protections against it already, this
         // is just an extra layer of security.
-        return Err("wrapAmountTooSmall".to_string());
+        return Err(PoolError::Custom("wrapAmountTooSmall".to_string()));
     }
 
     // Determine wrapping direction based on token addresses
@@ -35,14 +63,182 @@ pub fn erc4626_buffer_wrap_or_unwrap(
             WrappingDirection::Wrap
         };
 
-    calculate_buffer_amounts(
+    let mutable = &pool_state.mutable;
+    let rate = &mutable.rate;
+    let decimals_offset = pool_state.immutable.decimals_offset();
+    let amount_given = swap_input.amount_raw;
+    let swap_kind = swap_input.swap_kind.clone();
+
+    // Amounts the buffer would trade, from the ERC4626 previews
+    let (amount_in, amount_out) = match (wrapping_direction, &swap_kind) {
+        (WrappingDirection::Wrap, SwapKind::GivenIn) => (
+            amount_given,
+            _convert_to_shares(&amount_given, rate, decimals_offset, Rounding::RoundDown),
+        ),
+        (WrappingDirection::Wrap, SwapKind::GivenOut) => (
+            _convert_to_assets(&amount_given, rate, decimals_offset, Rounding::RoundUp),
+            amount_given,
+        ),
+        (WrappingDirection::Unwrap, SwapKind::GivenIn) => (
+            amount_given,
+            _convert_to_assets(&amount_given, rate, decimals_offset, Rounding::RoundDown),
+        ),
+        (WrappingDirection::Unwrap, SwapKind::GivenOut) => (
+            _convert_to_shares(&amount_given, rate, decimals_offset, Rounding::RoundUp),
+            amount_given,
+        ),
+    };
+
+    let mut buffer_state = pool_state.clone();
+    let (balance_in, balance_out) = match wrapping_direction {
+        WrappingDirection::Wrap => (mutable.underlying_balance, mutable.wrapped_balance),
+        WrappingDirection::Unwrap => (mutable.wrapped_balance, mutable.underlying_balance),
+    };
+
+    if balance_out >= amount_out {
+        let (new_balance_in, new_balance_out) = (balance_in + amount_in, balance_out - amount_out);
+        set_buffer_balances(
+            &mut buffer_state,
+            wrapping_direction,
+            new_balance_in,
+            new_balance_out,
+        );
+        let amount_calculated_raw = match swap_kind {
+            SwapKind::GivenIn => amount_out,
+            SwapKind::GivenOut => amount_in,
+        };
+
+        return Ok(BufferWrapOrUnwrapResult {
+            amount_calculated_raw,
+            path: BufferWrapOrUnwrapPath::Buffer,
+            buffer_state,
+        });
+    }
+
+    // Half of the buffer's excess of the token in, and what it converts to
+    let surplus_in = match wrapping_direction {
+        WrappingDirection::Wrap => get_buffer_underlying_surplus(pool_state),
+        WrappingDirection::Unwrap => get_buffer_wrapped_surplus(pool_state),
+    };
+    let surplus_out = if surplus_in.is_zero() {
+        U256::ZERO
+    } else {
+        calculate_buffer_amounts(
+            wrapping_direction,
+            SwapKind::GivenIn,
+            &surplus_in,
+            rate,
+            decimals_offset,
+            None,
+            None,
+            None,
+            None,
+        )?
+    };
+
+    // The ERC4626 operation covers the amount and the surplus together
+    let (total_in, total_out) = match swap_kind {
+        SwapKind::GivenIn => {
+            let total_in = amount_given + surplus_in;
+            let total_out = calculate_buffer_amounts(
+                wrapping_direction,
+                SwapKind::GivenIn,
+                &total_in,
+                rate,
+                decimals_offset,
+                mutable.max_deposit.as_ref(),
+                mutable.max_mint.as_ref(),
+                mutable.max_withdraw.as_ref(),
+                mutable.max_redeem.as_ref(),
+            )?;
+            (total_in, total_out)
+        }
+        SwapKind::GivenOut => {
+            let total_out = amount_given + surplus_out;
+            let total_in = calculate_buffer_amounts(
+                wrapping_direction,
+                SwapKind::GivenOut,
+                &total_out,
+                rate,
+                decimals_offset,
+                mutable.max_deposit.as_ref(),
+                mutable.max_mint.as_ref(),
+                mutable.max_withdraw.as_ref(),
+                mutable.max_redeem.as_ref(),
+            )?;
+            (total_in, total_out)
+        }
+    };
+
+    // As in the Vault, the user gets the preview amounts and the buffer keeps the rounding
+    // difference of the combined operation
+    let new_balance_in = (balance_in + amount_in)
+        .checked_sub(total_in)
+        .ok_or(PoolError::MathOverflow)?;
+    let new_balance_out = (balance_out + total_out)
+        .checked_sub(amount_out)
+        .ok_or(PoolError::MathOverflow)?;
+    set_buffer_balances(
+        &mut buffer_state,
         wrapping_direction,
-        swap_input.swap_kind.clone(),
-        &swap_input.amount_raw,
-        &pool_state.mutable.rate,
-        pool_state.mutable.max_deposit.as_ref(),
-        pool_state.mutable.max_mint.as_ref(),
-    )
+        new_balance_in,
+        new_balance_out,
+    );
+    let amount_calculated_raw = match swap_kind {
+        SwapKind::GivenIn => amount_out,
+        SwapKind::GivenOut => amount_in,
+    };
+
+    Ok(BufferWrapOrUnwrapResult {
+        amount_calculated_raw,
+        path: BufferWrapOrUnwrapPath::Vault,
+        buffer_state,
+    })
+}
+
+/// Half of the underlying balance in excess of the value of the wrapped balance (raw)
+pub fn get_buffer_underlying_surplus(pool_state: &BufferState) -> U256 {
+    let mutable = &pool_state.mutable;
+    let wrapped_balance_as_underlying = _convert_to_assets(
+        &mutable.wrapped_balance,
+        &mutable.rate,
+        pool_state.immutable.decimals_offset(),
+        Rounding::RoundUp,
+    );
+    mutable
+        .underlying_balance
+        .saturating_sub(wrapped_balance_as_underlying)
+        / U256::from(2)
+}
+
+/// Half of the wrapped balance in excess of the value of the underlying balance (raw)
+pub fn get_buffer_wrapped_surplus(pool_state: &BufferState) -> U256 {
+    let mutable = &pool_state.mutable;
+    let underlying_balance_as_wrapped = _convert_to_shares(
+        &mutable.underlying_balance,
+        &mutable.rate,
+        pool_state.immutable.decimals_offset(),
+        Rounding::RoundUp,
+    );
+    mutable
+        .wrapped_balance
+        .saturating_sub(underlying_balance_as_wrapped)
+        / U256::from(2)
+}
+
+/// Set the buffer balances from the balances of the token in and out
+fn set_buffer_balances(
+    buffer_state: &mut BufferState,
+    wrapping_direction: WrappingDirection,
+    balance_in: U256,
+    balance_out: U256,
+) {
+    let (underlying_balance, wrapped_balance) = match wrapping_direction {
+        WrappingDirection::Wrap => (balance_in, balance_out),
+        WrappingDirection::Unwrap => (balance_out, balance_in),
+    };
+    buffer_state.mutable.underlying_balance = underlying_balance;
+    buffer_state.mutable.wrapped_balance = wrapped_balance;
 }
 
 /// Check if two addresses are the same (case-insensitive)
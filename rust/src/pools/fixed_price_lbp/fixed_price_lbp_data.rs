@@ -19,6 +19,37 @@ pub struct FixedPriceLBPImmutable {
     pub end_time: U256,
 }
 
+/// Progress of a FixedPriceLBP sale
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedPriceLBPSaleStatus {
+    /// Timestamp the status is computed at
+    pub current_timestamp: U256,
+    /// Whether swaps are within the sale window, `start_time` to `end_time` inclusive
+    pub is_sale_active: bool,
+    /// Seconds until the sale starts, zero once started
+    pub time_until_start: U256,
+    /// Seconds until the sale ends, zero once ended
+    pub time_remaining: U256,
+    /// Project tokens left in the pool (raw)
+    pub project_tokens_remaining_raw: U256,
+    /// Reserve tokens raised so far, swap fees included (raw)
+    pub reserve_raised_raw: U256,
+    /// Reserve tokens raised once every remaining project token is sold at the project token
+    /// rate, excluding further swap fees (raw)
+    pub final_raise_raw: U256,
+    /// Reserve raised as a fraction of the final raise (18 decimals)
+    pub raise_progress: U256,
+}
+
+/// Largest buy a FixedPriceLBP can fill
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedPriceLBPMaxBuy {
+    /// Every project token left in the pool (raw)
+    pub project_amount_out_raw: U256,
+    /// Reserve tokens paid for them, swap fee included (raw)
+    pub reserve_amount_in_raw: U256,
+}
+
 /// FixedPriceLBP pool state
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FixedPriceLBPState {
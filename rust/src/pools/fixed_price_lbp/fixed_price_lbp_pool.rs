@@ -1,38 +1,111 @@
 //! FixedPriceLBP pool implementation
 
+use crate::common::constants::WAD;
 use crate::common::errors::PoolError;
 use crate::common::maths::{div_down_fixed, mul_down_fixed, mul_up_fixed};
 use crate::common::pool_base::PoolBase;
 use crate::common::types::{Rounding, SwapKind, SwapParams};
-use crate::pools::fixed_price_lbp::fixed_price_lbp_data::FixedPriceLBPState;
+use crate::common::utils::to_raw_undo_rate_round_down;
+use crate::pools::fixed_price_lbp::fixed_price_lbp_data::{
+    FixedPriceLBPSaleStatus, FixedPriceLBPState,
+};
 use alloy_primitives::U256;
 
 /// FixedPriceLBP pool implementation
 pub struct FixedPriceLBPPool {
-    project_token_index: usize,
-    reserve_token_index: usize,
-    project_token_rate: U256,
-    is_swap_enabled: bool,
+    /// Pool state
+    state: FixedPriceLBPState,
+}
+
+impl FixedPriceLBPPool {
+    /// Create a new FixedPriceLBP pool
+    pub fn new(state: FixedPriceLBPState) -> Self {
+        Self { state }
+    }
+
+    /// Check that the current timestamp is within the sale window, as the pool only enables
+    /// swaps from `start_time` to `end_time` inclusive
+    pub(crate) fn ensure_sale_active(&self) -> Result<(), PoolError> {
+        let immutable = &self.state.immutable;
+        let current_timestamp = self.state.mutable.current_timestamp;
+        if current_timestamp < immutable.start_time || current_timestamp > immutable.end_time {
+            return Err(PoolError::SaleWindowClosed {
+                current_timestamp,
+                start_time: immutable.start_time,
+                end_time: immutable.end_time,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Get the progress of the sale at the current timestamp
+    ///
+    /// The final raise values the remaining project tokens at the project token rate, on top of
+    /// the reserve balance, so it excludes the swap fees of future buys.
+    pub fn sale_status(&self) -> Result<FixedPriceLBPSaleStatus, PoolError> {
+        let base = &self.state.base;
+        let immutable = &self.state.immutable;
+        let current_timestamp = self.state.mutable.current_timestamp;
+        let project_index = immutable.project_token_index;
+        let reserve_index = immutable.reserve_token_index;
+        let project_balance = base.balances_live_scaled_18[project_index];
+        let reserve_balance = base.balances_live_scaled_18[reserve_index];
+        let final_raise =
+            reserve_balance + mul_down_fixed(&project_balance, &immutable.project_token_rate)?;
+
+        let raise_progress = if final_raise.is_zero() {
+            U256::ZERO
+        } else {
+            reserve_balance * WAD / final_raise
+        };
+
+        Ok(FixedPriceLBPSaleStatus {
+            current_timestamp,
+            is_sale_active: self.ensure_sale_active().is_ok(),
+            time_until_start: immutable.start_time.saturating_sub(current_timestamp),
+            time_remaining: immutable.end_time.saturating_sub(current_timestamp),
+            project_tokens_remaining_raw: to_raw_undo_rate_round_down(
+                &project_balance,
+                &base.scaling_factors[project_index],
+                &base.token_rates[project_index],
+            )?,
+            reserve_raised_raw: to_raw_undo_rate_round_down(
+                &reserve_balance,
+                &base.scaling_factors[reserve_index],
+                &base.token_rates[reserve_index],
+            )?,
+            final_raise_raw: to_raw_undo_rate_round_down(
+                &final_raise,
+                &base.scaling_factors[reserve_index],
+                &base.token_rates[reserve_index],
+            )?,
+            raise_progress,
+        })
+    }
 }
 
 impl PoolBase for FixedPriceLBPPool {
     fn on_swap(&self, swap_params: &SwapParams) -> Result<U256, PoolError> {
-        if !self.is_swap_enabled {
+        self.ensure_sale_active()?;
+
+        if !self.state.mutable.is_swap_enabled {
             return Err(PoolError::InvalidSwapParameters);
         }
 
-        if swap_params.token_in_index == self.project_token_index {
+        let immutable = &self.state.immutable;
+        if swap_params.token_in_index == immutable.project_token_index {
             return Err(PoolError::InvalidSwapParameters);
         }
 
         match swap_params.swap_kind {
             SwapKind::GivenIn => {
                 // Reserve tokens in, project tokens out: amountOut = amountIn / rate
-                div_down_fixed(&swap_params.amount_scaled_18, &self.project_token_rate)
+                div_down_fixed(&swap_params.amount_scaled_18, &immutable.project_token_rate)
             }
             SwapKind::GivenOut => {
                 // ExactOut: amountIn = amountOut * rate
-                mul_up_fixed(&swap_params.amount_scaled_18, &self.project_token_rate)
+                mul_up_fixed(&swap_params.amount_scaled_18, &immutable.project_token_rate)
             }
         }
     }
@@ -43,18 +116,20 @@ impl PoolBase for FixedPriceLBPPool {
         rounding: Rounding,
     ) -> Result<U256, PoolError> {
         // inv = projectBalance * rate + reserveBalance
+        let immutable = &self.state.immutable;
+        let project_index = immutable.project_token_index;
         let project_token_value = match rounding {
             Rounding::RoundUp => mul_up_fixed(
-                &balances_live_scaled_18[self.project_token_index],
-                &self.project_token_rate,
+                &balances_live_scaled_18[project_index],
+                &immutable.project_token_rate,
             )?,
             Rounding::RoundDown => mul_down_fixed(
-                &balances_live_scaled_18[self.project_token_index],
-                &self.project_token_rate,
+                &balances_live_scaled_18[project_index],
+                &immutable.project_token_rate,
             )?,
         };
 
-        Ok(project_token_value + balances_live_scaled_18[self.reserve_token_index])
+        Ok(project_token_value + balances_live_scaled_18[immutable.reserve_token_index])
     }
 
     fn compute_balance(
@@ -77,11 +152,6 @@ impl PoolBase for FixedPriceLBPPool {
 
 impl From<FixedPriceLBPState> for FixedPriceLBPPool {
     fn from(state: FixedPriceLBPState) -> Self {
-        Self {
-            project_token_index: state.immutable.project_token_index,
-            reserve_token_index: state.immutable.reserve_token_index,
-            project_token_rate: state.immutable.project_token_rate,
-            is_swap_enabled: state.mutable.is_swap_enabled,
-        }
+        Self::new(state)
     }
 }
@@ -0,0 +1,141 @@
+//! Price range and liquidity distribution of Gyro ECLP pools
+
+use crate::common::errors::PoolError;
+use crate::pools::gyro::gyro_eclp_math::{
+    calc_spot_price0in1, calculate_invariant_with_error, max_balances0, max_balances1, tau,
+    virtual_offset0, virtual_offset1, DerivedEclpParams, EclpParams, Vector2,
+};
+use crate::pools::gyro::signed_fixed_point::ONE;
+use alloy_primitives::{uint, I256, U256};
+
+/// Relative price step used to differentiate the balance curve (1e-6)
+const DENSITY_PRICE_STEP_DIVISOR: U256 = uint!(1000000_U256);
+
+/// A sample of the liquidity distribution of an ECLP pool
+#[derive(Debug, Clone, PartialEq)]
+pub struct EclpLiquidityPoint {
+    /// Price of token 0 in token 1 (18 decimals)
+    pub price: U256,
+    /// Real balances of the pool when it trades at `price` (scaled 18)
+    pub balances: Vec<U256>,
+    /// Amount of token 1 the pool trades per unit move of the price at `price` (scaled 18)
+    pub liquidity_density: U256,
+}
+
+/// Price range and liquidity distribution of an ECLP pool at its current invariant
+#[derive(Debug, Clone, PartialEq)]
+pub struct EclpAnalytics {
+    /// Price of token 0 in token 1 (18 decimals)
+    pub current_price: U256,
+    /// Whether the pool is at the lower bound of its range, holding only token 0
+    pub is_at_alpha_bound: bool,
+    /// Whether the pool is at the upper bound of its range, holding only token 1
+    pub is_at_beta_bound: bool,
+    /// Real balances of the pool at the alpha bound (scaled 18)
+    pub balances_at_alpha: Vec<U256>,
+    /// Real balances of the pool at the beta bound (scaled 18)
+    pub balances_at_beta: Vec<U256>,
+    /// Liquidity density sampled at evenly spaced prices from alpha to beta, both included
+    pub liquidity_density: Vec<EclpLiquidityPoint>,
+}
+
+fn to_balance(value: I256) -> U256 {
+    if value > I256::ZERO {
+        value.into_raw()
+    } else {
+        U256::ZERO
+    }
+}
+
+/// Compute the real balances of the pool when it trades at price `px`, for invariant `r`
+///
+/// The balances are the virtual offsets less the point of the curve at `px`, which is the
+/// offset computation with the bound's tau replaced by `tau(px)`.
+fn balances_at_price(
+    p: &EclpParams,
+    d: &DerivedEclpParams,
+    r: &Vector2,
+    px: &U256,
+) -> Result<Vec<U256>, PoolError> {
+    let tau_px = tau(p, &I256::from_raw(*px))?;
+    let at_price = DerivedEclpParams {
+        tau_alpha: tau_px.clone(),
+        tau_beta: tau_px,
+        ..d.clone()
+    };
+
+    Ok(vec![
+        to_balance(virtual_offset0(p, d, r) - virtual_offset0(p, &at_price, r)),
+        to_balance(virtual_offset1(p, d, r) - virtual_offset1(p, &at_price, r)),
+    ])
+}
+
+/// Compute the current price, the balances at both bounds and `num_points` samples of the
+/// liquidity density across [alpha, beta]
+///
+/// The density at a price is the derivative of the token 1 balance along the curve,
+/// approximated with a price step of one millionth of the price.
+pub fn compute_eclp_analytics(
+    balances_live_scaled18: &[U256],
+    params: &EclpParams,
+    derived: &DerivedEclpParams,
+    num_points: usize,
+) -> Result<EclpAnalytics, PoolError> {
+    if num_points < 2 {
+        return Err(PoolError::InvalidInput(
+            "at least two liquidity density points are required".to_string(),
+        ));
+    }
+
+    let (invariant, inv_err) =
+        calculate_invariant_with_error(balances_live_scaled18, params, derived)?;
+    // Same overestimate of the invariant as swaps use for their bounds
+    let r = Vector2 {
+        x: invariant + inv_err * I256::try_from(2).unwrap(),
+        y: invariant,
+    };
+
+    let current_price = calc_spot_price0in1(
+        balances_live_scaled18,
+        params,
+        derived,
+        &invariant.into_raw(),
+    );
+    let alpha = params.alpha.into_raw();
+    let beta = params.beta.into_raw();
+
+    let step = (beta - alpha) / U256::from(num_points - 1);
+    let liquidity_density = (0..num_points)
+        .map(|i| {
+            let price = if i == num_points - 1 {
+                beta
+            } else {
+                alpha + step * U256::from(i)
+            };
+            let price_step = price / DENSITY_PRICE_STEP_DIVISOR;
+            let lower_price = std::cmp::max(price - price_step, alpha);
+            let upper_price = std::cmp::min(price + price_step, beta);
+
+            let lower_balances = balances_at_price(params, derived, &r, &lower_price)?;
+            let upper_balances = balances_at_price(params, derived, &r, &upper_price)?;
+            let liquidity_density = upper_balances[1].saturating_sub(lower_balances[1])
+                * ONE.into_raw()
+                / (upper_price - lower_price);
+
+            Ok(EclpLiquidityPoint {
+                price,
+                balances: balances_at_price(params, derived, &r, &price)?,
+                liquidity_density,
+            })
+        })
+        .collect::<Result<Vec<_>, PoolError>>()?;
+
+    Ok(EclpAnalytics {
+        current_price,
+        is_at_alpha_bound: balances_live_scaled18[1].is_zero() || current_price <= alpha,
+        is_at_beta_bound: balances_live_scaled18[0].is_zero() || current_price >= beta,
+        balances_at_alpha: vec![to_balance(max_balances0(params, derived, &r)), U256::ZERO],
+        balances_at_beta: vec![U256::ZERO, to_balance(max_balances1(params, derived, &r))],
+        liquidity_density,
+    })
+}
@@ -1,4 +1,6 @@
+use crate::common::errors::PoolError;
 use crate::common::types::BasePoolState;
+use crate::pools::gyro::gyro_eclp_math::{compute_derived_params, EclpParams};
 use alloy_primitives::I256;
 use serde::{Deserialize, Serialize};
 
@@ -20,6 +22,42 @@ pub struct GyroECLPImmutable {
     pub d_sq: I256,
 }
 
+impl GyroECLPImmutable {
+    /// Build the immutable pool data from the base parameters, deriving and validating the rest
+    pub fn from_params(
+        alpha: I256,
+        beta: I256,
+        c: I256,
+        s: I256,
+        lambda: I256,
+    ) -> Result<Self, PoolError> {
+        let derived = compute_derived_params(&EclpParams {
+            alpha,
+            beta,
+            c,
+            s,
+            lambda,
+        })?;
+
+        Ok(Self {
+            alpha,
+            beta,
+            c,
+            s,
+            lambda,
+            tau_alpha_x: derived.tau_alpha.x,
+            tau_alpha_y: derived.tau_alpha.y,
+            tau_beta_x: derived.tau_beta.x,
+            tau_beta_y: derived.tau_beta.y,
+            u: derived.u,
+            v: derived.v,
+            w: derived.w,
+            z: derived.z,
+            d_sq: derived.d_sq,
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GyroECLPState {
     #[serde(flatten)]
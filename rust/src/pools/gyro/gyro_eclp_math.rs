@@ -3,9 +3,9 @@ use crate::common::maths::mul_up_fixed;
 use crate::pools::gyro::gyro_pool_math::gyro_pool_math_sqrt;
 use crate::pools::gyro::signed_fixed_point::{
     div_down_mag, div_up_mag, div_xp_u, mul_down_mag, mul_down_xp_to_np, mul_up_mag,
-    mul_up_xp_to_np, mul_xp_u, ONE_XP,
+    mul_up_xp_to_np, mul_xp_u, ONE, ONE_XP,
 };
-use alloy_primitives::{uint, I256, U256};
+use alloy_primitives::{uint, I256, U256, U512};
 use std::str::FromStr;
 
 // Constants matching Python implementation
@@ -75,10 +75,9 @@ fn scalar_prod(t1: &Vector2, t2: &Vector2) -> I256 {
     x_prod + y_prod
 }
 
-// commented out to avoid dead code warning
-// fn scalar_prod_xp(t1: &Vector2, t2: &Vector2) -> U256 {
-//     mul_xp(&t1.x, &t2.x) + mul_xp(&t1.y, &t2.y)
-// }
+fn scalar_prod_xp(t1: &Vector2, t2: &Vector2) -> I256 {
+    mul_xp_u(&t1.x, &t2.x) + mul_xp_u(&t1.y, &t2.y)
+}
 
 fn mul_a(params: &EclpParams, tp: &Vector2) -> Vector2 {
     // Calculate the numerator for x component using signed arithmetic
@@ -96,7 +95,8 @@ fn mul_a(params: &EclpParams, tp: &Vector2) -> Vector2 {
     }
 }
 
-fn virtual_offset0(p: &EclpParams, d: &DerivedEclpParams, r: &Vector2) -> I256 {
+/// Virtual offset `a` of token 0 for invariant `r`, rounded up
+pub fn virtual_offset0(p: &EclpParams, d: &DerivedEclpParams, r: &Vector2) -> I256 {
     let term_xp = div_xp_u(&d.tau_beta.x, &d.d_sq);
 
     let a = if d.tau_beta.x > I256::ZERO {
@@ -111,7 +111,8 @@ fn virtual_offset0(p: &EclpParams, d: &DerivedEclpParams, r: &Vector2) -> I256 {
     a + mul_up_xp_to_np(&mul_up_mag(&r.x, &p.s), &div_xp_u(&d.tau_beta.y, &d.d_sq))
 }
 
-fn virtual_offset1(p: &EclpParams, d: &DerivedEclpParams, r: &Vector2) -> I256 {
+/// Virtual offset `b` of token 1 for invariant `r`, rounded up
+pub fn virtual_offset1(p: &EclpParams, d: &DerivedEclpParams, r: &Vector2) -> I256 {
     let term_xp = div_xp_u(&d.tau_alpha.x, &d.d_sq);
 
     let b = if d.tau_alpha.x < I256::ZERO {
@@ -126,7 +127,8 @@ fn virtual_offset1(p: &EclpParams, d: &DerivedEclpParams, r: &Vector2) -> I256 {
     b + mul_up_xp_to_np(&mul_up_mag(&r.x, &p.c), &div_xp_u(&d.tau_alpha.y, &d.d_sq))
 }
 
-fn max_balances0(p: &EclpParams, d: &DerivedEclpParams, r: &Vector2) -> I256 {
+/// Balance of token 0 at the alpha bound for invariant `r`, where the pool holds no token 1
+pub fn max_balances0(p: &EclpParams, d: &DerivedEclpParams, r: &Vector2) -> I256 {
     let term_xp1 = div_xp_u(&(d.tau_beta.x - d.tau_alpha.x), &d.d_sq);
     let term_xp2 = div_xp_u(&(d.tau_beta.y - d.tau_alpha.y), &d.d_sq);
 
@@ -144,7 +146,8 @@ fn max_balances0(p: &EclpParams, d: &DerivedEclpParams, r: &Vector2) -> I256 {
     xp + mul_down_xp_to_np(&term2, &term_xp2)
 }
 
-fn max_balances1(p: &EclpParams, d: &DerivedEclpParams, r: &Vector2) -> I256 {
+/// Balance of token 1 at the beta bound for invariant `r`, where the pool holds no token 0
+pub fn max_balances1(p: &EclpParams, d: &DerivedEclpParams, r: &Vector2) -> I256 {
     let term_xp1 = div_xp_u(&(d.tau_beta.x - d.tau_alpha.x), &d.d_sq);
     let term_xp2 = div_xp_u(&(d.tau_alpha.y - d.tau_beta.y), &d.d_sq);
 
@@ -371,7 +374,7 @@ pub fn calc_spot_price0in1(
 
     let pc = Vector2 {
         x: div_down_mag(&transformed_vec.x, &transformed_vec.y),
-        y: ONE_XP,
+        y: ONE,
     };
 
     let pgx = scalar_prod(
@@ -379,7 +382,7 @@ pub fn calc_spot_price0in1(
         &mul_a(
             params,
             &Vector2 {
-                x: ONE_XP,
+                x: ONE,
                 y: zero_signed,
             },
         ),
@@ -391,7 +394,7 @@ pub fn calc_spot_price0in1(
             params,
             &Vector2 {
                 x: zero_signed,
-                y: ONE_XP,
+                y: ONE,
             },
         ),
     );
@@ -792,3 +795,150 @@ pub fn compute_balance(
         Ok(result.into_raw())
     }
 }
+
+/// Validate the base parameters of an ECLP pool, as the pool does on deployment
+pub fn validate_params(p: &EclpParams) -> Result<(), PoolError> {
+    if p.alpha <= I256::ZERO || p.beta <= p.alpha {
+        return Err(PoolError::InvalidInput("Price bounds wrong".to_string()));
+    }
+    if p.s < I256::ZERO || p.s > ONE {
+        return Err(PoolError::InvalidInput(
+            "Rotation vector s wrong".to_string(),
+        ));
+    }
+    if p.c < I256::ZERO || p.c > ONE {
+        return Err(PoolError::InvalidInput(
+            "Rotation vector c wrong".to_string(),
+        ));
+    }
+
+    let sc = Vector2 { x: p.s, y: p.c };
+    let sc_norm2 = scalar_prod(&sc, &sc);
+    let accuracy = I256::from_raw(_ROTATION_VECTOR_NORM_ACCURACY);
+    if sc_norm2 < ONE - accuracy || sc_norm2 > ONE + accuracy {
+        return Err(PoolError::InvalidInput(
+            "Rotation vector not normalized".to_string(),
+        ));
+    }
+
+    if p.lambda < I256::ZERO || p.lambda > I256::from_raw(_MAX_STRETCH_FACTOR) {
+        return Err(PoolError::InvalidInput(
+            "Stretching factor wrong".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validate the derived parameters of an ECLP pool against the limits the pool enforces
+pub fn validate_derived_params_limits(
+    p: &EclpParams,
+    d: &DerivedEclpParams,
+) -> Result<(), PoolError> {
+    let tau_accuracy = I256::from_raw(_DERIVED_TAU_NORM_ACCURACY_XP);
+    for (tau, name) in [(&d.tau_alpha, "alpha"), (&d.tau_beta, "beta")] {
+        let norm2 = scalar_prod_xp(tau, tau);
+        if norm2 < ONE_XP - tau_accuracy || norm2 > ONE_XP + tau_accuracy {
+            return Err(PoolError::InvalidInput(format!(
+                "Derived tau {} not normalized",
+                name
+            )));
+        }
+    }
+
+    for (value, name) in [(&d.u, "u"), (&d.v, "v"), (&d.w, "w"), (&d.z, "z")] {
+        if *value > ONE_XP {
+            return Err(PoolError::InvalidInput(format!("Derived {} wrong", name)));
+        }
+    }
+
+    let d_sq_accuracy = I256::from_raw(_DERIVED_DSQ_NORM_ACCURACY_XP);
+    if d.d_sq < ONE_XP - d_sq_accuracy || d.d_sq > ONE_XP + d_sq_accuracy {
+        return Err(PoolError::InvalidInput("Derived dSq wrong".to_string()));
+    }
+
+    let a_chi_a_chi_minus_one = calc_a_chi_a_chi_in_xp(p, d) - ONE_XP;
+    if a_chi_a_chi_minus_one <= I256::ZERO
+        || div_xp_u(&ONE_XP, &a_chi_a_chi_minus_one)
+            > I256::from_raw(_MAX_INV_INVARIANT_DENOMINATOR_XP)
+    {
+        return Err(PoolError::InvalidInput(
+            "Invariant denominator wrong".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn u512_to_i256(value: U512) -> Result<I256, PoolError> {
+    let limbs = value.as_limbs();
+    if limbs[4..].iter().any(|limb| *limb != 0) {
+        return Err(PoolError::MathOverflow);
+    }
+    I256::try_from(U256::from_limbs_slice(&limbs[..4])).map_err(|_| PoolError::MathOverflow)
+}
+
+/// Compute the point on the circle for price `px`, scaled by the norm of `(c, s)` (38 decimals)
+///
+/// This is `eta(zeta(px)) * sqrt(c^2 + s^2)`, the form the deployed pools use. Intermediates
+/// are kept in 512 bits so the normalization does not lose precision.
+pub(crate) fn tau(p: &EclpParams, px: &I256) -> Result<Vector2, PoolError> {
+    // zeta(px) = lambda * (c * px - s) / (c + s * px), kept as a numerator and denominator with
+    // 54 decimals
+    let numerator = p.lambda * (p.c * *px - p.s * ONE);
+    let denominator = (p.c * ONE + p.s * *px) * ONE;
+    if denominator <= I256::ZERO {
+        return Err(PoolError::InvalidInput("Price bounds wrong".to_string()));
+    }
+
+    let numerator_abs = U512::from(numerator.unsigned_abs());
+    let denominator = U512::from(denominator.into_raw());
+    let norm = (numerator_abs * numerator_abs + denominator * denominator).root(2);
+
+    // sqrt(c^2 + s^2) with 38 decimals
+    let d_sq = U512::from((p.c * p.c + p.s * p.s).into_raw());
+    let scale = (d_sq * U512::from(10u64).pow(U512::from(40u64))).root(2);
+
+    let x_abs = u512_to_i256(numerator_abs * scale / norm)?;
+    let y = u512_to_i256(denominator * scale / norm)?;
+
+    Ok(Vector2 {
+        x: if numerator < I256::ZERO {
+            -x_abs
+        } else {
+            x_abs
+        },
+        y,
+    })
+}
+
+/// Compute the derived parameters of an ECLP pool from its base parameters (38 decimals)
+///
+/// The base parameters are validated first and the derived ones are checked against the
+/// pool's limits, so the result can be used to model a pool before it is deployed.
+pub fn compute_derived_params(p: &EclpParams) -> Result<DerivedEclpParams, PoolError> {
+    validate_params(p)?;
+
+    let tau_alpha = tau(p, &p.alpha)?;
+    let tau_beta = tau(p, &p.beta)?;
+
+    // 18 decimals * 18 decimals * 38 decimals, back to 38 decimals
+    let one_36 = ONE * ONE;
+    let sc = p.s * p.c;
+    let c_sq = p.c * p.c;
+    let s_sq = p.s * p.s;
+
+    let derived = DerivedEclpParams {
+        u: sc * (tau_beta.x - tau_alpha.x) / one_36,
+        v: (s_sq * tau_beta.y + c_sq * tau_alpha.y) / one_36,
+        w: sc * (tau_beta.y - tau_alpha.y) / one_36,
+        z: (c_sq * tau_beta.x + s_sq * tau_alpha.x) / one_36,
+        d_sq: (c_sq + s_sq) * (ONE_XP / one_36),
+        tau_alpha,
+        tau_beta,
+    };
+
+    validate_derived_params_limits(p, &derived)?;
+
+    Ok(derived)
+}
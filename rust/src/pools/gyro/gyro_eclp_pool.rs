@@ -1,6 +1,7 @@
 use crate::common::errors::PoolError;
 use crate::common::pool_base::PoolBase;
 use crate::common::types::{Rounding, SwapKind, SwapParams};
+use crate::pools::gyro::gyro_eclp_analytics::{compute_eclp_analytics, EclpAnalytics};
 use crate::pools::gyro::gyro_eclp_data::GyroECLPImmutable;
 use crate::pools::gyro::gyro_eclp_math::{
     calc_in_given_out, calc_out_given_in, calculate_invariant_with_error, compute_balance,
@@ -12,6 +13,8 @@ use alloy_primitives::{I256, U256};
 pub struct GyroECLPPool {
     pub params: EclpParams,
     pub derived: DerivedEclpParams,
+    /// Invariant and error computed by `prepare`, keyed by the balances they were computed for
+    prepared_invariant: Option<(Vec<U256>, (I256, I256))>,
 }
 
 impl GyroECLPPool {
@@ -41,7 +44,50 @@ impl GyroECLPPool {
             d_sq: immutable.d_sq,
         };
 
-        Self { params, derived }
+        Self {
+            params,
+            derived,
+            prepared_invariant: None,
+        }
+    }
+
+    /// Compute the invariant for `balances_live_scaled18` once, so repeated swaps against
+    /// the same balances skip `calculate_invariant_with_error`
+    pub fn prepare(&mut self, balances_live_scaled18: &[U256]) -> Result<(), PoolError> {
+        let invariant =
+            calculate_invariant_with_error(balances_live_scaled18, &self.params, &self.derived)?;
+        self.prepared_invariant = Some((balances_live_scaled18.to_vec(), invariant));
+        Ok(())
+    }
+
+    /// Compute the current price, the balances at both price bounds and `num_points` samples
+    /// of the liquidity density across [alpha, beta]
+    pub fn analytics(
+        &self,
+        balances_live_scaled18: &[U256],
+        num_points: usize,
+    ) -> Result<EclpAnalytics, PoolError> {
+        compute_eclp_analytics(
+            balances_live_scaled18,
+            &self.params,
+            &self.derived,
+            num_points,
+        )
+    }
+
+    /// Get the invariant and its error, reusing the prepared value when balances match
+    fn invariant_with_error(
+        &self,
+        balances_live_scaled18: &[U256],
+    ) -> Result<(I256, I256), PoolError> {
+        match &self.prepared_invariant {
+            Some((balances, invariant)) if balances.as_slice() == balances_live_scaled18 => {
+                Ok(*invariant)
+            }
+            _ => {
+                calculate_invariant_with_error(balances_live_scaled18, &self.params, &self.derived)
+            }
+        }
     }
 }
 
@@ -58,11 +104,8 @@ impl PoolBase for GyroECLPPool {
 
     fn on_swap(&self, swap_params: &SwapParams) -> Result<U256, PoolError> {
         // Calculate current invariant with error for swaps (matching Python pattern)
-        let (current_invariant, inv_err) = calculate_invariant_with_error(
-            &swap_params.balances_live_scaled_18,
-            &self.params,
-            &self.derived,
-        )?;
+        let (current_invariant, inv_err) =
+            self.invariant_with_error(&swap_params.balances_live_scaled_18)?;
 
         let invariant_x_result = current_invariant + inv_err * I256::try_from(2).unwrap();
         let invariant = Vector2 {
@@ -97,8 +140,7 @@ impl PoolBase for GyroECLPPool {
         balances_live_scaled18: &[U256],
         rounding: Rounding,
     ) -> Result<U256, PoolError> {
-        let (current_invariant, inv_err) =
-            calculate_invariant_with_error(balances_live_scaled18, &self.params, &self.derived)?;
+        let (current_invariant, inv_err) = self.invariant_with_error(balances_live_scaled18)?;
         match rounding {
             Rounding::RoundDown => Ok((current_invariant - inv_err).into_raw()),
             Rounding::RoundUp => Ok((current_invariant + inv_err).into_raw()),
@@ -1,7 +1,9 @@
+pub mod gyro_eclp_analytics;
 pub mod gyro_eclp_data;
 pub mod gyro_eclp_math;
 pub mod gyro_pool_math;
 pub mod signed_fixed_point;
+pub use gyro_eclp_analytics::*;
 pub use gyro_eclp_data::*;
 pub use gyro_eclp_math::*;
 mod gyro_eclp_pool;
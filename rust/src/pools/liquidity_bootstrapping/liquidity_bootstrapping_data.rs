@@ -20,6 +20,18 @@ pub struct LiquidityBootstrappingImmutable {
     pub end_time: U256,
 }
 
+/// Weights and zero-trade project token price of a Liquidity Bootstrapping pool at a timestamp
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiquidityBootstrappingPricePoint {
+    /// Timestamp the weights are interpolated at
+    pub timestamp: U256,
+    /// Normalized weights of the tokens (scaled 18)
+    pub normalized_weights: Vec<U256>,
+    /// Spot price of one project token in reserve tokens, excluding fees and undoing token
+    /// rates (18 decimals)
+    pub project_token_price: U256,
+}
+
 /// Liquidity Bootstrapping pool state
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LiquidityBootstrappingState {
@@ -1,9 +1,12 @@
 //! Liquidity Bootstrapping pool implementation
 
+use crate::common::constants::WAD;
 use crate::common::errors::PoolError;
 use crate::common::pool_base::PoolBase;
 use crate::common::types::{Rounding, SwapParams};
-use crate::pools::liquidity_bootstrapping::liquidity_bootstrapping_data::LiquidityBootstrappingState;
+use crate::pools::liquidity_bootstrapping::liquidity_bootstrapping_data::{
+    LiquidityBootstrappingPricePoint, LiquidityBootstrappingState,
+};
 use crate::pools::liquidity_bootstrapping::liquidity_bootstrapping_math::get_normalized_weights;
 use crate::pools::weighted::weighted_math::{MAX_INVARIANT_RATIO, MIN_INVARIANT_RATIO, *};
 use alloy_primitives::U256;
@@ -49,6 +52,58 @@ impl LiquidityBootstrappingPool {
         self.state.immutable.is_project_token_swap_in_blocked
     }
 
+    /// Get the current normalized weights
+    pub fn normalized_weights(&self) -> &[U256] {
+        &self.normalized_weights
+    }
+
+    /// Get the weight schedule and the zero-trade spot price of the project token at each of
+    /// `timestamps`
+    ///
+    /// Balances are held at their current values, so the price only moves with the weights.
+    /// The price is in reserve tokens per project token: live balances already apply the
+    /// scaling factors, and token rates are undone.
+    pub fn price_curve(
+        &self,
+        timestamps: &[U256],
+    ) -> Result<Vec<LiquidityBootstrappingPricePoint>, PoolError> {
+        let project_token_index = self.state.immutable.project_token_index;
+        let reserve_token_index = 1 - project_token_index;
+        let base = &self.state.base;
+        let balance_project = base.balances_live_scaled_18[project_token_index];
+        let balance_reserve = base.balances_live_scaled_18[reserve_token_index];
+        if balance_project.is_zero() || balance_reserve.is_zero() {
+            return Err(PoolError::ZeroInvariant);
+        }
+
+        timestamps
+            .iter()
+            .map(|timestamp| {
+                let normalized_weights = get_normalized_weights(
+                    project_token_index,
+                    timestamp,
+                    &self.state.immutable.start_time,
+                    &self.state.immutable.end_time,
+                    &self.state.immutable.start_weights[project_token_index],
+                    &self.state.immutable.end_weights[project_token_index],
+                );
+
+                // (balanceReserve / weightReserve) / (balanceProject / weightProject)
+                let price_scaled_18 =
+                    balance_reserve * normalized_weights[project_token_index] * WAD
+                        / (balance_project * normalized_weights[reserve_token_index]);
+                let project_token_price = price_scaled_18 * base.token_rates[project_token_index]
+                    / base.token_rates[reserve_token_index];
+
+                Ok(LiquidityBootstrappingPricePoint {
+                    timestamp: *timestamp,
+                    normalized_weights,
+                    project_token_price,
+                })
+            })
+            .collect()
+    }
+
     /// Get normalized weights for a specific token pair
     fn get_normalized_weight_pair(
         &self,
@@ -12,7 +12,9 @@ pub mod weighted;
 
 // Re-export pool traits and types
 pub use buffer::{
-    erc4626_buffer_wrap_or_unwrap, BufferImmutable, BufferMutable, BufferState, WrappingDirection,
+    erc4626_buffer_wrap_or_unwrap, erc4626_buffer_wrap_or_unwrap_with_state, BufferImmutable,
+    BufferMutable, BufferState, BufferWrapOrUnwrapPath, BufferWrapOrUnwrapResult,
+    WrappingDirection,
 };
 pub use fixed_price_lbp::{
     FixedPriceLBPImmutable, FixedPriceLBPMutable, FixedPriceLBPPool, FixedPriceLBPState,
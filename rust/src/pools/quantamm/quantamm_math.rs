@@ -95,3 +95,70 @@ pub fn get_second_four_weights_and_multipliers(
 
     (weights, multipliers)
 }
+
+/// Precision of the packed weights and multipliers, which are stored with 9 decimals on chain
+pub const PACKED_PRECISION: I256 = I256::from_raw(uint!(1000000000_U256)); // 1e9
+
+/// Number of weights and multipliers held by each packed array
+pub const PACKED_ARRAY_LENGTH: usize = 8;
+
+/// Calculate the per-second multiplier that moves a weight to its target over the update interval
+///
+/// # Arguments
+/// * `current_weight` - The weight at the time of the update
+/// * `target_weight` - The weight to reach at the end of the update interval
+/// * `update_interval` - The update interval in seconds
+///
+/// # Returns
+/// The weight multiplier, rounded towards zero
+pub fn calculate_block_multiplier(
+    current_weight: &I256,
+    target_weight: &I256,
+    update_interval: &U256,
+) -> I256 {
+    (*target_weight - *current_weight) / I256::from_raw(*update_interval)
+}
+
+/// Truncate a weight or multiplier towards zero to the precision it is packed with
+pub fn truncate_to_packed_precision(value: &I256) -> I256 {
+    *value / PACKED_PRECISION * PACKED_PRECISION
+}
+
+/// Pack weights and multipliers into the first four and second four arrays
+///
+/// Inverse of `get_first_four_weights_and_multipliers` and
+/// `get_second_four_weights_and_multipliers`. Each array holds the weights of its tokens
+/// followed by their multipliers, padded with zeros.
+///
+/// # Arguments
+/// * `weights` - The weights of all tokens
+/// * `multipliers` - The multipliers of all tokens
+///
+/// # Returns
+/// Tuple of (first_four_weights_and_multipliers, second_four_weights_and_multipliers)
+pub fn pack_weights_and_multipliers(
+    weights: &[I256],
+    multipliers: &[I256],
+) -> (Vec<I256>, Vec<I256>) {
+    let less_than_4_tokens_offset = weights.len().min(4);
+
+    let mut first_four_weights_and_multipliers = vec![I256::ZERO; PACKED_ARRAY_LENGTH];
+    first_four_weights_and_multipliers[..less_than_4_tokens_offset]
+        .copy_from_slice(&weights[..less_than_4_tokens_offset]);
+    first_four_weights_and_multipliers
+        [less_than_4_tokens_offset..(less_than_4_tokens_offset + less_than_4_tokens_offset)]
+        .copy_from_slice(&multipliers[..less_than_4_tokens_offset]);
+
+    let mut second_four_weights_and_multipliers = vec![I256::ZERO; PACKED_ARRAY_LENGTH];
+    let more_than_4_tokens_offset = weights.len() - less_than_4_tokens_offset;
+    second_four_weights_and_multipliers[..more_than_4_tokens_offset]
+        .copy_from_slice(&weights[less_than_4_tokens_offset..]);
+    second_four_weights_and_multipliers
+        [more_than_4_tokens_offset..(more_than_4_tokens_offset + more_than_4_tokens_offset)]
+        .copy_from_slice(&multipliers[less_than_4_tokens_offset..]);
+
+    (
+        first_four_weights_and_multipliers,
+        second_four_weights_and_multipliers,
+    )
+}
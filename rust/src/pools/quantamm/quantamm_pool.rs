@@ -6,16 +6,24 @@ use crate::common::pool_base::PoolBase;
 use crate::common::types::{Rounding, SwapParams};
 use crate::pools::quantamm::quantamm_data::QuantAmmState;
 use crate::pools::quantamm::quantamm_math::{
-    calculate_block_normalised_weight, get_first_four_weights_and_multipliers,
-    get_second_four_weights_and_multipliers,
+    calculate_block_multiplier, calculate_block_normalised_weight,
+    get_first_four_weights_and_multipliers, get_second_four_weights_and_multipliers,
+    pack_weights_and_multipliers, truncate_to_packed_precision, ONE,
 };
 use crate::pools::weighted::weighted_math::{MAX_INVARIANT_RATIO, MIN_INVARIANT_RATIO, *};
 use alloy_primitives::{I256, U256};
 
+/// Last interpolation time set when no weight is moving, the largest uint40 timestamp
+const MAX_LAST_INTEROP_TIME: u64 = (1 << 40) - 1;
+
 /// QuantAmm pool implementation
 pub struct QuantAmmPool {
     /// Current normalized weights (scaled 18) based on time interpolation
     normalized_weights: Vec<U256>,
+    /// Weights at the last update (scaled 18)
+    base_weights: Vec<I256>,
+    /// Per-second weight multipliers since the last update (scaled 18)
+    multipliers: Vec<I256>,
     /// Pool state
     state: QuantAmmState,
 }
@@ -51,6 +59,8 @@ impl QuantAmmPool {
 
         Ok(Self {
             normalized_weights,
+            base_weights,
+            multipliers,
             state,
         })
     }
@@ -85,6 +95,139 @@ impl QuantAmmPool {
         normalized_weights
     }
 
+    /// Get the current normalized weights
+    pub fn normalized_weights(&self) -> &[U256] {
+        &self.normalized_weights
+    }
+
+    /// Get the interpolated normalized weights at a timestamp
+    ///
+    /// Weights move linearly from the last update and are held once `last_interop_time` is
+    /// reached. Timestamps before the last update are rejected.
+    pub fn weights_at(&self, timestamp: &U256) -> Result<Vec<U256>, PoolError> {
+        if timestamp < &self.state.mutable.last_update_time {
+            return Err(PoolError::InvalidInput(
+                "Timestamp is before the last weight update".to_string(),
+            ));
+        }
+
+        Ok(Self::calculate_normalized_weights(
+            &self.base_weights,
+            &self.multipliers,
+            &self.state.mutable.last_update_time,
+            &self.state.mutable.last_interop_time,
+            timestamp,
+        ))
+    }
+
+    /// Get the interpolated normalized weights at each of `timestamps`
+    pub fn weight_trajectory(&self, timestamps: &[U256]) -> Result<Vec<Vec<U256>>, PoolError> {
+        timestamps
+            .iter()
+            .map(|timestamp| self.weights_at(timestamp))
+            .collect()
+    }
+
+    /// Apply a weight update at the current timestamp, as the update weight runner does
+    ///
+    /// The current interpolated weights become the base weights, with multipliers that reach
+    /// `target_weights` after `update_interval` seconds. Both are truncated to the 9 decimals
+    /// they are packed with. Interpolation stops when the first weight would cross its guard
+    /// rail: `absolute_weight_guard_rail` below, or one less the guard rail of every other
+    /// token above.
+    ///
+    /// # Arguments
+    /// * `target_weights` - The target weights (scaled 18), summing to one
+    /// * `update_interval` - The update interval in seconds
+    /// * `absolute_weight_guard_rail` - The minimum weight of any token (scaled 18)
+    ///
+    /// # Returns
+    /// The pool state after the update
+    pub fn apply_weight_update(
+        &self,
+        target_weights: &[U256],
+        update_interval: &U256,
+        absolute_weight_guard_rail: &U256,
+    ) -> Result<QuantAmmState, PoolError> {
+        let num_tokens = self.normalized_weights.len();
+        if target_weights.len() != num_tokens {
+            return Err(PoolError::InvalidInput(
+                "Target weights length mismatch".to_string(),
+            ));
+        }
+        if update_interval.is_zero() {
+            return Err(PoolError::InvalidInput(
+                "Update interval must be positive".to_string(),
+            ));
+        }
+        if absolute_weight_guard_rail * U256::from(num_tokens) > ONE {
+            return Err(PoolError::InvalidInput(
+                "Guard rail exceeds the weight of each token".to_string(),
+            ));
+        }
+        let upper_guard_rail = ONE - absolute_weight_guard_rail * U256::from(num_tokens - 1);
+        if target_weights
+            .iter()
+            .fold(U256::ZERO, |sum, weight| sum + weight)
+            != ONE
+        {
+            return Err(PoolError::InvalidInput(
+                "Target weights must sum to one".to_string(),
+            ));
+        }
+        if target_weights
+            .iter()
+            .any(|weight| weight < absolute_weight_guard_rail || weight > &upper_guard_rail)
+        {
+            return Err(PoolError::InvalidInput(
+                "Target weight outside the guard rails".to_string(),
+            ));
+        }
+
+        let now = self.state.mutable.current_timestamp;
+        let mut base_weights = Vec::with_capacity(num_tokens);
+        let mut multipliers = Vec::with_capacity(num_tokens);
+        let mut time_until_guard_rail = U256::from(MAX_LAST_INTEROP_TIME);
+
+        for (current_weight, target_weight) in self.normalized_weights.iter().zip(target_weights) {
+            let current_weight = I256::from_raw(*current_weight);
+            let multiplier = truncate_to_packed_precision(&calculate_block_multiplier(
+                &current_weight,
+                &I256::from_raw(*target_weight),
+                update_interval,
+            ));
+            let base_weight = truncate_to_packed_precision(&current_weight);
+
+            // Time for the packed weight to reach the guard rail it is moving towards
+            let time = if multiplier > I256::ZERO {
+                upper_guard_rail.saturating_sub(base_weight.into_raw()) / multiplier.into_raw()
+            } else if multiplier < I256::ZERO {
+                base_weight
+                    .into_raw()
+                    .saturating_sub(*absolute_weight_guard_rail)
+                    / (-multiplier).into_raw()
+            } else {
+                U256::from(MAX_LAST_INTEROP_TIME)
+            };
+            time_until_guard_rail = time_until_guard_rail.min(time);
+
+            base_weights.push(base_weight);
+            multipliers.push(multiplier);
+        }
+
+        let (first_four_weights_and_multipliers, second_four_weights_and_multipliers) =
+            pack_weights_and_multipliers(&base_weights, &multipliers);
+
+        let mut state = self.state.clone();
+        state.mutable.first_four_weights_and_multipliers = first_four_weights_and_multipliers;
+        state.mutable.second_four_weights_and_multipliers = second_four_weights_and_multipliers;
+        state.mutable.last_update_time = now;
+        state.mutable.last_interop_time =
+            (now + time_until_guard_rail).min(U256::from(MAX_LAST_INTEROP_TIME));
+
+        Ok(state)
+    }
+
     /// Get normalized weights for a specific token pair
     fn get_normalized_weight_pair(
         &self,
@@ -101,7 +244,7 @@ impl QuantAmmPool {
         Ok((token_in_weight, token_out_weight))
     }
 
-    /// Check if trade size exceeds max trade size ratio
+    /// Check if trade size exceeds max trade size ratio of the balance it trades against
     fn check_max_trade_size(
         &self,
         amount_scaled_18: &U256,
@@ -114,7 +257,10 @@ impl QuantAmmPool {
         .unwrap_or(U256::ZERO);
 
         if amount_scaled_18 > &max_amount {
-            return Err(PoolError::InvalidSwapParameters);
+            return Err(PoolError::MaxTradeSizeRatioExceeded {
+                amount_scaled_18: *amount_scaled_18,
+                max_amount_scaled_18: max_amount,
+            });
         }
 
         Ok(())
@@ -208,7 +354,13 @@ impl PoolBase for QuantAmmPool {
         let weight = &self.normalized_weights[token_in_index];
 
         // Calculate the new balance based on the invariant ratio
-        compute_balance_out_given_invariant(current_balance, weight, invariant_ratio)
+        let new_balance =
+            compute_balance_out_given_invariant(current_balance, weight, invariant_ratio)?;
+
+        // Single token adds and removes trade the balance change against the rest of the pool
+        self.check_max_trade_size(&new_balance.abs_diff(*current_balance), current_balance)?;
+
+        Ok(new_balance)
     }
 
     fn get_maximum_invariant_ratio(&self) -> U256 {
@@ -32,6 +32,49 @@ pub struct ReClammImmutable {
     pub tokens: Vec<String>,
 }
 
+/// Initial price range and target price of a ReClamm pool (scaled 18), as configured when the
+/// pool is created. Also used for ReClammV2 pools.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReClammPriceParams {
+    #[serde(rename = "initialMinPrice")]
+    pub initial_min_price: U256,
+    #[serde(rename = "initialMaxPrice")]
+    pub initial_max_price: U256,
+    #[serde(rename = "initialTargetPrice")]
+    pub initial_target_price: U256,
+}
+
+/// Status of a ReClamm pool at its current timestamp (scaled 18). Also used for ReClammV2 pools,
+/// which re-export it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReClammStatus {
+    /// Timestamp the status is computed at
+    pub timestamp: U256,
+    /// Current virtual balances of tokens A and B
+    pub virtual_balances: Vec<U256>,
+    /// Lowest price of token A in token B in the current range
+    pub min_price: U256,
+    /// Highest price of token A in token B in the current range
+    pub max_price: U256,
+    /// Current spot price of token A in token B, from the real and virtual balances
+    pub spot_price: U256,
+    /// Target price of the range, its geometric center `sqrt(min_price * max_price)`, which is
+    /// the spot price at a centeredness of 1
+    pub target_price: U256,
+    /// Current fourth root of the price ratio, interpolated during a price ratio update
+    pub fourth_root_price_ratio: U256,
+    /// Centeredness of the pool, between 0 (at an edge of the range) and 1 (at its center)
+    pub centeredness: U256,
+    /// Centeredness below which the range starts moving towards the market price
+    pub centeredness_margin: U256,
+    /// Whether the pool holds relatively more token A than at the center of the range
+    pub is_pool_above_center: bool,
+    /// Whether the centeredness is at least the margin, so the range is not moving
+    pub is_pool_in_range: bool,
+    /// Whether the price ratio is being updated at the current timestamp
+    pub is_price_ratio_updating: bool,
+}
+
 /// ReClamm pool state
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ReClammState {
@@ -4,11 +4,16 @@ use crate::common::maths::{div_down_fixed, div_up_fixed, mul_down_fixed, mul_up_
 use crate::common::oz_math::sqrt;
 use crate::common::types::Rounding;
 use crate::common::WAD;
-use alloy_primitives::U256;
+use crate::pools::reclamm::reclamm_data::ReClammStatus;
+use alloy_primitives::{uint, U256};
 
 // Constants
 const A: usize = 0;
 const B: usize = 1;
+// Maximum real balance of token A used for the theoretical initial balances (1e6 tokens)
+const INITIALIZATION_MAX_BALANCE_A: U256 = uint!(1_000_000_000_000_000_000_000_000_U256);
+// Tolerance of the initial balance ratio (0.01%)
+const BALANCE_RATIO_AND_PRICE_TOLERANCE: U256 = uint!(100_000_000_000_000_U256);
 
 /// Compute current virtual balances for ReClamm pool
 #[allow(clippy::too_many_arguments)]
@@ -209,7 +214,7 @@ fn compute_virtual_balances_updating_price_range(
 }
 
 /// Compute price ratio
-fn compute_price_ratio(
+pub fn compute_price_ratio(
     balances_scaled_18: &[U256],
     virtual_balance_a: &U256,
     virtual_balance_b: &U256,
@@ -221,7 +226,7 @@ fn compute_price_ratio(
 }
 
 /// Compute price range
-fn compute_price_range(
+pub fn compute_price_range(
     balances_scaled_18: &[U256],
     virtual_balance_a: &U256,
     virtual_balance_b: &U256,
@@ -247,7 +252,7 @@ fn compute_price_range(
 }
 
 /// Compute fourth root price ratio
-fn compute_fourth_root_price_ratio(
+pub fn compute_fourth_root_price_ratio(
     current_timestamp: &U256,
     start_fourth_root_price_ratio: &U256,
     end_fourth_root_price_ratio: &U256,
@@ -293,7 +298,7 @@ fn compute_fourth_root_price_ratio(
 }
 
 /// Compute centeredness of the pool
-fn compute_centeredness(
+pub fn compute_centeredness(
     balances_scaled_18: &[U256],
     virtual_balance_a: &U256,
     virtual_balance_b: &U256,
@@ -321,6 +326,157 @@ fn compute_centeredness(
     }
 }
 
+/// Compute the status of a pool from its current virtual balances, shared by both versions of
+/// the pool. The fourth root price ratio is passed in, as each version interpolates its own.
+#[allow(clippy::too_many_arguments)]
+pub fn compute_status(
+    current_timestamp: &U256,
+    balances_scaled_18: &[U256],
+    virtual_balance_a: &U256,
+    virtual_balance_b: &U256,
+    fourth_root_price_ratio: &U256,
+    centeredness_margin: &U256,
+    price_ratio_update_start_time: &U256,
+    price_ratio_update_end_time: &U256,
+) -> ReClammStatus {
+    let (min_price, max_price) =
+        compute_price_range(balances_scaled_18, virtual_balance_a, virtual_balance_b);
+    let (centeredness, is_pool_above_center) =
+        compute_centeredness(balances_scaled_18, virtual_balance_a, virtual_balance_b);
+
+    ReClammStatus {
+        timestamp: *current_timestamp,
+        virtual_balances: vec![*virtual_balance_a, *virtual_balance_b],
+        min_price,
+        max_price,
+        spot_price: (balances_scaled_18[B] + virtual_balance_b) * WAD
+            / (balances_scaled_18[A] + virtual_balance_a),
+        target_price: sqrt(&(min_price * max_price)),
+        fourth_root_price_ratio: *fourth_root_price_ratio,
+        centeredness,
+        centeredness_margin: *centeredness_margin,
+        is_pool_above_center,
+        is_pool_in_range: centeredness >= *centeredness_margin,
+        is_price_ratio_updating: current_timestamp >= price_ratio_update_start_time
+            && current_timestamp < price_ratio_update_end_time,
+    }
+}
+
+/// Compute the theoretical real balances, virtual balances and fourth root price ratio of a
+/// ReClamm pool for its initial price range and target price
+///
+/// Balances are computed for an arbitrary maximum balance of token A, and must be scaled to the
+/// liquidity actually provided at initialization.
+pub fn compute_theoretical_price_ratio_and_balances(
+    min_price: &U256,
+    max_price: &U256,
+    target_price: &U256,
+) -> Result<(Vec<U256>, U256, U256, U256), String> {
+    if min_price.is_zero()
+        || max_price <= min_price
+        || target_price < min_price
+        || target_price > max_price
+    {
+        return Err("InvalidInitialPrice".to_string());
+    }
+
+    let price_ratio = div_down_fixed(max_price, min_price).map_err(|e| e.to_string())?;
+    let sqrt_price_ratio = sqrt(&(price_ratio * WAD));
+    if sqrt_price_ratio <= WAD {
+        return Err("InvalidInitialPrice".to_string());
+    }
+    let fourth_root_price_ratio = sqrt(&(sqrt_price_ratio * WAD));
+
+    // Va = Ra_max / (sqrtPriceRatio - 1)
+    let virtual_balance_a =
+        div_down_fixed(&INITIALIZATION_MAX_BALANCE_A, &(sqrt_price_ratio - WAD))
+            .map_err(|e| e.to_string())?;
+    // Vb = minPrice * (Va + Ra_max)
+    let virtual_balance_b = mul_down_fixed(
+        min_price,
+        &(virtual_balance_a + INITIALIZATION_MAX_BALANCE_A),
+    )
+    .map_err(|e| e.to_string())?;
+
+    // Rb = sqrt(targetPrice * Vb * (Ra_max + Va)) - Vb
+    let invariant_times_price = mul_up_fixed(
+        &mul_up_fixed(target_price, &virtual_balance_b).map_err(|e| e.to_string())?,
+        &(INITIALIZATION_MAX_BALANCE_A + virtual_balance_a),
+    )
+    .map_err(|e| e.to_string())?;
+    let real_balance_b = sqrt(&(invariant_times_price * WAD))
+        .checked_sub(virtual_balance_b)
+        .ok_or_else(|| "InvalidInitialPrice".to_string())?;
+    // Ra = (Rb + Vb - (Va * targetPrice)) / targetPrice
+    let real_balance_a = div_down_fixed(
+        &(real_balance_b + virtual_balance_b
+            - mul_down_fixed(&virtual_balance_a, target_price).map_err(|e| e.to_string())?),
+        target_price,
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok((
+        vec![real_balance_a, real_balance_b],
+        virtual_balance_a,
+        virtual_balance_b,
+        fourth_root_price_ratio,
+    ))
+}
+
+/// Compute the initial virtual balances and fourth root price ratio of a ReClamm pool
+///
+/// The initial balances (scaled 18) must match the ratio of the theoretical balances for the
+/// target price within a 0.01% tolerance, as checked by the pool when it is initialized.
+pub fn compute_initial_virtual_balances(
+    balances_scaled_18: &[U256],
+    min_price: &U256,
+    max_price: &U256,
+    target_price: &U256,
+) -> Result<(U256, U256, U256), String> {
+    let (
+        theoretical_real_balances,
+        theoretical_virtual_balance_a,
+        theoretical_virtual_balance_b,
+        fourth_root_price_ratio,
+    ) = compute_theoretical_price_ratio_and_balances(min_price, max_price, target_price)?;
+
+    if balances_scaled_18[A].is_zero() || theoretical_real_balances[A].is_zero() {
+        return Err("BalanceRatioExceedsTolerance".to_string());
+    }
+    let real_balance_ratio = div_down_fixed(&balances_scaled_18[B], &balances_scaled_18[A])
+        .map_err(|e| e.to_string())?;
+    let theoretical_balance_ratio =
+        div_down_fixed(&theoretical_real_balances[B], &theoretical_real_balances[A])
+            .map_err(|e| e.to_string())?;
+    let ratio_lower_bound = mul_down_fixed(
+        &theoretical_balance_ratio,
+        &(WAD - BALANCE_RATIO_AND_PRICE_TOLERANCE),
+    )
+    .map_err(|e| e.to_string())?;
+    let ratio_upper_bound = mul_down_fixed(
+        &theoretical_balance_ratio,
+        &(WAD + BALANCE_RATIO_AND_PRICE_TOLERANCE),
+    )
+    .map_err(|e| e.to_string())?;
+    if real_balance_ratio < ratio_lower_bound || real_balance_ratio > ratio_upper_bound {
+        return Err("BalanceRatioExceedsTolerance".to_string());
+    }
+
+    // Scale the theoretical virtual balances to the liquidity provided
+    let scale = div_down_fixed(&balances_scaled_18[A], &theoretical_real_balances[A])
+        .map_err(|e| e.to_string())?;
+    let virtual_balance_a =
+        mul_down_fixed(&theoretical_virtual_balance_a, &scale).map_err(|e| e.to_string())?;
+    let virtual_balance_b =
+        mul_down_fixed(&theoretical_virtual_balance_b, &scale).map_err(|e| e.to_string())?;
+
+    Ok((
+        virtual_balance_a,
+        virtual_balance_b,
+        fourth_root_price_ratio,
+    ))
+}
+
 /// Compute invariant for ReClamm pool
 pub fn compute_invariant(
     balances_scaled_18: &[U256],
@@ -1,26 +1,7 @@
+use crate::common::utils::u256_to_f64;
 use alloy_primitives::U256;
 
-/// Result struct for swap to target price calculation
-#[derive(Debug, Clone)]
-pub struct SwapToTargetPriceResult {
-    pub token_in_index: usize,
-    pub token_out_index: usize,
-    pub amount_in_raw: U256,
-    pub amount_out_raw: U256,
-}
-
-/// Helper function to convert U256 to f64
-/// Handles large numbers by converting to string and parsing
-fn u256_to_f64(value: &U256) -> f64 {
-    // For values that fit in u128, use direct conversion
-    if let Ok(val) = u128::try_from(*value) {
-        val as f64
-    } else {
-        // For very large numbers, convert via string
-        // This may lose precision but is acceptable for our use case
-        value.to_string().parse::<f64>().unwrap_or(f64::MAX)
-    }
-}
+pub use crate::common::types::SwapToTargetPriceResult;
 
 /// Calculate current ReCLAMM price
 /// Returns price in e18 format: (balanceB + virtualB) / (balanceA + virtualA)
@@ -2,6 +2,9 @@ use crate::common::types::BasePoolState;
 use alloy_primitives::U256;
 use serde::{Deserialize, Serialize};
 
+// Status is the same in both versions of the pool
+pub use crate::pools::reclamm::reclamm_data::ReClammStatus;
+
 /// ReClammV2 mutable state
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ReClammV2Mutable {
@@ -7,6 +7,11 @@ use crate::common::oz_math::sqrt;
 use crate::common::types::Rounding;
 use alloy_primitives::{uint, U256};
 
+// Initialization is the same in both versions of the pool
+pub use crate::pools::reclamm::reclamm_math::{
+    compute_initial_virtual_balances, compute_theoretical_price_ratio_and_balances,
+};
+
 // Constants
 const A: usize = 0;
 const B: usize = 1;
@@ -253,7 +258,7 @@ fn compute_virtual_balances_updating_price_range(
 }
 
 /// Compute price ratio
-fn compute_price_ratio(
+pub fn compute_price_ratio(
     balances_scaled_18: &[U256],
     virtual_balance_a: &U256,
     virtual_balance_b: &U256,
@@ -265,7 +270,7 @@ fn compute_price_ratio(
 }
 
 /// Compute price range
-fn compute_price_range(
+pub fn compute_price_range(
     balances_scaled_18: &[U256],
     virtual_balance_a: &U256,
     virtual_balance_b: &U256,
@@ -297,7 +302,7 @@ fn compute_price_range(
 }
 
 /// Compute fourth root price ratio
-fn compute_fourth_root_price_ratio(
+pub fn compute_fourth_root_price_ratio(
     current_time: &U256,
     start_fourth_root_price_ratio: &U256,
     end_fourth_root_price_ratio: &U256,
@@ -338,7 +343,7 @@ fn compute_fourth_root_price_ratio(
 }
 
 /// Compute centeredness
-fn compute_centeredness(
+pub fn compute_centeredness(
     balances_scaled_18: &[U256],
     virtual_balance_a: &U256,
     virtual_balance_b: &U256,
@@ -1,15 +1,21 @@
 use crate::common::errors::PoolError;
+use crate::common::maths::{div_down_fixed, div_up_fixed, mul_down_fixed};
 use crate::common::pool_base::PoolBase;
-use crate::common::types::{Rounding, SwapKind, SwapParams};
-use crate::pools::reclammv2::reclammv2_data::ReClammV2State;
+use crate::common::types::{Rounding, SwapKind, SwapParams, VirtualBalancesUpdate};
+use crate::common::WAD;
+use crate::pools::reclamm::reclamm_math::compute_status;
+use crate::pools::reclammv2::reclammv2_data::{ReClammStatus, ReClammV2State};
 use crate::pools::reclammv2::reclammv2_math::{
-    compute_current_virtual_balances, compute_in_given_out, compute_out_given_in,
+    compute_current_virtual_balances, compute_fourth_root_price_ratio, compute_in_given_out,
+    compute_out_given_in,
 };
 use alloy_primitives::U256;
 
 /// ReClammV2 pool implementation
 pub struct ReClammV2Pool {
     re_clamm_v2_state: ReClammV2State,
+    /// Virtual balances computed by `prepare`, keyed by the balances they were computed for
+    prepared_virtual_balances: Option<(Vec<U256>, (U256, U256, bool))>,
 }
 
 impl ReClammV2Pool {
@@ -17,9 +23,127 @@ impl ReClammV2Pool {
     pub fn new(pool_state: ReClammV2State) -> Self {
         Self {
             re_clamm_v2_state: pool_state,
+            prepared_virtual_balances: None,
         }
     }
 
+    /// Compute the current virtual balances for `balances_scaled_18` once, so repeated swaps
+    /// against the same balances skip `compute_current_virtual_balances`
+    pub fn prepare(&mut self, balances_scaled_18: &[U256]) {
+        let virtual_balances = self.compute_current_virtual_balances(balances_scaled_18);
+        self.prepared_virtual_balances = Some((balances_scaled_18.to_vec(), virtual_balances));
+    }
+
+    /// Get the current virtual balances and whether they changed since the last update,
+    /// reusing the prepared value when balances match
+    pub fn current_virtual_balances(&self, balances_scaled_18: &[U256]) -> (U256, U256, bool) {
+        match &self.prepared_virtual_balances {
+            Some((balances, virtual_balances)) if balances.as_slice() == balances_scaled_18 => {
+                *virtual_balances
+            }
+            _ => self.compute_current_virtual_balances(balances_scaled_18),
+        }
+    }
+
+    /// Get the status of the pool at its current timestamp: virtual balances, price range,
+    /// centeredness and whether the range or the price ratio is moving
+    pub fn status(&self) -> ReClammStatus {
+        let state = &self.re_clamm_v2_state;
+        let balances_scaled_18 = &state.base.balances_live_scaled_18;
+        let (virtual_balance_a, virtual_balance_b, _) =
+            self.current_virtual_balances(balances_scaled_18);
+        let mutable = &state.mutable;
+
+        compute_status(
+            &mutable.current_timestamp,
+            balances_scaled_18,
+            &virtual_balance_a,
+            &virtual_balance_b,
+            &compute_fourth_root_price_ratio(
+                &mutable.current_timestamp,
+                &mutable.start_fourth_root_price_ratio,
+                &mutable.end_fourth_root_price_ratio,
+                &mutable.price_ratio_update_start_time,
+                &mutable.price_ratio_update_end_time,
+            ),
+            &mutable.centeredness_margin,
+            &mutable.price_ratio_update_start_time,
+            &mutable.price_ratio_update_end_time,
+        )
+    }
+
+    /// Project the status of the pool to `timestamp`, assuming no swaps or liquidity changes
+    ///
+    /// Out of range pools move their price range towards the market price over time, and price
+    /// ratio updates keep interpolating, so the virtual balances and prices drift without trades.
+    pub fn status_at(&self, timestamp: &U256) -> Result<ReClammStatus, PoolError> {
+        if *timestamp < self.re_clamm_v2_state.mutable.last_timestamp {
+            return Err(PoolError::InvalidInput(
+                "timestamp must not be before the last virtual balance update".to_string(),
+            ));
+        }
+
+        let mut projected_state = self.re_clamm_v2_state.clone();
+        projected_state.mutable.current_timestamp = *timestamp;
+        Ok(ReClammV2Pool::new(projected_state).status())
+    }
+
+    /// Project the status of the pool to each of `timestamps`, assuming no swaps or liquidity
+    /// changes
+    pub fn project(&self, timestamps: &[U256]) -> Result<Vec<ReClammStatus>, PoolError> {
+        timestamps
+            .iter()
+            .map(|timestamp| self.status_at(timestamp))
+            .collect()
+    }
+
+    /// Compute the virtual balances set when adding liquidity for `min_bpt_amount_out`
+    ///
+    /// As in the pool's before add liquidity hook, the current virtual balances grow with the
+    /// BPT supply, rounding down, and the last timestamp moves to the current timestamp.
+    pub fn virtual_balances_after_add_liquidity(
+        &self,
+        min_bpt_amount_out: &U256,
+    ) -> Result<VirtualBalancesUpdate, PoolError> {
+        // Rounding proportion down, which will round the virtual balances down
+        let proportion = div_down_fixed(
+            min_bpt_amount_out,
+            &self.re_clamm_v2_state.base.total_supply,
+        )?;
+        self.scale_virtual_balances(&(WAD + proportion))
+    }
+
+    /// Compute the virtual balances set when removing liquidity for `max_bpt_amount_in`
+    ///
+    /// As in the pool's before remove liquidity hook, the current virtual balances shrink with
+    /// the BPT supply, rounding down, and the last timestamp moves to the current timestamp.
+    pub fn virtual_balances_after_remove_liquidity(
+        &self,
+        max_bpt_amount_in: &U256,
+    ) -> Result<VirtualBalancesUpdate, PoolError> {
+        // Rounding proportion up, which will round the virtual balances down
+        let proportion =
+            div_up_fixed(max_bpt_amount_in, &self.re_clamm_v2_state.base.total_supply)?;
+        let factor = WAD.checked_sub(proportion).ok_or_else(|| {
+            PoolError::InvalidInput("BPT amount in exceeds total supply".to_string())
+        })?;
+        self.scale_virtual_balances(&factor)
+    }
+
+    /// Scale the current virtual balances by `factor`, rounding down
+    fn scale_virtual_balances(&self, factor: &U256) -> Result<VirtualBalancesUpdate, PoolError> {
+        let (virtual_balance_a, virtual_balance_b, _) =
+            self.current_virtual_balances(&self.re_clamm_v2_state.base.balances_live_scaled_18);
+
+        Ok(VirtualBalancesUpdate {
+            virtual_balances: vec![
+                mul_down_fixed(&virtual_balance_a, factor)?,
+                mul_down_fixed(&virtual_balance_b, factor)?,
+            ],
+            last_timestamp: self.re_clamm_v2_state.mutable.current_timestamp,
+        })
+    }
+
     /// Compute current virtual balances for the pool
     fn compute_current_virtual_balances(&self, balances_scaled_18: &[U256]) -> (U256, U256, bool) {
         compute_current_virtual_balances(
@@ -52,8 +176,7 @@ impl PoolBase for ReClammV2Pool {
     }
 
     fn on_swap(&self, swap_params: &SwapParams) -> Result<U256, PoolError> {
-        let compute_result =
-            self.compute_current_virtual_balances(&swap_params.balances_live_scaled_18);
+        let compute_result = self.current_virtual_balances(&swap_params.balances_live_scaled_18);
 
         match swap_params.swap_kind {
             SwapKind::GivenIn => {
@@ -1,11 +1,44 @@
 use crate::common::types::BasePoolState;
+use crate::pools::stable::stable_math::get_amplification_parameter;
 use alloy_primitives::U256;
 use serde::{Deserialize, Serialize};
 
+/// Amplification parameter update, ramping linearly between two values (`AMP_PRECISION` units)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AmpUpdate {
+    pub start_value: U256,
+    pub end_value: U256,
+    pub start_time: U256,
+    pub end_time: U256,
+}
+
 /// Stable pool mutable state
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StableMutable {
+    /// Amplification parameter when no update is in progress
     pub amp: U256,
+    /// Amplification parameter update, if one was started
+    #[serde(default)]
+    pub amp_update: Option<AmpUpdate>,
+    /// Current timestamp, used to interpolate `amp_update`
+    #[serde(default)]
+    pub current_timestamp: U256,
+}
+
+impl StableMutable {
+    /// Get the live amplification parameter, interpolating `amp_update` at `current_timestamp`
+    pub fn amplification_parameter(&self) -> U256 {
+        match &self.amp_update {
+            Some(amp_update) => get_amplification_parameter(
+                &self.current_timestamp,
+                &amp_update.start_time,
+                &amp_update.end_time,
+                &amp_update.start_value,
+                &amp_update.end_value,
+            ),
+            None => self.amp,
+        }
+    }
 }
 
 /// Stable pool state
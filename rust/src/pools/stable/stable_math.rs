@@ -10,6 +10,33 @@ pub const _MIN_INVARIANT_RATIO: u64 = 60e16 as u64; // 60%
 /// Invariant shrink limit: non-proportional remove cannot cause the invariant to decrease by less than this ratio.
 pub const _MAX_INVARIANT_RATIO: u64 = 500e16 as u64; // 500%
 
+/// Get the amplification parameter at `current_time` while it is being updated
+///
+/// The value moves linearly from `start_value` at `start_time` to `end_value` at `end_time`,
+/// as in the V3 StablePool. Values are in `AMP_PRECISION` units.
+pub fn get_amplification_parameter(
+    current_time: &U256,
+    start_time: &U256,
+    end_time: &U256,
+    start_value: &U256,
+    end_value: &U256,
+) -> U256 {
+    if current_time >= end_time {
+        return *end_value;
+    }
+    if current_time <= start_time {
+        return *start_value;
+    }
+
+    let seconds_elapsed = current_time - start_time;
+    let total_seconds = end_time - start_time;
+    if end_value > start_value {
+        start_value + (end_value - start_value) * seconds_elapsed / total_seconds
+    } else {
+        start_value - (start_value - end_value) * seconds_elapsed / total_seconds
+    }
+}
+
 /// Calculate the invariant for the stable swap curve.
 pub fn compute_invariant(
     amplification_parameter: &U256,
@@ -12,13 +12,34 @@ use alloy_primitives::U256;
 /// Stable pool implementation
 pub struct StablePool {
     pub amp: U256,
+    /// Invariant computed by `prepare`, keyed by the balances it was computed for
+    prepared_invariant: Option<(Vec<U256>, U256)>,
 }
 
 impl StablePool {
-    /// Create a new stable pool
+    /// Create a new stable pool, with the amplification parameter live at the current timestamp
     pub fn new(pool_state: StableMutable) -> Self {
         Self {
-            amp: pool_state.amp,
+            amp: pool_state.amplification_parameter(),
+            prepared_invariant: None,
+        }
+    }
+
+    /// Compute the invariant for `balances_live_scaled18` once, so repeated swaps against
+    /// the same balances skip the Newton iteration
+    pub fn prepare(&mut self, balances_live_scaled18: &[U256]) -> Result<(), PoolError> {
+        let invariant = compute_invariant(&self.amp, balances_live_scaled18)?;
+        self.prepared_invariant = Some((balances_live_scaled18.to_vec(), invariant));
+        Ok(())
+    }
+
+    /// Get the invariant (rounded down), reusing the prepared value when balances match
+    fn invariant(&self, balances_live_scaled18: &[U256]) -> Result<U256, PoolError> {
+        match &self.prepared_invariant {
+            Some((balances, invariant)) if balances.as_slice() == balances_live_scaled18 => {
+                Ok(*invariant)
+            }
+            _ => compute_invariant(&self.amp, balances_live_scaled18),
         }
     }
 }
@@ -33,7 +54,7 @@ impl PoolBase for StablePool {
     }
 
     fn on_swap(&self, swap_params: &SwapParams) -> Result<U256, PoolError> {
-        let invariant = compute_invariant(&self.amp, &swap_params.balances_live_scaled_18)?;
+        let invariant = self.invariant(&swap_params.balances_live_scaled_18)?;
 
         let result = match swap_params.swap_kind {
             SwapKind::GivenIn => compute_out_given_exact_in(
@@ -62,7 +83,7 @@ impl PoolBase for StablePool {
         balances_live_scaled18: &[U256],
         rounding: Rounding,
     ) -> Result<U256, PoolError> {
-        let mut invariant = compute_invariant(&self.amp, balances_live_scaled18)?;
+        let mut invariant = self.invariant(balances_live_scaled18)?;
 
         if invariant > U256::ZERO {
             match rounding {
@@ -3,14 +3,28 @@
 use crate::common::errors::PoolError;
 use crate::common::pool_base::PoolBase;
 use crate::common::utils::{
-    compute_and_charge_aggregate_swap_fees, copy_to_scaled18_apply_rate_round_down_array,
-    get_single_input_index, require_unbalanced_liquidity_enabled, to_raw_undo_rate_round_up,
+    aggregate_swap_fee_percentage, compute_and_charge_aggregate_swap_fees,
+    copy_to_scaled18_apply_rate_round_down_array, find_case_insensitive_index_in_list,
+    get_single_input_index, require_donation_enabled, require_unbalanced_liquidity_enabled,
+    require_unpaused, to_raw_undo_rate_round_down, to_raw_undo_rate_round_up,
 };
 use crate::common::{to_scaled_18_apply_rate_round_down, types::*};
 use crate::hooks::types::HookState;
 use crate::hooks::HookBase;
+use crate::pools::reclamm::ReClammPool;
+use crate::pools::reclammv2::ReClammV2Pool;
+use crate::vault::base_pool_math::compute_proportional_amounts_in;
 use alloy_primitives::U256;
 
+/// Add liquidity result together with the swap fees charged on each token
+struct AddLiquidityWithFees {
+    result: AddLiquidityResult,
+    /// Total swap fees (scaled 18)
+    swap_fee_amounts_scaled18: Vec<U256>,
+    /// Protocol and pool creator part of the swap fees (raw)
+    aggregate_swap_fee_amounts_raw: Vec<U256>,
+}
+
 /// Add liquidity to a pool
 pub fn add_liquidity(
     add_liquidity_input: &AddLiquidityInput,
@@ -19,6 +33,24 @@ pub fn add_liquidity(
     hook_class: &dyn HookBase,
     hook_state: Option<&HookState>,
 ) -> Result<AddLiquidityResult, PoolError> {
+    Ok(add_liquidity_with_fees(
+        add_liquidity_input,
+        pool_state,
+        pool_class,
+        hook_class,
+        hook_state,
+    )?
+    .result)
+}
+
+fn add_liquidity_with_fees(
+    add_liquidity_input: &AddLiquidityInput,
+    pool_state: &PoolState,
+    pool_class: &dyn PoolBase,
+    hook_class: &dyn HookBase,
+    hook_state: Option<&HookState>,
+) -> Result<AddLiquidityWithFees, PoolError> {
+    require_unpaused(pool_state)?;
     let base_state = pool_state.base();
 
     // Amounts are entering pool math, so round down.
@@ -95,10 +127,26 @@ pub fn add_liquidity(
             amounts_in_scaled18[token_index] = computed.amount_in_with_fee;
             (bpt_amount_out, computed.swap_fee_amounts)
         }
+        AddLiquidityKind::Proportional => {
+            let bpt_amount_out = add_liquidity_input.min_bpt_amount_out_raw;
+            amounts_in_scaled18 = compute_proportional_amounts_in(
+                &updated_balances_live_scaled18,
+                &base_state.total_supply,
+                &bpt_amount_out,
+            )?;
+            (bpt_amount_out, vec![U256::ZERO; base_state.tokens.len()])
+        }
+        AddLiquidityKind::Donation => {
+            require_donation_enabled(pool_state)?;
+            // Donations add the amounts as given and do not mint BPT
+            amounts_in_scaled18 = max_amounts_in_scaled18.clone();
+            (U256::ZERO, vec![U256::ZERO; base_state.tokens.len()])
+        }
     };
 
     // Initialize amountsInRaw as a list with the same length as the tokens in the pool
     let mut amounts_in_raw = vec![U256::ZERO; base_state.tokens.len()];
+    let mut aggregate_swap_fee_amounts_raw = vec![U256::ZERO; base_state.tokens.len()];
 
     for i in 0..base_state.tokens.len() {
         // amountsInRaw are amounts actually entering the Pool, so we round up.
@@ -112,12 +160,14 @@ pub fn add_liquidity(
         // Computes protocol and pool creator fee which is eventually taken from pool balance
         let aggregate_swap_fee_amount_raw = compute_and_charge_aggregate_swap_fees(
             &swap_fee_amounts_scaled18[i],
-            &base_state.aggregate_swap_fee,
+            &aggregate_swap_fee_percentage(pool_state),
             &base_state.scaling_factors,
             &base_state.token_rates,
             i,
         )?;
 
+        aggregate_swap_fee_amounts_raw[i] = aggregate_swap_fee_amount_raw;
+
         let aggregate_swap_fee_amount_scaled_18 = to_scaled_18_apply_rate_round_down(
             &aggregate_swap_fee_amount_raw,
             &base_state.scaling_factors[i],
@@ -130,6 +180,21 @@ pub fn add_liquidity(
             - aggregate_swap_fee_amount_scaled_18;
     }
 
+    // ReClamm pools scale their virtual balances with the BPT supply before adding liquidity
+    let virtual_balances_update = match pool_state {
+        PoolState::ReClamm(re_clamm_state) => Some(
+            ReClammPool::new(re_clamm_state.clone()).virtual_balances_after_add_liquidity(
+                &add_liquidity_input.min_bpt_amount_out_raw,
+            )?,
+        ),
+        PoolState::ReClammV2(re_clamm_v2_state) => Some(
+            ReClammV2Pool::new(re_clamm_v2_state.clone()).virtual_balances_after_add_liquidity(
+                &add_liquidity_input.min_bpt_amount_out_raw,
+            )?,
+        ),
+        _ => None,
+    };
+
     // Call after add liquidity hook if needed
     if hook_class.config().should_call_after_add_liquidity {
         let hook_return = hook_class.on_after_add_liquidity(
@@ -156,8 +221,116 @@ pub fn add_liquidity(
         }
     }
 
-    Ok(AddLiquidityResult {
-        bpt_amount_out_raw: bpt_amount_out,
-        amounts_in_raw,
+    Ok(AddLiquidityWithFees {
+        result: AddLiquidityResult {
+            bpt_amount_out_raw: bpt_amount_out,
+            amounts_in_raw,
+            virtual_balances_update,
+        },
+        swap_fee_amounts_scaled18,
+        aggregate_swap_fee_amounts_raw,
+    })
+}
+
+/// Add an exact raw amount of a single token to a pool
+///
+/// With `SingleTokenExactInMethod::Unbalanced` the amount is added as an unbalanced add and
+/// enters the pool in full. With `SingleTokenExactInMethod::InverseExactOut` the largest BPT
+/// amount is found whose `SingleTokenExactOut` add needs at most the given amount; the amount in
+/// of the result is what that add actually takes. Both go through the same steps as
+/// `add_liquidity`, hooks included, so the BPT out is what the Vault would mint.
+#[allow(clippy::too_many_arguments)]
+pub fn add_liquidity_single_token_exact_in(
+    token_in: &str,
+    exact_amount_in_raw: &U256,
+    method: SingleTokenExactInMethod,
+    pool_state: &PoolState,
+    pool_class: &dyn PoolBase,
+    hook_class: &dyn HookBase,
+    hook_state: Option<&HookState>,
+) -> Result<AddLiquiditySingleTokenExactInResult, PoolError> {
+    let base_state = pool_state.base();
+    let token_index = find_case_insensitive_index_in_list(&base_state.tokens, token_in)
+        .ok_or(PoolError::InputTokenNotFound)?;
+
+    let mut max_amounts_in_raw = vec![U256::ZERO; base_state.tokens.len()];
+    max_amounts_in_raw[token_index] = *exact_amount_in_raw;
+    let mut add_liquidity_input = AddLiquidityInput {
+        pool: base_state.pool_address.clone(),
+        max_amounts_in_raw,
+        min_bpt_amount_out_raw: U256::ZERO,
+        kind: AddLiquidityKind::Unbalanced,
+    };
+
+    let unbalanced = add_liquidity_with_fees(
+        &add_liquidity_input,
+        pool_state,
+        pool_class,
+        hook_class,
+        hook_state,
+    )?;
+
+    let added = match method {
+        SingleTokenExactInMethod::Unbalanced => Some(unbalanced),
+        SingleTokenExactInMethod::InverseExactOut => {
+            add_liquidity_input.kind = AddLiquidityKind::SingleTokenExactOut;
+
+            // Amount in for an exact BPT out, if it fits in the exact amount in
+            let mut add_exact_out = |bpt_amount_out: U256| -> Option<AddLiquidityWithFees> {
+                add_liquidity_input.min_bpt_amount_out_raw = bpt_amount_out;
+                add_liquidity_with_fees(
+                    &add_liquidity_input,
+                    pool_state,
+                    pool_class,
+                    hook_class,
+                    hook_state,
+                )
+                .ok()
+                .filter(|added| added.result.amounts_in_raw[token_index] <= *exact_amount_in_raw)
+            };
+
+            // The unbalanced BPT out is within rounding of the answer: grow the bracket from
+            // it, then bisect to the largest BPT out that fits
+            let mut lower = (U256::ZERO, None);
+            let mut upper = unbalanced.result.bpt_amount_out_raw.max(U256::ONE);
+            loop {
+                match add_exact_out(upper) {
+                    Some(added) if upper.leading_zeros() > 0 => {
+                        lower = (upper, Some(added));
+                        upper <<= 1;
+                    }
+                    _ => break,
+                }
+            }
+            while upper - lower.0 > U256::ONE {
+                let middle = lower.0 + ((upper - lower.0) >> 1);
+                match add_exact_out(middle) {
+                    Some(added) => lower = (middle, Some(added)),
+                    None => upper = middle,
+                }
+            }
+
+            lower.1
+        }
+    };
+
+    Ok(match added {
+        Some(added) => AddLiquiditySingleTokenExactInResult {
+            bpt_amount_out_raw: added.result.bpt_amount_out_raw,
+            amount_in_raw: added.result.amounts_in_raw[token_index],
+            swap_fee_amount_raw: to_raw_undo_rate_round_down(
+                &added.swap_fee_amounts_scaled18[token_index],
+                &base_state.scaling_factors[token_index],
+                &base_state.token_rates[token_index],
+            )?,
+            aggregate_swap_fee_amount_raw: added.aggregate_swap_fee_amounts_raw[token_index],
+        },
+        // Not even one wei of BPT can be minted with the exact amount in
+        None => AddLiquiditySingleTokenExactInResult {
+            bpt_amount_out_raw: U256::ZERO,
+            amount_in_raw: U256::ZERO,
+            swap_fee_amount_raw: U256::ZERO,
+            aggregate_swap_fee_amount_raw: U256::ZERO,
+        },
     })
 }
@@ -0,0 +1,721 @@
+//! Approximate f64 swap quotes for pre-screening routes
+//!
+//! These quotes follow the same steps as `swap` (scaling, rates, static swap fee, minimum
+//! trade amounts and pool limits) but evaluate the pool math in `f64`, without allocating.
+//! They are meant to prune candidate routes cheaply; winners should be confirmed with the
+//! exact `U256` path. Hooks are not called, so dynamic fees and hook-adjusted amounts are not
+//! reflected in the approximation.
+//!
+//! Error bounds against the exact path: besides the integer rounding of the exact result (one
+//! raw unit), the difference is at most `1e-9` of the amount calculated plus an absolute term
+//! proportional to the pool balances (scaled 18):
+//! * Weighted, LBP and QuantAMM: up to `1e-13` of the balances, from the rounding of the exact
+//!   power function. `ln_1p`/`exp_m1` keep the approximation itself precise for small trades.
+//! * Stable: up to `1e-12` of the balances, from solving for the new balance with Newton
+//!   iterations in `f64`.
+//! * Gyro ECLP: up to `1e-10` of the balances. The exact invariant is reduced by its error
+//!   estimate, which grows with lambda; the approximation solves the curve itself, for the change
+//!   in balance rather than the new balance, to avoid cancellation with the virtual offsets.
+//! * ReClamm and ReClammV2: no absolute term. Virtual balances are computed with the exact math,
+//!   so only the constant product evaluation is approximated.
+//! * FixedPriceLBP and buffers: no absolute term, as only a conversion at a rate is involved.
+//!   Buffers are approximated as going through the ERC4626 vault, ignoring buffer balances.
+//!
+//! Near pool limits (max in/out ratios, QuantAMM max trade size, ECLP asset bounds, minimum
+//! trade amount) an approximate quote can succeed where the exact one fails or vice versa.
+//! QuantAMM max trade size errors carry the approximate amount and limit, truncated from `f64`.
+
+use crate::common::errors::PoolError;
+use crate::common::types::*;
+use crate::common::utils::{
+    f64_to_u256, find_case_insensitive_index_in_list, i256_to_f64, require_unpaused, u256_to_f64,
+};
+use crate::pools::buffer::BufferState;
+use crate::pools::buffer::_MINIMUM_WRAP_AMOUNT;
+use crate::pools::fixed_price_lbp::FixedPriceLBPState;
+use crate::pools::gyro::GyroECLPImmutable;
+use crate::pools::liquidity_bootstrapping::LiquidityBootstrappingState;
+use crate::pools::quantamm::QuantAmmState;
+use crate::pools::reclamm::compute_current_virtual_balances;
+use crate::pools::reclammv2::compute_current_virtual_balances as compute_current_virtual_balances_v2;
+use crate::pools::stable::AMP_PRECISION;
+use crate::pools::weighted::{MAX_IN_RATIO, MAX_OUT_RATIO};
+use crate::vault::swap::MINIMUM_TRADE_AMOUNT;
+
+/// Maximum number of tokens supported by the approximation (the Vault limit)
+pub const MAX_APPROX_TOKENS: usize = 8;
+
+const ONE: f64 = 1e18;
+
+/// Approximate the raw amount calculated by a swap
+///
+/// Returns the amount out for `GivenIn` and the amount in for `GivenOut`, in raw token units,
+/// without rounding to an integer.
+pub fn swap_approx(
+    swap_input: &SwapInput,
+    pool_state_or_buffer: &PoolStateOrBuffer,
+) -> Result<f64, PoolError> {
+    match pool_state_or_buffer {
+        PoolStateOrBuffer::Pool(pool_state) => pool_swap_approx(swap_input, pool_state),
+        PoolStateOrBuffer::Buffer(buffer_state) => buffer_swap_approx(swap_input, buffer_state),
+    }
+}
+
+fn pool_swap_approx(swap_input: &SwapInput, pool_state: &PoolState) -> Result<f64, PoolError> {
+    require_unpaused(pool_state)?;
+
+    if swap_input.amount_raw.is_zero() {
+        return Ok(0.0);
+    }
+
+    let base_state = pool_state.base();
+    let input_index = find_case_insensitive_index_in_list(&base_state.tokens, &swap_input.token_in)
+        .ok_or(PoolError::InputTokenNotFound)?;
+    let output_index =
+        find_case_insensitive_index_in_list(&base_state.tokens, &swap_input.token_out)
+            .ok_or(PoolError::OutputTokenNotFound)?;
+
+    let num_tokens = base_state.tokens.len();
+    if num_tokens > MAX_APPROX_TOKENS {
+        return Err(PoolError::InvalidInput(format!(
+            "Too many tokens for approximation: {}",
+            num_tokens
+        )));
+    }
+
+    let mut balances = [0.0; MAX_APPROX_TOKENS];
+    for (balance, balance_scaled_18) in balances
+        .iter_mut()
+        .zip(base_state.balances_live_scaled_18.iter())
+    {
+        *balance = u256_to_f64(balance_scaled_18);
+    }
+    let balances = &mut balances[..num_tokens];
+
+    // Scale 18 with rate applied: amount * scalingFactor * rate / 1e18
+    let scale = |index: usize| {
+        u256_to_f64(&base_state.scaling_factors[index])
+            * u256_to_f64(&base_state.token_rates[index])
+            / ONE
+    };
+    let swap_fee = u256_to_f64(&base_state.swap_fee) / ONE;
+
+    let amount_raw = u256_to_f64(&swap_input.amount_raw);
+    let amount_given_scaled_18 = match swap_input.swap_kind {
+        SwapKind::GivenIn => amount_raw * scale(input_index) * (1.0 - swap_fee),
+        SwapKind::GivenOut => amount_raw * scale(output_index),
+    };
+
+    ensure_valid_swap_amount(amount_given_scaled_18)?;
+
+    let amount_calculated_scaled_18 = pool_on_swap_approx(
+        pool_state,
+        &swap_input.swap_kind,
+        input_index,
+        output_index,
+        amount_given_scaled_18,
+        balances,
+    )?;
+
+    ensure_valid_swap_amount(amount_calculated_scaled_18)?;
+
+    Ok(match swap_input.swap_kind {
+        SwapKind::GivenIn => amount_calculated_scaled_18 / scale(output_index),
+        SwapKind::GivenOut => amount_calculated_scaled_18 / (1.0 - swap_fee) / scale(input_index),
+    })
+}
+
+/// Approximate the pool math, returning the amount calculated (scaled 18)
+fn pool_on_swap_approx(
+    pool_state: &PoolState,
+    swap_kind: &SwapKind,
+    index_in: usize,
+    index_out: usize,
+    amount_scaled_18: f64,
+    balances: &mut [f64],
+) -> Result<f64, PoolError> {
+    match pool_state {
+        PoolState::Weighted(weighted_state) => weighted_on_swap_approx(
+            swap_kind,
+            balances[index_in],
+            u256_to_f64(&weighted_state.weights[index_in]) / ONE,
+            balances[index_out],
+            u256_to_f64(&weighted_state.weights[index_out]) / ONE,
+            amount_scaled_18,
+        ),
+        PoolState::Stable(stable_state) => stable_on_swap_approx(
+            swap_kind,
+            u256_to_f64(&stable_state.mutable.amplification_parameter()) / AMP_PRECISION as f64,
+            index_in,
+            index_out,
+            amount_scaled_18,
+            balances,
+        ),
+        PoolState::GyroECLP(gyro_eclp_state) => gyro_eclp_on_swap_approx(
+            swap_kind,
+            &gyro_eclp_state.immutable,
+            index_in,
+            amount_scaled_18,
+            balances,
+        ),
+        PoolState::ReClamm(re_clamm_state) => {
+            let mutable = &re_clamm_state.mutable;
+            let (virtual_balance_a, virtual_balance_b, _) = compute_current_virtual_balances(
+                &mutable.current_timestamp,
+                &pool_state.base().balances_live_scaled_18,
+                &mutable.last_virtual_balances[0],
+                &mutable.last_virtual_balances[1],
+                &mutable.daily_price_shift_base,
+                &mutable.last_timestamp,
+                &mutable.centeredness_margin,
+                &mutable.start_fourth_root_price_ratio,
+                &mutable.end_fourth_root_price_ratio,
+                &mutable.price_ratio_update_start_time,
+                &mutable.price_ratio_update_end_time,
+            );
+            re_clamm_on_swap_approx(
+                swap_kind,
+                [
+                    u256_to_f64(&virtual_balance_a),
+                    u256_to_f64(&virtual_balance_b),
+                ],
+                index_in,
+                index_out,
+                amount_scaled_18,
+                balances,
+            )
+            // ReClammPool::on_swap reports math errors as invalid swap parameters
+            .map_err(|_| PoolError::InvalidSwapParameters)
+        }
+        PoolState::ReClammV2(re_clamm_v2_state) => {
+            let mutable = &re_clamm_v2_state.mutable;
+            let (virtual_balance_a, virtual_balance_b, _) = compute_current_virtual_balances_v2(
+                &mutable.current_timestamp,
+                &pool_state.base().balances_live_scaled_18,
+                &mutable.last_virtual_balances[0],
+                &mutable.last_virtual_balances[1],
+                &mutable.daily_price_shift_base,
+                &mutable.last_timestamp,
+                &mutable.centeredness_margin,
+                &mutable.start_fourth_root_price_ratio,
+                &mutable.end_fourth_root_price_ratio,
+                &mutable.price_ratio_update_start_time,
+                &mutable.price_ratio_update_end_time,
+            );
+            re_clamm_on_swap_approx(
+                swap_kind,
+                [
+                    u256_to_f64(&virtual_balance_a),
+                    u256_to_f64(&virtual_balance_b),
+                ],
+                index_in,
+                index_out,
+                amount_scaled_18,
+                balances,
+            )
+            // ReClammV2Pool::on_swap passes the math error message through
+            .map_err(PoolError::from)
+        }
+        PoolState::LiquidityBootstrapping(liquidity_bootstrapping_state) => {
+            liquidity_bootstrapping_on_swap_approx(
+                swap_kind,
+                liquidity_bootstrapping_state,
+                index_in,
+                index_out,
+                amount_scaled_18,
+                balances,
+            )
+        }
+        PoolState::QuantAmm(quant_amm_state) => quant_amm_on_swap_approx(
+            swap_kind,
+            quant_amm_state,
+            index_in,
+            index_out,
+            amount_scaled_18,
+            balances,
+        ),
+        PoolState::FixedPriceLBP(fixed_price_lbp_state) => fixed_price_lbp_on_swap_approx(
+            swap_kind,
+            fixed_price_lbp_state,
+            index_in,
+            amount_scaled_18,
+        ),
+        PoolState::Base(base_state) => {
+            Err(PoolError::UnsupportedPoolType(base_state.pool_type.clone()))
+        }
+    }
+}
+
+/// Weighted math, using `ln_1p`/`exp_m1` so small trades keep full relative precision
+///
+/// aO = bO * (1 - (bI / (bI + aI)) ^ (wI / wO))
+/// aI = bI * ((bO / (bO - aO)) ^ (wO / wI) - 1)
+fn weighted_on_swap_approx(
+    swap_kind: &SwapKind,
+    balance_in: f64,
+    weight_in: f64,
+    balance_out: f64,
+    weight_out: f64,
+    amount_scaled_18: f64,
+) -> Result<f64, PoolError> {
+    match swap_kind {
+        SwapKind::GivenIn => {
+            if amount_scaled_18 > balance_in * u256_to_f64(&MAX_IN_RATIO) / ONE {
+                return Err(PoolError::MaxInRatioExceeded);
+            }
+            let exponent = weight_in / weight_out;
+            Ok(-balance_out * (-exponent * (amount_scaled_18 / balance_in).ln_1p()).exp_m1())
+        }
+        SwapKind::GivenOut => {
+            if amount_scaled_18 > balance_out * u256_to_f64(&MAX_OUT_RATIO) / ONE {
+                return Err(PoolError::MaxOutRatioExceeded);
+            }
+            let exponent = weight_out / weight_in;
+            Ok(balance_in * (-exponent * (-amount_scaled_18 / balance_out).ln_1p()).exp_m1())
+        }
+    }
+}
+
+/// Stable math: Newton iterations for the invariant and for the new balance
+fn stable_on_swap_approx(
+    swap_kind: &SwapKind,
+    amp: f64,
+    index_in: usize,
+    index_out: usize,
+    amount_scaled_18: f64,
+    balances: &mut [f64],
+) -> Result<f64, PoolError> {
+    let invariant = stable_invariant_approx(amp, balances)?;
+
+    match swap_kind {
+        SwapKind::GivenIn => {
+            let balance_out = balances[index_out];
+            balances[index_in] += amount_scaled_18;
+            let final_balance_out = stable_balance_approx(amp, balances, invariant, index_out)?;
+            Ok(balance_out - final_balance_out)
+        }
+        SwapKind::GivenOut => {
+            if balances[index_out] <= amount_scaled_18 {
+                return Err(PoolError::TokenAmountOutIsGreaterThanBalance);
+            }
+            let balance_in = balances[index_in];
+            balances[index_out] -= amount_scaled_18;
+            let final_balance_in = stable_balance_approx(amp, balances, invariant, index_in)?;
+            Ok(final_balance_in - balance_in)
+        }
+    }
+}
+
+fn stable_invariant_approx(amp: f64, balances: &[f64]) -> Result<f64, PoolError> {
+    let num_tokens = balances.len() as f64;
+    let total_balance: f64 = balances.iter().sum();
+    if total_balance == 0.0 {
+        return Err(PoolError::ZeroInvariant);
+    }
+
+    let amp_times_total = amp * num_tokens;
+    let mut invariant = total_balance;
+
+    for _ in 0..255 {
+        let mut d_p = invariant;
+        for balance in balances {
+            d_p = d_p * invariant / (balance * num_tokens);
+        }
+
+        let prev_invariant = invariant;
+        invariant = (amp_times_total * total_balance + d_p * num_tokens) * invariant
+            / ((amp_times_total - 1.0) * invariant + (num_tokens + 1.0) * d_p);
+
+        if (invariant - prev_invariant).abs() <= invariant * 1e-15 {
+            return Ok(invariant);
+        }
+    }
+
+    Err(PoolError::StableInvariantDidntConverge)
+}
+
+fn stable_balance_approx(
+    amp: f64,
+    balances: &[f64],
+    invariant: f64,
+    token_index: usize,
+) -> Result<f64, PoolError> {
+    let num_tokens = balances.len() as f64;
+    let amp_times_total = amp * num_tokens;
+
+    // c = D^(n+1) / (A * n^(n+1) * prod(other balances)), accumulated to stay in range
+    let mut c = invariant;
+    let mut sum = 0.0;
+    for (i, balance) in balances.iter().enumerate() {
+        if i != token_index {
+            c = c * invariant / (balance * num_tokens);
+            sum += balance;
+        }
+    }
+    c = c * invariant / (amp_times_total * num_tokens);
+
+    let b = sum + invariant / amp_times_total;
+    let mut token_balance = (invariant * invariant + c) / (invariant + b);
+
+    for _ in 0..255 {
+        let prev_token_balance = token_balance;
+        token_balance = (token_balance * token_balance + c) / (2.0 * token_balance + b - invariant);
+
+        if (token_balance - prev_token_balance).abs() <= token_balance * 1e-15 {
+            return Ok(token_balance);
+        }
+    }
+
+    Err(PoolError::StableInvariantDidntConverge)
+}
+
+/// Gyro ECLP math: the invariant `r` solves |A(t - r * chi)|^2 = r^2, with `A` the
+/// ellipse transform and `chi` the (normalized) virtual offsets per unit of invariant
+fn gyro_eclp_on_swap_approx(
+    swap_kind: &SwapKind,
+    params: &GyroECLPImmutable,
+    index_in: usize,
+    amount_scaled_18: f64,
+    balances: &[f64],
+) -> Result<f64, PoolError> {
+    let c = i256_to_f64(&params.c) / ONE;
+    let s = i256_to_f64(&params.s) / ONE;
+    let lambda = i256_to_f64(&params.lambda) / ONE;
+    let d_sq = i256_to_f64(&params.d_sq);
+
+    let tau_alpha_x = i256_to_f64(&params.tau_alpha_x) / d_sq;
+    let tau_alpha_y = i256_to_f64(&params.tau_alpha_y) / d_sq;
+    let tau_beta_x = i256_to_f64(&params.tau_beta_x) / d_sq;
+    let tau_beta_y = i256_to_f64(&params.tau_beta_y) / d_sq;
+
+    let chi_x = lambda * c * tau_beta_x + s * tau_beta_y;
+    let chi_y = -lambda * s * tau_alpha_x + c * tau_alpha_y;
+
+    let mul_a = |x: f64, y: f64| ((c * x - s * y) / lambda, s * x + c * y);
+
+    let (a_chi_x, a_chi_y) = mul_a(chi_x, chi_y);
+    let (a_t_x, a_t_y) = mul_a(balances[0], balances[1]);
+    let a_chi_a_chi = a_chi_x * a_chi_x + a_chi_y * a_chi_y;
+    let a_t_a_chi = a_t_x * a_chi_x + a_t_y * a_chi_y;
+    let a_t_a_t = a_t_x * a_t_x + a_t_y * a_t_y;
+
+    let invariant = (a_t_a_chi
+        + (a_t_a_chi * a_t_a_chi - (a_chi_a_chi - 1.0) * a_t_a_t)
+            .max(0.0)
+            .sqrt())
+        / (a_chi_a_chi - 1.0);
+
+    // Balances relative to the virtual offsets (r * chi)
+    let offsets = [invariant * chi_x, invariant * chi_y];
+    let lambda_sq_inv = 1.0 / (lambda * lambda);
+    let cross_coefficient = s * c * (1.0 - lambda_sq_inv);
+    let square_coefficients = [c * c * lambda_sq_inv + s * s, s * s * lambda_sq_inv + c * c];
+
+    // Change of the balance at `index` when the other balance changes by `delta_other`.
+    // With k and e the other and solved balances relative to their offsets, the curve is
+    // q_e * e^2 + 2 * cross_coefficient * k * e + q_k * k^2 = r^2. Subtracting it at the old and
+    // new point gives the change in e without the cancellation of computing both balances.
+    let balance_delta = |index: usize, delta_other: f64| {
+        let other = 1 - index;
+        let q_e = square_coefficients[index];
+        let q_k = square_coefficients[other];
+        let k_old = balances[other] - offsets[other];
+        let k_new = k_old + delta_other;
+        let e_old = balances[index] - offsets[index];
+
+        // Root of the curve for the new point, on the trading side of the ellipse
+        let q_b = 2.0 * cross_coefficient * k_new;
+        let q_c = q_k * k_new * k_new - invariant * invariant;
+        let e_new = (-q_b - (q_b * q_b - 4.0 * q_e * q_c).max(0.0).sqrt()) / (2.0 * q_e);
+
+        -delta_other * (2.0 * cross_coefficient * e_old + q_k * (k_new + k_old))
+            / (q_e * (e_new + e_old) + 2.0 * cross_coefficient * k_new)
+    };
+
+    // Balances at the price bounds, which new balances may not exceed
+    let max_balances = [
+        invariant * (lambda * c * (tau_beta_x - tau_alpha_x) + s * (tau_beta_y - tau_alpha_y)),
+        invariant * (lambda * s * (tau_beta_x - tau_alpha_x) + c * (tau_alpha_y - tau_beta_y)),
+    ];
+    let check_asset_bounds = |new_balance: f64, index: usize| {
+        if new_balance > max_balances[index] {
+            Err(PoolError::InvalidInput("Asset bounds exceeded".to_string()))
+        } else {
+            Ok(new_balance)
+        }
+    };
+
+    let index_out = 1 - index_in;
+    match swap_kind {
+        SwapKind::GivenIn => {
+            check_asset_bounds(balances[index_in] + amount_scaled_18, index_in)?;
+            Ok(-balance_delta(index_out, amount_scaled_18))
+        }
+        SwapKind::GivenOut => {
+            if amount_scaled_18 > balances[index_out] {
+                return Err(PoolError::InvalidInput("Asset bounds exceeded".to_string()));
+            }
+            let amount_in = balance_delta(index_in, -amount_scaled_18);
+            check_asset_bounds(balances[index_in] + amount_in, index_in)?;
+            Ok(amount_in)
+        }
+    }
+}
+
+/// ReClamm math: constant product over real plus virtual balances
+fn re_clamm_on_swap_approx(
+    swap_kind: &SwapKind,
+    virtual_balances: [f64; 2],
+    index_in: usize,
+    index_out: usize,
+    amount_scaled_18: f64,
+    balances: &[f64],
+) -> Result<f64, String> {
+    let total_in = balances[index_in] + virtual_balances[index_in];
+    let total_out = balances[index_out] + virtual_balances[index_out];
+
+    match swap_kind {
+        SwapKind::GivenIn => {
+            let amount_out = total_out * amount_scaled_18 / (total_in + amount_scaled_18);
+            if amount_out > balances[index_out] {
+                return Err("reClammMath: AmountOutGreaterThanBalance".to_string());
+            }
+            Ok(amount_out)
+        }
+        SwapKind::GivenOut => {
+            if amount_scaled_18 > balances[index_out] {
+                return Err("reClammMath: AmountOutGreaterThanBalance".to_string());
+            }
+            Ok(total_in * amount_scaled_18 / (total_out - amount_scaled_18))
+        }
+    }
+}
+
+/// LBP math: weighted math with the project token weight interpolated linearly in time
+fn liquidity_bootstrapping_on_swap_approx(
+    swap_kind: &SwapKind,
+    state: &LiquidityBootstrappingState,
+    index_in: usize,
+    index_out: usize,
+    amount_scaled_18: f64,
+    balances: &[f64],
+) -> Result<f64, PoolError> {
+    let project_token_index = state.immutable.project_token_index;
+    if state.immutable.start_weights.len() != 2 || state.immutable.end_weights.len() != 2 {
+        return Err(PoolError::InvalidSwapParameters);
+    }
+    if index_in >= 2 || index_out >= 2 {
+        return Err(PoolError::InvalidTokenIndex);
+    }
+    if !state.mutable.is_swap_enabled
+        || (state.immutable.is_project_token_swap_in_blocked && index_in == project_token_index)
+    {
+        return Err(PoolError::InvalidSwapParameters);
+    }
+
+    let current_time = u256_to_f64(&state.mutable.current_timestamp);
+    let start_time = u256_to_f64(&state.immutable.start_time);
+    let end_time = u256_to_f64(&state.immutable.end_time);
+    let progress = if current_time >= end_time {
+        1.0
+    } else if current_time <= start_time {
+        0.0
+    } else {
+        (current_time - start_time) / (end_time - start_time)
+    };
+
+    let start_weight = u256_to_f64(&state.immutable.start_weights[project_token_index]) / ONE;
+    let end_weight = u256_to_f64(&state.immutable.end_weights[project_token_index]) / ONE;
+    let project_weight = start_weight + (end_weight - start_weight) * progress;
+    let weight = |index: usize| {
+        if index == project_token_index {
+            project_weight
+        } else {
+            1.0 - project_weight
+        }
+    };
+
+    weighted_on_swap_approx(
+        swap_kind,
+        balances[index_in],
+        weight(index_in),
+        balances[index_out],
+        weight(index_out),
+        amount_scaled_18,
+    )
+}
+
+/// QuantAMM math: weighted math with weights moved by their multipliers since the last update
+fn quant_amm_on_swap_approx(
+    swap_kind: &SwapKind,
+    state: &QuantAmmState,
+    index_in: usize,
+    index_out: usize,
+    amount_scaled_18: f64,
+    balances: &[f64],
+) -> Result<f64, PoolError> {
+    let num_tokens = balances.len();
+    let first_count = num_tokens.min(4);
+    let second_count = num_tokens.saturating_sub(4);
+
+    let multiplier_time = state
+        .mutable
+        .current_timestamp
+        .min(state.mutable.last_interop_time);
+    let time_since_last_update =
+        u256_to_f64(&multiplier_time) - u256_to_f64(&state.mutable.last_update_time);
+
+    // Packed as [weights..., multipliers...] over the first four and remaining tokens
+    let weight = |index: usize| {
+        let (packed, count, offset) = if index < 4 {
+            (
+                &state.mutable.first_four_weights_and_multipliers,
+                first_count,
+                index,
+            )
+        } else {
+            (
+                &state.mutable.second_four_weights_and_multipliers,
+                second_count,
+                index - 4,
+            )
+        };
+        (i256_to_f64(&packed[offset])
+            + i256_to_f64(&packed[count + offset]) * time_since_last_update)
+            / ONE
+    };
+
+    let max_trade_size_ratio = u256_to_f64(&state.immutable.max_trade_size_ratio) / ONE;
+    let check_max_trade_size = |amount: f64, balance: f64| {
+        let max_amount = balance * max_trade_size_ratio;
+        if amount > max_amount {
+            Err(PoolError::MaxTradeSizeRatioExceeded {
+                amount_scaled_18: f64_to_u256(amount)?,
+                max_amount_scaled_18: f64_to_u256(max_amount)?,
+            })
+        } else {
+            Ok(())
+        }
+    };
+
+    let balance_in = balances[index_in];
+    let balance_out = balances[index_out];
+    let (balance_given, balance_calculated) = match swap_kind {
+        SwapKind::GivenIn => (balance_in, balance_out),
+        SwapKind::GivenOut => (balance_out, balance_in),
+    };
+
+    check_max_trade_size(amount_scaled_18, balance_given)?;
+    let amount_calculated = weighted_on_swap_approx(
+        swap_kind,
+        balance_in,
+        weight(index_in),
+        balance_out,
+        weight(index_out),
+        amount_scaled_18,
+    )?;
+    check_max_trade_size(amount_calculated, balance_calculated)?;
+
+    Ok(amount_calculated)
+}
+
+/// FixedPriceLBP math: reserve tokens buy project tokens at a fixed rate
+fn fixed_price_lbp_on_swap_approx(
+    swap_kind: &SwapKind,
+    state: &FixedPriceLBPState,
+    index_in: usize,
+    amount_scaled_18: f64,
+) -> Result<f64, PoolError> {
+    let current_timestamp = state.mutable.current_timestamp;
+    if current_timestamp < state.immutable.start_time
+        || current_timestamp > state.immutable.end_time
+    {
+        return Err(PoolError::SaleWindowClosed {
+            current_timestamp,
+            start_time: state.immutable.start_time,
+            end_time: state.immutable.end_time,
+        });
+    }
+    if !state.mutable.is_swap_enabled || index_in == state.immutable.project_token_index {
+        return Err(PoolError::InvalidSwapParameters);
+    }
+
+    let project_token_rate = u256_to_f64(&state.immutable.project_token_rate) / ONE;
+    Ok(match swap_kind {
+        SwapKind::GivenIn => amount_scaled_18 / project_token_rate,
+        SwapKind::GivenOut => amount_scaled_18 * project_token_rate,
+    })
+}
+
+/// ERC4626 buffer math: conversion at the vault rate, with the ERC4626 vault limits
+fn buffer_swap_approx(
+    swap_input: &SwapInput,
+    buffer_state: &BufferState,
+) -> Result<f64, PoolError> {
+    let amount_raw = u256_to_f64(&swap_input.amount_raw);
+    if swap_input.amount_raw < _MINIMUM_WRAP_AMOUNT {
+        return Err(PoolError::Custom("wrapAmountTooSmall".to_string()));
+    }
+
+    // Underlying raw amount per wrapped raw amount
+    let rate = u256_to_f64(&buffer_state.mutable.rate)
+        / ONE
+        / 10f64.powi(buffer_state.immutable.decimals_offset());
+    let is_unwrap = swap_input
+        .token_in
+        .eq_ignore_ascii_case(&buffer_state.immutable.pool_address);
+
+    match (is_unwrap, &swap_input.swap_kind) {
+        (false, SwapKind::GivenIn) => {
+            if let Some(max_deposit) = &buffer_state.mutable.max_deposit {
+                if swap_input.amount_raw > *max_deposit {
+                    return Err(PoolError::ERC4626ExceededMaxDeposit {
+                        assets: swap_input.amount_raw,
+                        max_assets: *max_deposit,
+                    });
+                }
+            }
+            Ok(amount_raw / rate)
+        }
+        (false, SwapKind::GivenOut) => {
+            if let Some(max_mint) = &buffer_state.mutable.max_mint {
+                if swap_input.amount_raw > *max_mint {
+                    return Err(PoolError::ERC4626ExceededMaxMint {
+                        shares: swap_input.amount_raw,
+                        max_shares: *max_mint,
+                    });
+                }
+            }
+            Ok(amount_raw * rate)
+        }
+        (true, SwapKind::GivenIn) => {
+            if let Some(max_redeem) = &buffer_state.mutable.max_redeem {
+                if swap_input.amount_raw > *max_redeem {
+                    return Err(PoolError::ERC4626ExceededMaxRedeem {
+                        shares: swap_input.amount_raw,
+                        max_shares: *max_redeem,
+                    });
+                }
+            }
+            Ok(amount_raw * rate)
+        }
+        (true, SwapKind::GivenOut) => {
+            if let Some(max_withdraw) = &buffer_state.mutable.max_withdraw {
+                if swap_input.amount_raw > *max_withdraw {
+                    return Err(PoolError::ERC4626ExceededMaxWithdraw {
+                        assets: swap_input.amount_raw,
+                        max_assets: *max_withdraw,
+                    });
+                }
+            }
+            Ok(amount_raw / rate)
+        }
+    }
+}
+
+fn ensure_valid_swap_amount(amount_scaled_18: f64) -> Result<(), PoolError> {
+    if amount_scaled_18 < u256_to_f64(&MINIMUM_TRADE_AMOUNT) {
+        return Err(PoolError::TradeAmountTooSmall);
+    }
+    Ok(())
+}
@@ -35,6 +35,13 @@ pub struct RemoveLiquiditySingleTokenExactOutResult {
     pub swap_fee_amounts: Vec<U256>,
 }
 
+/// Result of remove liquidity unbalanced operation
+#[derive(Debug, Clone)]
+pub struct RemoveLiquidityUnbalancedResult {
+    pub bpt_amount_in: U256,
+    pub swap_fee_amounts: Vec<U256>,
+}
+
 /// Compute add liquidity for unbalanced amounts
 #[allow(clippy::type_complexity)]
 pub fn compute_add_liquidity_unbalanced(
@@ -139,6 +146,24 @@ pub fn compute_add_liquidity_single_token_exact_out(
     })
 }
 
+/// Compute proportional amounts in for add liquidity
+///
+/// Amounts are entering the pool, so they are rounded up to favor the pool.
+pub fn compute_proportional_amounts_in(
+    balances: &[U256],
+    bpt_total_supply: &U256,
+    bpt_amount_out: &U256,
+) -> Result<Vec<U256>, PoolError> {
+    let mut amounts_in = Vec::with_capacity(balances.len());
+
+    for balance in balances {
+        let amount_in = mul_div_up_fixed(balance, bpt_amount_out, bpt_total_supply)?;
+        amounts_in.push(amount_in);
+    }
+
+    Ok(amounts_in)
+}
+
 /// Compute proportional amounts out for remove liquidity
 pub fn compute_proportional_amounts_out(
     balances: &[U256],
@@ -272,3 +297,75 @@ pub fn compute_remove_liquidity_single_token_exact_out(
         swap_fee_amounts,
     })
 }
+
+/// Compute remove liquidity for unbalanced exact amounts out
+///
+/// The reverse of `compute_add_liquidity_unbalanced`: any subset of tokens can be removed in
+/// exact amounts, and swap fees are charged on the part of each amount out above the
+/// proportional share of the invariant ratio. Tokens that are not removed are not charged, so
+/// with a single token out this matches
+/// `compute_remove_liquidity_single_token_exact_out`.
+#[allow(clippy::type_complexity)]
+pub fn compute_remove_liquidity_unbalanced(
+    current_balances: &[U256],
+    exact_amounts_out: &[U256],
+    total_supply: &U256,
+    swap_fee_percentage: &U256,
+    min_invariant_ratio: &U256,
+    compute_invariant: &dyn Fn(&[U256], Rounding) -> Result<U256, PoolError>,
+) -> Result<RemoveLiquidityUnbalancedResult, PoolError> {
+    let num_tokens = current_balances.len();
+
+    // Create new balances with removed amounts, rounding down to favor the pool
+    let mut new_balances = vec![U256::ZERO; num_tokens];
+    let mut swap_fee_amounts = vec![U256::ZERO; num_tokens];
+
+    for index in 0..current_balances.len() {
+        new_balances[index] = (current_balances[index] - U256::ONE)
+            .checked_sub(exact_amounts_out[index])
+            .ok_or(PoolError::TokenAmountOutIsGreaterThanBalance)?;
+    }
+
+    // Calculate the invariant using the current balances
+    let current_invariant = compute_invariant(current_balances, Rounding::RoundUp)?;
+
+    let invariant_ratio = div_up_fixed(
+        &compute_invariant(&new_balances, Rounding::RoundUp)?,
+        &current_invariant,
+    )?;
+
+    // Check invariant ratio bounds
+    if &invariant_ratio < min_invariant_ratio {
+        return Err(PoolError::MathOverflow);
+    }
+
+    // Apply fees to non-proportional amounts, only on the tokens removed
+    for index in 0..current_balances.len() {
+        if exact_amounts_out[index].is_zero() {
+            continue;
+        }
+        let proportional_token_balance = mul_up_fixed(&invariant_ratio, &current_balances[index])?;
+        if proportional_token_balance > new_balances[index] {
+            let taxable_amount = proportional_token_balance - new_balances[index];
+            let fee = div_up_fixed(&taxable_amount, &complement_fixed(swap_fee_percentage)?)?
+                - taxable_amount;
+            swap_fee_amounts[index] = fee;
+            new_balances[index] -= fee;
+        }
+    }
+
+    // Calculate the new invariant with fees applied
+    let invariant_with_fees_applied = compute_invariant(&new_balances, Rounding::RoundDown)?;
+
+    // Calculate the amount of BPT to burn
+    let bpt_amount_in = mul_div_up_fixed(
+        total_supply,
+        &(current_invariant - invariant_with_fees_applied),
+        &current_invariant,
+    )?;
+
+    Ok(RemoveLiquidityUnbalancedResult {
+        bpt_amount_in,
+        swap_fee_amounts,
+    })
+}
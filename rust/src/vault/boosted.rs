@@ -0,0 +1,339 @@
+//! Composite operations on boosted pools, whose tokens are ERC4626 wrappers
+//!
+//! Users trade and provide liquidity in the underlying tokens, and each operation wraps or
+//! unwraps through the buffer of a pool token on the way in or out, as the V3 batch router and
+//! CompositeLiquidityRouter do. Pool tokens without a buffer in `buffers` are used directly, so
+//! partially boosted pools are supported. Buffers are quoted as in `Vault::buffer_wrap_or_unwrap`,
+//! serving from their balances when they can and going through the ERC4626 vault otherwise, and
+//! results report the path and buffer state of each wrap and unwrap so routes can chain them.
+//! Exact out operations are solved backwards from the amounts out.
+
+use crate::common::errors::PoolError;
+use crate::common::types::*;
+use crate::hooks::types::HookState;
+use crate::hooks::HookBase;
+use crate::pools::buffer::{
+    erc4626_buffer_wrap_or_unwrap_with_state, BufferState, BufferWrapOrUnwrapResult,
+};
+use crate::vault::swap::swap;
+use crate::vault::Vault;
+use alloy_primitives::U256;
+
+/// Outcome of a swap through a boosted pool
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoostedSwapResult {
+    /// Amount out for `GivenIn`, amount in for `GivenOut` (raw)
+    pub amount_calculated_raw: U256,
+    /// Amount of the pool token in swapped by the pool, after wrapping (raw)
+    pub wrapped_amount_in_raw: U256,
+    /// Amount of the pool token out swapped by the pool, before unwrapping (raw)
+    pub wrapped_amount_out_raw: U256,
+    /// Wrap of the token in, if it went through a buffer
+    pub wrap_result: Option<BufferWrapOrUnwrapResult>,
+    /// Unwrap of the token out, if it went through a buffer
+    pub unwrap_result: Option<BufferWrapOrUnwrapResult>,
+}
+
+/// Outcome of adding liquidity to a boosted pool in underlying tokens
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoostedAddLiquidityResult {
+    pub bpt_amount_out_raw: U256,
+    /// Amounts paid in, in underlying tokens for pool tokens with a buffer (raw)
+    pub amounts_in_raw: Vec<U256>,
+    /// Amounts added to the pool, in pool tokens (raw)
+    pub wrapped_amounts_in_raw: Vec<U256>,
+    /// Wrap of each pool token, if it went through a buffer
+    pub wrap_results: Vec<Option<BufferWrapOrUnwrapResult>>,
+}
+
+/// Outcome of removing liquidity from a boosted pool to underlying tokens
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoostedRemoveLiquidityResult {
+    pub bpt_amount_in_raw: U256,
+    /// Amounts paid out, in underlying tokens for pool tokens with a buffer (raw)
+    pub amounts_out_raw: Vec<U256>,
+    /// Amounts removed from the pool, in pool tokens (raw)
+    pub wrapped_amounts_out_raw: Vec<U256>,
+    /// Unwrap of each pool token, if it went through a buffer
+    pub unwrap_results: Vec<Option<BufferWrapOrUnwrapResult>>,
+}
+
+impl Vault {
+    /// Swap through a boosted pool, wrapping the token in and unwrapping the token out
+    ///
+    /// `token_in` and `token_out` are either the underlying token of a pool token's buffer or a
+    /// pool token itself, which is then swapped without wrapping.
+    pub fn swap_boosted(
+        &self,
+        swap_input: &SwapInput,
+        pool_state: &PoolState,
+        buffers: &[BufferState],
+        hook_state: Option<&HookState>,
+    ) -> Result<BoostedSwapResult, PoolError> {
+        let base_state = pool_state.base();
+        let (wrapped_token_in, buffer_in) =
+            resolve_pool_token(&base_state.tokens, buffers, &swap_input.token_in)
+                .ok_or(PoolError::InputTokenNotFound)?;
+        let (wrapped_token_out, buffer_out) =
+            resolve_pool_token(&base_state.tokens, buffers, &swap_input.token_out)
+                .ok_or(PoolError::OutputTokenNotFound)?;
+
+        let pool = self.get_pool(pool_state)?;
+        let hook: Box<dyn HookBase> = self.get_hook(pool_state, hook_state);
+        let pool_swap = |swap_kind: SwapKind, amount_raw: U256| {
+            swap(
+                &SwapInput {
+                    swap_kind,
+                    amount_raw,
+                    token_in: wrapped_token_in.clone(),
+                    token_out: wrapped_token_out.clone(),
+                },
+                pool_state,
+                pool.as_ref(),
+                hook.as_ref(),
+                hook_state,
+            )
+        };
+
+        match swap_input.swap_kind {
+            SwapKind::GivenIn => {
+                let (wrapped_amount_in_raw, wrap_result) =
+                    wrap(buffer_in, SwapKind::GivenIn, swap_input.amount_raw)?;
+                let wrapped_amount_out_raw = pool_swap(SwapKind::GivenIn, wrapped_amount_in_raw)?;
+                let (amount_calculated_raw, unwrap_result) =
+                    unwrap(buffer_out, SwapKind::GivenIn, wrapped_amount_out_raw)?;
+                Ok(BoostedSwapResult {
+                    amount_calculated_raw,
+                    wrapped_amount_in_raw,
+                    wrapped_amount_out_raw,
+                    wrap_result,
+                    unwrap_result,
+                })
+            }
+            SwapKind::GivenOut => {
+                let (wrapped_amount_out_raw, unwrap_result) =
+                    unwrap(buffer_out, SwapKind::GivenOut, swap_input.amount_raw)?;
+                let wrapped_amount_in_raw = pool_swap(SwapKind::GivenOut, wrapped_amount_out_raw)?;
+                let (amount_calculated_raw, wrap_result) =
+                    wrap(buffer_in, SwapKind::GivenOut, wrapped_amount_in_raw)?;
+                Ok(BoostedSwapResult {
+                    amount_calculated_raw,
+                    wrapped_amount_in_raw,
+                    wrapped_amount_out_raw,
+                    wrap_result,
+                    unwrap_result,
+                })
+            }
+        }
+    }
+
+    /// Add liquidity to a boosted pool in underlying tokens
+    ///
+    /// `max_amounts_in_raw` follow the pool's token order, in underlying tokens for pool tokens
+    /// with a buffer. `Unbalanced` and `Donation` wrap the exact amounts before adding them, while
+    /// `Proportional` and `SingleTokenExactOut` add to the pool first and wrap the pool token
+    /// amounts it asks for.
+    pub fn add_liquidity_boosted(
+        &self,
+        add_liquidity_input: &AddLiquidityInput,
+        pool_state: &PoolState,
+        buffers: &[BufferState],
+        hook_state: Option<&HookState>,
+    ) -> Result<BoostedAddLiquidityResult, PoolError> {
+        let pool_buffers = get_pool_buffers(&pool_state.base().tokens, buffers);
+        match add_liquidity_input.kind {
+            AddLiquidityKind::Unbalanced | AddLiquidityKind::Donation => {
+                let (wrapped_amounts_in_raw, wrap_results) = convert_amounts(
+                    &pool_buffers,
+                    &add_liquidity_input.max_amounts_in_raw,
+                    |buffer, amount_raw| wrap(buffer, SwapKind::GivenIn, amount_raw),
+                )?;
+                let result = self.add_liquidity(
+                    &AddLiquidityInput {
+                        max_amounts_in_raw: wrapped_amounts_in_raw.clone(),
+                        ..add_liquidity_input.clone()
+                    },
+                    pool_state,
+                    hook_state,
+                )?;
+                Ok(BoostedAddLiquidityResult {
+                    bpt_amount_out_raw: result.bpt_amount_out_raw,
+                    amounts_in_raw: add_liquidity_input.max_amounts_in_raw.clone(),
+                    wrapped_amounts_in_raw,
+                    wrap_results,
+                })
+            }
+            AddLiquidityKind::Proportional | AddLiquidityKind::SingleTokenExactOut => {
+                let result = self.add_liquidity(add_liquidity_input, pool_state, hook_state)?;
+                let (amounts_in_raw, wrap_results) = convert_amounts(
+                    &pool_buffers,
+                    &result.amounts_in_raw,
+                    |buffer, amount_raw| wrap(buffer, SwapKind::GivenOut, amount_raw),
+                )?;
+                Ok(BoostedAddLiquidityResult {
+                    bpt_amount_out_raw: result.bpt_amount_out_raw,
+                    amounts_in_raw,
+                    wrapped_amounts_in_raw: result.amounts_in_raw,
+                    wrap_results,
+                })
+            }
+        }
+    }
+
+    /// Remove liquidity from a boosted pool to underlying tokens
+    ///
+    /// `min_amounts_out_raw` follow the pool's token order, in underlying tokens for pool tokens
+    /// with a buffer. `Proportional` and `SingleTokenExactIn` remove from the pool first and
+    /// unwrap what it pays out, while `SingleTokenExactOut` and `Custom` unwrap the exact amounts
+    /// out backwards to the pool token amounts to remove.
+    pub fn remove_liquidity_boosted(
+        &self,
+        remove_liquidity_input: &RemoveLiquidityInput,
+        pool_state: &PoolState,
+        buffers: &[BufferState],
+        hook_state: Option<&HookState>,
+    ) -> Result<BoostedRemoveLiquidityResult, PoolError> {
+        let pool_buffers = get_pool_buffers(&pool_state.base().tokens, buffers);
+        match remove_liquidity_input.kind {
+            RemoveLiquidityKind::Proportional | RemoveLiquidityKind::SingleTokenExactIn => {
+                let result =
+                    self.remove_liquidity(remove_liquidity_input, pool_state, hook_state)?;
+                let (amounts_out_raw, unwrap_results) = convert_amounts(
+                    &pool_buffers,
+                    &result.amounts_out_raw,
+                    |buffer, amount_raw| unwrap(buffer, SwapKind::GivenIn, amount_raw),
+                )?;
+                Ok(BoostedRemoveLiquidityResult {
+                    bpt_amount_in_raw: result.bpt_amount_in_raw,
+                    amounts_out_raw,
+                    wrapped_amounts_out_raw: result.amounts_out_raw,
+                    unwrap_results,
+                })
+            }
+            RemoveLiquidityKind::SingleTokenExactOut | RemoveLiquidityKind::Custom => {
+                let (wrapped_amounts_out_raw, unwrap_results) = convert_amounts(
+                    &pool_buffers,
+                    &remove_liquidity_input.min_amounts_out_raw,
+                    |buffer, amount_raw| unwrap(buffer, SwapKind::GivenOut, amount_raw),
+                )?;
+                let result = self.remove_liquidity(
+                    &RemoveLiquidityInput {
+                        min_amounts_out_raw: wrapped_amounts_out_raw.clone(),
+                        ..remove_liquidity_input.clone()
+                    },
+                    pool_state,
+                    hook_state,
+                )?;
+                Ok(BoostedRemoveLiquidityResult {
+                    bpt_amount_in_raw: result.bpt_amount_in_raw,
+                    amounts_out_raw: remove_liquidity_input.min_amounts_out_raw.clone(),
+                    wrapped_amounts_out_raw,
+                    unwrap_results,
+                })
+            }
+        }
+    }
+}
+
+/// The pool token for a token given as a pool token or as the underlying token of a buffer, with
+/// the buffer to go through if any
+fn resolve_pool_token<'a>(
+    pool_tokens: &[String],
+    buffers: &'a [BufferState],
+    token: &str,
+) -> Option<(String, Option<&'a BufferState>)> {
+    if let Some(pool_token) = pool_tokens.iter().find(|t| t.eq_ignore_ascii_case(token)) {
+        return Some((pool_token.clone(), None));
+    }
+    buffers
+        .iter()
+        .find(|buffer| {
+            buffer.immutable.tokens[1].eq_ignore_ascii_case(token)
+                && pool_tokens
+                    .iter()
+                    .any(|t| t.eq_ignore_ascii_case(&buffer.immutable.pool_address))
+        })
+        .map(|buffer| (buffer.immutable.pool_address.clone(), Some(buffer)))
+}
+
+/// The buffer of each pool token, if any
+fn get_pool_buffers<'a>(
+    pool_tokens: &[String],
+    buffers: &'a [BufferState],
+) -> Vec<Option<&'a BufferState>> {
+    pool_tokens
+        .iter()
+        .map(|token| {
+            buffers
+                .iter()
+                .find(|buffer| buffer.immutable.pool_address.eq_ignore_ascii_case(token))
+        })
+        .collect()
+}
+
+/// Convert the amount of each pool token with a buffer, leaving zero amounts and pool tokens
+/// without a buffer as they are, along with the wrap or unwrap of each buffer used
+#[allow(clippy::type_complexity)]
+fn convert_amounts(
+    pool_buffers: &[Option<&BufferState>],
+    amounts_raw: &[U256],
+    convert: impl Fn(
+        Option<&BufferState>,
+        U256,
+    ) -> Result<(U256, Option<BufferWrapOrUnwrapResult>), PoolError>,
+) -> Result<(Vec<U256>, Vec<Option<BufferWrapOrUnwrapResult>>), PoolError> {
+    pool_buffers
+        .iter()
+        .zip(amounts_raw)
+        .map(|(buffer, amount_raw)| {
+            if amount_raw.is_zero() {
+                Ok((U256::ZERO, None))
+            } else {
+                convert(*buffer, *amount_raw)
+            }
+        })
+        .collect::<Result<Vec<_>, PoolError>>()
+        .map(|converted| converted.into_iter().unzip())
+}
+
+/// Wrap underlying tokens through `buffer`, or pass the amount through without one
+fn wrap(
+    buffer: Option<&BufferState>,
+    swap_kind: SwapKind,
+    amount_raw: U256,
+) -> Result<(U256, Option<BufferWrapOrUnwrapResult>), PoolError> {
+    match buffer {
+        Some(buffer) => {
+            let swap_input = SwapInput {
+                swap_kind,
+                amount_raw,
+                token_in: buffer.immutable.tokens[1].clone(),
+                token_out: buffer.immutable.pool_address.clone(),
+            };
+            let result = erc4626_buffer_wrap_or_unwrap_with_state(&swap_input, buffer)?;
+            Ok((result.amount_calculated_raw, Some(result)))
+        }
+        None => Ok((amount_raw, None)),
+    }
+}
+
+/// Unwrap wrapped tokens through `buffer`, or pass the amount through without one
+fn unwrap(
+    buffer: Option<&BufferState>,
+    swap_kind: SwapKind,
+    amount_raw: U256,
+) -> Result<(U256, Option<BufferWrapOrUnwrapResult>), PoolError> {
+    match buffer {
+        Some(buffer) => {
+            let swap_input = SwapInput {
+                swap_kind,
+                amount_raw,
+                token_in: buffer.immutable.pool_address.clone(),
+                token_out: buffer.immutable.tokens[1].clone(),
+            };
+            let result = erc4626_buffer_wrap_or_unwrap_with_state(&swap_input, buffer)?;
+            Ok((result.amount_calculated_raw, Some(result)))
+        }
+        None => Ok((amount_raw, None)),
+    }
+}
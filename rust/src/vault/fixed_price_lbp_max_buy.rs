@@ -0,0 +1,48 @@
+//! Largest buy a FixedPriceLBP can fill
+//!
+//! The pool sells every project token it holds at the project token rate, so the largest buy is
+//! its whole project balance, quoted through the vault swap path to include the swap fee.
+
+use crate::common::errors::PoolError;
+use crate::common::types::*;
+use crate::common::utils::to_raw_undo_rate_round_down;
+use crate::hooks::DefaultHook;
+use crate::pools::fixed_price_lbp::{FixedPriceLBPMaxBuy, FixedPriceLBPPool, FixedPriceLBPState};
+use crate::vault::swap::swap;
+
+/// Get the largest buy the pool can fill at its current timestamp: every project token left
+///
+/// The reserve amount in is the vault's `GivenOut` quote for that amount, swap fee included.
+/// Fails with `SaleWindowClosed` outside the sale window.
+pub fn fixed_price_lbp_max_buy(
+    pool_state: &FixedPriceLBPState,
+) -> Result<FixedPriceLBPMaxBuy, PoolError> {
+    let pool = FixedPriceLBPPool::new(pool_state.clone());
+    pool.ensure_sale_active()?;
+
+    let base = &pool_state.base;
+    let project_index = pool_state.immutable.project_token_index;
+    let reserve_index = pool_state.immutable.reserve_token_index;
+    let project_amount_out_raw = to_raw_undo_rate_round_down(
+        &base.balances_live_scaled_18[project_index],
+        &base.scaling_factors[project_index],
+        &base.token_rates[project_index],
+    )?;
+    let reserve_amount_in_raw = swap(
+        &SwapInput {
+            swap_kind: SwapKind::GivenOut,
+            amount_raw: project_amount_out_raw,
+            token_in: base.tokens[reserve_index].clone(),
+            token_out: base.tokens[project_index].clone(),
+        },
+        &PoolState::FixedPriceLBP(pool_state.clone()),
+        &pool,
+        &DefaultHook::new(),
+        None,
+    )?;
+
+    Ok(FixedPriceLBPMaxBuy {
+        project_amount_out_raw,
+        reserve_amount_in_raw,
+    })
+}
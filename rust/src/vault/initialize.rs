@@ -0,0 +1,130 @@
+//! Vault pool initialization
+
+use crate::common::errors::PoolError;
+use crate::common::pool_base::PoolBase;
+use crate::common::types::*;
+use crate::common::utils::{copy_to_scaled18_apply_rate_round_down_array, require_unpaused};
+use crate::pools::reclamm::{reclamm_math, ReClammPool};
+use crate::pools::reclammv2::{reclammv2_math, ReClammV2Pool};
+use alloy_primitives::{uint, U256};
+
+/// BPT minted to the zero address on initialization, so the total supply can never reach zero
+pub const POOL_MINIMUM_TOTAL_SUPPLY: U256 = uint!(1_000_000_U256);
+
+/// Initialize a pool with its first deposit
+///
+/// The BPT minted is the invariant of the initial balances, rounded down, of which
+/// `POOL_MINIMUM_TOTAL_SUPPLY` is locked. ReClamm pools first set their virtual balances from
+/// the initial price range and target price. Hooks are not called.
+pub fn initialize(
+    initialize_input: &InitializeInput,
+    pool_state: &PoolState,
+    pool_class: &dyn PoolBase,
+) -> Result<InitializeResult, PoolError> {
+    require_unpaused(pool_state)?;
+    let base_state = pool_state.base();
+
+    if initialize_input.exact_amounts_in_raw.len() != base_state.tokens.len() {
+        return Err(PoolError::InvalidInput(
+            "exact amounts in length must match tokens".to_string(),
+        ));
+    }
+
+    // Amounts are entering pool math, so round down
+    let amounts_in_scaled18 = copy_to_scaled18_apply_rate_round_down_array(
+        &initialize_input.exact_amounts_in_raw,
+        &base_state.scaling_factors,
+        &base_state.token_rates,
+    )?;
+
+    let mut initialized_state = pool_state.clone();
+    initialized_state.base_mut().balances_live_scaled_18 = amounts_in_scaled18.clone();
+
+    let (bpt_amount_out, virtual_balances) = match &mut initialized_state {
+        PoolState::ReClamm(re_clamm_state) => {
+            let price_params = initialize_input
+                .reclamm_price_params
+                .as_ref()
+                .ok_or_else(|| {
+                    PoolError::InvalidInput("ReClamm price params are required".to_string())
+                })?;
+            let (virtual_balance_a, virtual_balance_b, fourth_root_price_ratio) =
+                reclamm_math::compute_initial_virtual_balances(
+                    &amounts_in_scaled18,
+                    &price_params.initial_min_price,
+                    &price_params.initial_max_price,
+                    &price_params.initial_target_price,
+                )
+                .map_err(PoolError::Custom)?;
+
+            let mutable = &mut re_clamm_state.mutable;
+            mutable.last_virtual_balances = vec![virtual_balance_a, virtual_balance_b];
+            mutable.last_timestamp = mutable.current_timestamp;
+            mutable.start_fourth_root_price_ratio = fourth_root_price_ratio;
+            mutable.end_fourth_root_price_ratio = fourth_root_price_ratio;
+            mutable.price_ratio_update_start_time = mutable.current_timestamp;
+            mutable.price_ratio_update_end_time = mutable.current_timestamp;
+
+            let (virtual_balance_a, virtual_balance_b, _) =
+                ReClammPool::new(re_clamm_state.clone())
+                    .current_virtual_balances(&amounts_in_scaled18);
+            let invariant = reclamm_math::compute_invariant(
+                &amounts_in_scaled18,
+                &virtual_balance_a,
+                &virtual_balance_b,
+                Rounding::RoundDown,
+            );
+            (invariant, Some(vec![virtual_balance_a, virtual_balance_b]))
+        }
+        PoolState::ReClammV2(re_clamm_v2_state) => {
+            let price_params = initialize_input
+                .reclamm_price_params
+                .as_ref()
+                .ok_or_else(|| {
+                    PoolError::InvalidInput("ReClamm price params are required".to_string())
+                })?;
+            let (virtual_balance_a, virtual_balance_b, fourth_root_price_ratio) =
+                reclammv2_math::compute_initial_virtual_balances(
+                    &amounts_in_scaled18,
+                    &price_params.initial_min_price,
+                    &price_params.initial_max_price,
+                    &price_params.initial_target_price,
+                )
+                .map_err(PoolError::Custom)?;
+
+            let mutable = &mut re_clamm_v2_state.mutable;
+            mutable.last_virtual_balances = vec![virtual_balance_a, virtual_balance_b];
+            mutable.last_timestamp = mutable.current_timestamp;
+            mutable.start_fourth_root_price_ratio = fourth_root_price_ratio;
+            mutable.end_fourth_root_price_ratio = fourth_root_price_ratio;
+            mutable.price_ratio_update_start_time = mutable.current_timestamp;
+            mutable.price_ratio_update_end_time = mutable.current_timestamp;
+
+            let (virtual_balance_a, virtual_balance_b, _) =
+                ReClammV2Pool::new(re_clamm_v2_state.clone())
+                    .current_virtual_balances(&amounts_in_scaled18);
+            let invariant = reclammv2_math::compute_invariant(
+                &amounts_in_scaled18,
+                &virtual_balance_a,
+                &virtual_balance_b,
+                Rounding::RoundDown,
+            );
+            (invariant, Some(vec![virtual_balance_a, virtual_balance_b]))
+        }
+        _ => (
+            pool_class.compute_invariant(&amounts_in_scaled18, Rounding::RoundDown)?,
+            None,
+        ),
+    };
+
+    if bpt_amount_out < POOL_MINIMUM_TOTAL_SUPPLY {
+        return Err(PoolError::PoolTotalSupplyTooLow);
+    }
+    initialized_state.base_mut().total_supply = bpt_amount_out;
+
+    Ok(InitializeResult {
+        bpt_amount_out_raw: bpt_amount_out - POOL_MINIMUM_TOTAL_SUPPLY,
+        virtual_balances,
+        pool_state: initialized_state,
+    })
+}
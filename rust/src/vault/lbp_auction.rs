@@ -0,0 +1,172 @@
+//! Simulation of a Liquidity Bootstrapping pool auction against a stream of orders
+//!
+//! Orders are applied in timestamp order through the vault swap path, so each fill is what the
+//! vault would quote at that timestamp (interpolated weights, swap fees and rates included) and
+//! the pool's live balances carry over from one order to the next. Swaps are enabled between
+//! `start_time` and `end_time` inclusive, as in the on-chain pool.
+
+use crate::common::constants::WAD;
+use crate::common::errors::PoolError;
+use crate::common::types::*;
+use crate::hooks::DefaultHook;
+use crate::pools::liquidity_bootstrapping::{
+    LiquidityBootstrappingPool, LiquidityBootstrappingState,
+};
+use crate::vault::swap::swap_with_balances;
+use alloy_primitives::{I256, U256};
+
+/// Side of an LBP order, from the trader's point of view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LbpOrderSide {
+    /// Pay reserve tokens for project tokens
+    Buy,
+    /// Pay project tokens for reserve tokens
+    Sell,
+}
+
+/// A timestamped order against an LBP
+#[derive(Debug, Clone, PartialEq)]
+pub struct LbpOrder {
+    pub timestamp: U256,
+    pub side: LbpOrderSide,
+    /// `GivenIn` if `amount_raw` is the exact amount paid, `GivenOut` if it is the exact amount
+    /// received
+    pub swap_kind: SwapKind,
+    pub amount_raw: U256,
+}
+
+/// Outcome of one order of an LBP auction
+#[derive(Debug, Clone, PartialEq)]
+pub struct LbpFill {
+    pub timestamp: U256,
+    pub side: LbpOrderSide,
+    pub amount_in_raw: U256,
+    pub amount_out_raw: U256,
+    /// Average price paid or received, in reserve tokens per project token, fees included
+    /// (18 decimals)
+    pub clearing_price: U256,
+    /// Spot price of the project token in reserve tokens after the fill, excluding fees
+    /// (18 decimals)
+    pub spot_price_after: U256,
+}
+
+/// Outcome of an LBP auction
+#[derive(Debug, Clone, PartialEq)]
+pub struct LbpAuctionResult {
+    pub fills: Vec<LbpFill>,
+    /// Reserve tokens paid in by buyers less reserve tokens paid out to sellers (raw). Aggregate
+    /// swap fees are included, so the pool's reserve balance grows by less than this.
+    pub reserve_proceeds_raw: I256,
+    /// Project tokens paid out to buyers less project tokens paid in by sellers (raw)
+    pub project_tokens_sold_raw: I256,
+    /// Pool state at `end_time`, or at the last order if it is later
+    pub final_state: LiquidityBootstrappingState,
+}
+
+/// Run an LBP auction from its current state, applying `orders` in sequence
+///
+/// Orders must be sorted by timestamp and not precede the current timestamp of the pool. Any
+/// order the pool rejects (outside the sale window, a sell while project token swap in is
+/// blocked, or an amount the balances cannot cover) fails the whole simulation.
+pub fn simulate_lbp_auction(
+    pool_state: &LiquidityBootstrappingState,
+    orders: &[LbpOrder],
+) -> Result<LbpAuctionResult, PoolError> {
+    let project_index = pool_state.immutable.project_token_index;
+    let reserve_index = 1 - project_index;
+    let project_token = pool_state.base.tokens[project_index].clone();
+    let reserve_token = pool_state.base.tokens[reserve_index].clone();
+    let project_scaling_factor = pool_state.base.scaling_factors[project_index];
+    let reserve_scaling_factor = pool_state.base.scaling_factors[reserve_index];
+
+    let mut state = pool_state.clone();
+    let mut fills = Vec::with_capacity(orders.len());
+    let mut reserve_proceeds_raw = I256::ZERO;
+    let mut project_tokens_sold_raw = I256::ZERO;
+
+    for order in orders {
+        if order.timestamp < state.mutable.current_timestamp {
+            return Err(PoolError::InvalidInput(
+                "Orders must be sorted by timestamp".to_string(),
+            ));
+        }
+        set_timestamp(&mut state, order.timestamp);
+
+        let (token_in, token_out) = match order.side {
+            LbpOrderSide::Buy => (&reserve_token, &project_token),
+            LbpOrderSide::Sell => (&project_token, &reserve_token),
+        };
+        let swap_input = SwapInput {
+            swap_kind: order.swap_kind.clone(),
+            amount_raw: order.amount_raw,
+            token_in: token_in.clone(),
+            token_out: token_out.clone(),
+        };
+
+        let pool_state = PoolState::LiquidityBootstrapping(state.clone());
+        let pool = LiquidityBootstrappingPool::new(state.clone())?;
+        let (amount_calculated_raw, updated_balances) =
+            swap_with_balances(&swap_input, &pool_state, &pool, &DefaultHook::new(), None)?;
+        state.base.balances_live_scaled_18 = updated_balances;
+
+        let (amount_in_raw, amount_out_raw) = match order.swap_kind {
+            SwapKind::GivenIn => (order.amount_raw, amount_calculated_raw),
+            SwapKind::GivenOut => (amount_calculated_raw, order.amount_raw),
+        };
+        let (reserve_amount_raw, project_amount_raw) = match order.side {
+            LbpOrderSide::Buy => {
+                reserve_proceeds_raw += to_i256(amount_in_raw)?;
+                project_tokens_sold_raw += to_i256(amount_out_raw)?;
+                (amount_in_raw, amount_out_raw)
+            }
+            LbpOrderSide::Sell => {
+                reserve_proceeds_raw -= to_i256(amount_out_raw)?;
+                project_tokens_sold_raw -= to_i256(amount_in_raw)?;
+                (amount_out_raw, amount_in_raw)
+            }
+        };
+
+        // Scaling factors bring raw amounts to 18 decimals, so the price is in whole tokens
+        let project_amount_scaled_18 = project_amount_raw * project_scaling_factor;
+        let clearing_price = if project_amount_scaled_18.is_zero() {
+            U256::ZERO
+        } else {
+            reserve_amount_raw * reserve_scaling_factor * WAD / project_amount_scaled_18
+        };
+        let spot_price_after = LiquidityBootstrappingPool::new(state.clone())?
+            .price_curve(&[order.timestamp])?[0]
+            .project_token_price;
+
+        fills.push(LbpFill {
+            timestamp: order.timestamp,
+            side: order.side,
+            amount_in_raw,
+            amount_out_raw,
+            clearing_price,
+            spot_price_after,
+        });
+    }
+
+    if state.mutable.current_timestamp < state.immutable.end_time {
+        let end_time = state.immutable.end_time;
+        set_timestamp(&mut state, end_time);
+    }
+
+    Ok(LbpAuctionResult {
+        fills,
+        reserve_proceeds_raw,
+        project_tokens_sold_raw,
+        final_state: state,
+    })
+}
+
+/// Move the pool to `timestamp`, enabling swaps only within the sale window
+fn set_timestamp(state: &mut LiquidityBootstrappingState, timestamp: U256) {
+    state.mutable.current_timestamp = timestamp;
+    state.mutable.is_swap_enabled =
+        timestamp >= state.immutable.start_time && timestamp <= state.immutable.end_time;
+}
+
+fn to_i256(amount: U256) -> Result<I256, PoolError> {
+    I256::try_from(amount).map_err(|_| PoolError::MathOverflow)
+}
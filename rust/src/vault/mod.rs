@@ -1,9 +1,20 @@
 //! Vault operations for Balancer pools
 
 pub mod add_liquidity;
+pub mod approx_swap;
 pub mod base_pool_math;
+pub mod boosted;
+pub mod fixed_price_lbp_max_buy;
+pub mod initialize;
+pub mod lbp_auction;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod prepared_pool;
 pub mod remove_liquidity;
 pub mod swap;
+pub mod swap_to_price;
+
+pub use prepared_pool::PreparedPool;
 
 use crate::common::errors::PoolError;
 use crate::common::pool_base::PoolBase;
@@ -12,10 +23,20 @@ use crate::hooks::types::HookState;
 use crate::hooks::{
     AkronHook, DefaultHook, DirectionalFeeHook, ExitFeeHook, HookBase, StableSurgeHook,
 };
-use crate::pools::buffer::erc4626_buffer_wrap_or_unwrap;
-use crate::vault::add_liquidity::add_liquidity;
-use crate::vault::remove_liquidity::remove_liquidity;
-use crate::vault::swap::swap;
+use crate::pools::buffer::{
+    erc4626_buffer_wrap_or_unwrap, erc4626_buffer_wrap_or_unwrap_with_state, BufferState,
+    BufferWrapOrUnwrapResult,
+};
+use crate::pools::fixed_price_lbp::{FixedPriceLBPMaxBuy, FixedPriceLBPState};
+use crate::pools::liquidity_bootstrapping::LiquidityBootstrappingState;
+use crate::vault::add_liquidity::{add_liquidity, add_liquidity_single_token_exact_in};
+use crate::vault::approx_swap::swap_approx;
+use crate::vault::fixed_price_lbp_max_buy::fixed_price_lbp_max_buy;
+use crate::vault::initialize::initialize;
+use crate::vault::lbp_auction::{simulate_lbp_auction, LbpAuctionResult, LbpOrder};
+use crate::vault::remove_liquidity::{remove_liquidity, remove_liquidity_recovery};
+use crate::vault::swap::{swap, swap_ladder};
+use crate::vault::swap_to_price::{spot_price, swap_to_price};
 use alloy_primitives::U256;
 
 /// Main vault interface for pool operations
@@ -30,10 +51,10 @@ impl Vault {
     /// Get hook instance based on hook type
     fn get_hook(
         &self,
-        hook_type: &Option<String>,
+        pool_state: &PoolState,
         hook_state: Option<&HookState>,
     ) -> Box<dyn HookBase> {
-        match hook_type {
+        match &pool_state.base().hook_type {
             Some(hook_type) => match hook_type.as_str() {
                 "Akron" => {
                     if let Some(HookState::Akron(_)) = hook_state {
@@ -51,7 +72,13 @@ impl Vault {
                 }
                 "StableSurge" => {
                     if let Some(HookState::StableSurge(_)) = hook_state {
-                        Box::new(StableSurgeHook::new())
+                        // The hook simulates swaps with the pool's live amp
+                        match pool_state {
+                            PoolState::Stable(stable_state) => {
+                                Box::new(StableSurgeHook::for_pool(&stable_state.mutable))
+                            }
+                            _ => Box::new(StableSurgeHook::new()),
+                        }
                     } else {
                         Box::new(DefaultHook::new())
                     }
@@ -69,82 +96,8 @@ impl Vault {
         }
     }
 
-    /// Perform a swap operation
-    pub fn swap(
-        &self,
-        swap_input: &SwapInput,
-        pool_state_or_buffer: &PoolStateOrBuffer,
-        hook_state: Option<&HookState>,
-    ) -> Result<U256, PoolError> {
-        match pool_state_or_buffer {
-            PoolStateOrBuffer::Pool(pool_state) => {
-                let base_state = pool_state.base();
-
-                // Create pool instance
-                let pool: Box<dyn PoolBase> = match pool_state.as_ref() {
-                    PoolState::Weighted(weighted_state) => {
-                        // Use the weights from the WeightedState directly
-                        Box::new(crate::pools::weighted::WeightedPool::from(
-                            weighted_state.clone(),
-                        ))
-                    }
-                    PoolState::Stable(stable_state) => Box::new(
-                        crate::pools::stable::StablePool::new(stable_state.mutable.clone()),
-                    ),
-                    PoolState::GyroECLP(gyro_eclp_state) => Box::new(
-                        crate::pools::gyro::GyroECLPPool::new(gyro_eclp_state.immutable.clone()),
-                    ),
-                    PoolState::QuantAmm(quant_amm_state) => Box::new(
-                        crate::pools::quantamm::QuantAmmPool::from(quant_amm_state.clone()),
-                    ),
-                    PoolState::LiquidityBootstrapping(liquidity_bootstrapping_state) => Box::new(
-                        crate::pools::liquidity_bootstrapping::LiquidityBootstrappingPool::from(
-                            liquidity_bootstrapping_state.clone(),
-                        ),
-                    ),
-                    PoolState::FixedPriceLBP(fixed_price_lbp_state) => {
-                        Box::new(crate::pools::fixed_price_lbp::FixedPriceLBPPool::from(
-                            fixed_price_lbp_state.clone(),
-                        ))
-                    }
-                    PoolState::ReClamm(re_clamm_state) => Box::new(
-                        crate::pools::reclamm::ReClammPool::new(re_clamm_state.clone()),
-                    ),
-                    PoolState::ReClammV2(re_clamm_v2_state) => Box::new(
-                        crate::pools::reclammv2::ReClammV2Pool::new(re_clamm_v2_state.clone()),
-                    ),
-                    _ => return Err(PoolError::UnsupportedPoolType(base_state.pool_type.clone())),
-                };
-
-                // Get hook instance
-                let hook: Box<dyn HookBase> = self.get_hook(&base_state.hook_type, hook_state);
-
-                // Execute swap
-                swap(
-                    swap_input,
-                    pool_state,
-                    pool.as_ref(),
-                    hook.as_ref(),
-                    hook_state,
-                )
-            }
-            PoolStateOrBuffer::Buffer(buffer_state) => Ok(erc4626_buffer_wrap_or_unwrap(
-                swap_input,
-                buffer_state.as_ref(),
-            )?),
-        }
-    }
-
-    /// Add liquidity to a pool
-    pub fn add_liquidity(
-        &self,
-        add_liquidity_input: &AddLiquidityInput,
-        pool_state: &PoolState,
-        hook_state: Option<&HookState>,
-    ) -> Result<AddLiquidityResult, PoolError> {
-        let base_state = pool_state.base();
-
-        // Create pool instance
+    /// Get pool instance based on pool state
+    fn get_pool(&self, pool_state: &PoolState) -> Result<Box<dyn PoolBase>, PoolError> {
         let pool: Box<dyn PoolBase> = match pool_state {
             PoolState::Weighted(weighted_state) => {
                 // Use the weights from the WeightedState directly
@@ -177,11 +130,225 @@ impl Vault {
             PoolState::ReClammV2(re_clamm_v2_state) => Box::new(
                 crate::pools::reclammv2::ReClammV2Pool::new(re_clamm_v2_state.clone()),
             ),
-            _ => return Err(PoolError::UnsupportedPoolType(base_state.pool_type.clone())),
+            _ => {
+                return Err(PoolError::UnsupportedPoolType(
+                    pool_state.base().pool_type.clone(),
+                ))
+            }
         };
 
+        Ok(pool)
+    }
+
+    /// Perform a swap operation
+    pub fn swap(
+        &self,
+        swap_input: &SwapInput,
+        pool_state_or_buffer: &PoolStateOrBuffer,
+        hook_state: Option<&HookState>,
+    ) -> Result<U256, PoolError> {
+        match pool_state_or_buffer {
+            PoolStateOrBuffer::Pool(pool_state) => {
+                // Create pool instance
+                let pool = self.get_pool(pool_state)?;
+
+                // Get hook instance
+                let hook: Box<dyn HookBase> = self.get_hook(pool_state, hook_state);
+
+                // Execute swap
+                swap(
+                    swap_input,
+                    pool_state,
+                    pool.as_ref(),
+                    hook.as_ref(),
+                    hook_state,
+                )
+            }
+            PoolStateOrBuffer::Buffer(buffer_state) => {
+                erc4626_buffer_wrap_or_unwrap(swap_input, buffer_state.as_ref())
+            }
+        }
+    }
+
+    /// Wrap or unwrap through an ERC4626 buffer, returning the buffer state it leaves
+    ///
+    /// Reports whether the buffer's balances served the operation or the Vault had to go
+    /// through the ERC4626 vault. See `erc4626_buffer_wrap_or_unwrap_with_state` for details.
+    pub fn buffer_wrap_or_unwrap(
+        &self,
+        swap_input: &SwapInput,
+        buffer_state: &BufferState,
+    ) -> Result<BufferWrapOrUnwrapResult, PoolError> {
+        erc4626_buffer_wrap_or_unwrap_with_state(swap_input, buffer_state)
+    }
+
+    /// Approximate a swap operation with f64 math
+    ///
+    /// Much cheaper than `swap` and meant for pre-screening routes; see `approx_swap` for the
+    /// error bounds against the exact result. Hooks are not applied.
+    pub fn swap_approx(
+        &self,
+        swap_input: &SwapInput,
+        pool_state_or_buffer: &PoolStateOrBuffer,
+    ) -> Result<f64, PoolError> {
+        swap_approx(swap_input, pool_state_or_buffer)
+    }
+
+    /// Perform a swap operation against a prepared pool
+    ///
+    /// Gives the same result as `swap` on the pool state the prepared pool was built from,
+    /// without recomputing per-state values such as the invariant or virtual balances.
+    pub fn swap_prepared(
+        &self,
+        swap_input: &SwapInput,
+        prepared_pool: &PreparedPool,
+        hook_state: Option<&HookState>,
+    ) -> Result<U256, PoolError> {
+        let pool_state = prepared_pool.pool_state();
+        let hook: Box<dyn HookBase> = self.get_hook(pool_state, hook_state);
+
+        swap(
+            swap_input,
+            pool_state,
+            prepared_pool.pool(),
+            hook.as_ref(),
+            hook_state,
+        )
+    }
+
+    /// Perform a swap operation for each amount of a ladder
+    ///
+    /// Results are identical to calling `swap` once per amount, but the pool, hook, token
+    /// indices and per-state values such as the invariant are only set up once. Errors that
+    /// apply to the whole ladder (unknown tokens, unsupported pool type) are returned as the
+    /// outer error; errors for individual amounts are returned in place.
+    pub fn swap_ladder(
+        &self,
+        pool_state_or_buffer: &PoolStateOrBuffer,
+        token_in: &str,
+        token_out: &str,
+        swap_kind: SwapKind,
+        amounts_raw: &[U256],
+        hook_state: Option<&HookState>,
+    ) -> Result<Vec<Result<U256, PoolError>>, PoolError> {
+        match pool_state_or_buffer {
+            PoolStateOrBuffer::Pool(pool_state) => {
+                let prepared_pool = PreparedPool::new(pool_state.as_ref().clone())?;
+                let hook: Box<dyn HookBase> = self.get_hook(pool_state, hook_state);
+
+                swap_ladder(
+                    token_in,
+                    token_out,
+                    swap_kind,
+                    amounts_raw,
+                    pool_state,
+                    prepared_pool.pool(),
+                    hook.as_ref(),
+                    hook_state,
+                )
+            }
+            PoolStateOrBuffer::Buffer(buffer_state) => Ok(amounts_raw
+                .iter()
+                .map(|amount_raw| {
+                    let swap_input = SwapInput {
+                        amount_raw: *amount_raw,
+                        swap_kind: swap_kind.clone(),
+                        token_in: token_in.to_string(),
+                        token_out: token_out.to_string(),
+                    };
+                    erc4626_buffer_wrap_or_unwrap(&swap_input, buffer_state.as_ref())
+                })
+                .collect()),
+        }
+    }
+
+    /// Get the marginal price of `base_token` quoted in `quote_token`, excluding fees
+    ///
+    /// The price is computed from the live balances (scaled 18) and returned in 18 decimals.
+    pub fn spot_price(
+        &self,
+        pool_state: &PoolState,
+        base_token: &str,
+        quote_token: &str,
+    ) -> Result<U256, PoolError> {
+        spot_price(pool_state, base_token, quote_token)
+    }
+
+    /// Find the swap that moves the marginal price of `base_token` to `target_price_scaled_18`
+    ///
+    /// Returns the token indices and the exact raw amounts in and out (after fees) of the
+    /// smallest `GivenIn` swap that reaches the target. See `swap_to_price` for details.
+    pub fn swap_to_price(
+        &self,
+        pool_state: &PoolState,
+        base_token: &str,
+        quote_token: &str,
+        target_price_scaled_18: &U256,
+        hook_state: Option<&HookState>,
+    ) -> Result<SwapToTargetPriceResult, PoolError> {
+        let prepared_pool = PreparedPool::new(pool_state.clone())?;
+        let hook: Box<dyn HookBase> = self.get_hook(pool_state, hook_state);
+
+        swap_to_price(
+            base_token,
+            quote_token,
+            target_price_scaled_18,
+            pool_state,
+            prepared_pool.pool(),
+            hook.as_ref(),
+            hook_state,
+        )
+    }
+
+    /// Simulate a Liquidity Bootstrapping pool auction against a stream of timestamped orders
+    ///
+    /// Returns each fill with its clearing price, the net reserve proceeds and project tokens
+    /// sold, and the pool state at the end of the sale. See `simulate_lbp_auction` for details.
+    pub fn simulate_lbp_auction(
+        &self,
+        pool_state: &LiquidityBootstrappingState,
+        orders: &[LbpOrder],
+    ) -> Result<LbpAuctionResult, PoolError> {
+        simulate_lbp_auction(pool_state, orders)
+    }
+
+    /// Get the largest buy a FixedPriceLBP can fill at its current timestamp
+    ///
+    /// Returns every project token left and the reserve tokens they cost, swap fee included.
+    /// See `fixed_price_lbp_max_buy` for details.
+    pub fn fixed_price_lbp_max_buy(
+        &self,
+        pool_state: &FixedPriceLBPState,
+    ) -> Result<FixedPriceLBPMaxBuy, PoolError> {
+        fixed_price_lbp_max_buy(pool_state)
+    }
+
+    /// Initialize a pool with raw initial amounts
+    ///
+    /// Returns the BPT minted to the initializer, the initial virtual balances of ReClamm pools
+    /// and the pool state after initialization. See `initialize` for details.
+    pub fn initialize(
+        &self,
+        initialize_input: &InitializeInput,
+        pool_state: &PoolState,
+    ) -> Result<InitializeResult, PoolError> {
+        let pool = self.get_pool(pool_state)?;
+
+        initialize(initialize_input, pool_state, pool.as_ref())
+    }
+
+    /// Add liquidity to a pool
+    pub fn add_liquidity(
+        &self,
+        add_liquidity_input: &AddLiquidityInput,
+        pool_state: &PoolState,
+        hook_state: Option<&HookState>,
+    ) -> Result<AddLiquidityResult, PoolError> {
+        // Create pool instance
+        let pool = self.get_pool(pool_state)?;
+
         // Create hook instance
-        let hook: Box<dyn HookBase> = self.get_hook(&base_state.hook_type, hook_state);
+        let hook: Box<dyn HookBase> = self.get_hook(pool_state, hook_state);
 
         add_liquidity(
             add_liquidity_input,
@@ -192,6 +359,32 @@ impl Vault {
         )
     }
 
+    /// Add an exact raw amount of a single token to a pool
+    ///
+    /// Returns the BPT out and the swap fee charged on the token. See
+    /// `add_liquidity_single_token_exact_in` for the solving methods.
+    pub fn add_liquidity_single_token_exact_in(
+        &self,
+        pool_state: &PoolState,
+        token_in: &str,
+        exact_amount_in_raw: &U256,
+        method: SingleTokenExactInMethod,
+        hook_state: Option<&HookState>,
+    ) -> Result<AddLiquiditySingleTokenExactInResult, PoolError> {
+        let pool = self.get_pool(pool_state)?;
+        let hook: Box<dyn HookBase> = self.get_hook(pool_state, hook_state);
+
+        add_liquidity_single_token_exact_in(
+            token_in,
+            exact_amount_in_raw,
+            method,
+            pool_state,
+            pool.as_ref(),
+            hook.as_ref(),
+            hook_state,
+        )
+    }
+
     /// Remove liquidity from a pool
     pub fn remove_liquidity(
         &self,
@@ -199,46 +392,11 @@ impl Vault {
         pool_state: &PoolState,
         hook_state: Option<&HookState>,
     ) -> Result<RemoveLiquidityResult, PoolError> {
-        let base_state = pool_state.base();
-
         // Create pool instance
-        let pool: Box<dyn PoolBase> = match pool_state {
-            PoolState::Weighted(weighted_state) => {
-                // Use the weights from the WeightedState directly
-                Box::new(crate::pools::weighted::WeightedPool::from(
-                    weighted_state.clone(),
-                ))
-            }
-            PoolState::Stable(stable_state) => Box::new(crate::pools::stable::StablePool::new(
-                stable_state.mutable.clone(),
-            )),
-            PoolState::GyroECLP(gyro_eclp_state) => Box::new(
-                crate::pools::gyro::GyroECLPPool::new(gyro_eclp_state.immutable.clone()),
-            ),
-            PoolState::QuantAmm(quant_amm_state) => Box::new(
-                crate::pools::quantamm::QuantAmmPool::from(quant_amm_state.clone()),
-            ),
-            PoolState::LiquidityBootstrapping(liquidity_bootstrapping_state) => Box::new(
-                crate::pools::liquidity_bootstrapping::LiquidityBootstrappingPool::from(
-                    liquidity_bootstrapping_state.clone(),
-                ),
-            ),
-            PoolState::FixedPriceLBP(fixed_price_lbp_state) => {
-                Box::new(crate::pools::fixed_price_lbp::FixedPriceLBPPool::from(
-                    fixed_price_lbp_state.clone(),
-                ))
-            }
-            PoolState::ReClamm(re_clamm_state) => Box::new(
-                crate::pools::reclamm::ReClammPool::new(re_clamm_state.clone()),
-            ),
-            PoolState::ReClammV2(re_clamm_v2_state) => Box::new(
-                crate::pools::reclammv2::ReClammV2Pool::new(re_clamm_v2_state.clone()),
-            ),
-            _ => return Err(PoolError::UnsupportedPoolType(base_state.pool_type.clone())),
-        };
+        let pool = self.get_pool(pool_state)?;
 
         // Create hook instance
-        let hook: Box<dyn HookBase> = self.get_hook(&base_state.hook_type, hook_state);
+        let hook: Box<dyn HookBase> = self.get_hook(pool_state, hook_state);
 
         remove_liquidity(
             remove_liquidity_input,
@@ -248,6 +406,17 @@ impl Vault {
             hook_state,
         )
     }
+
+    /// Remove liquidity proportionally from a pool in recovery mode
+    ///
+    /// Works on paused pools and bypasses hooks and fees, as the Vault's recovery exit does.
+    pub fn remove_liquidity_recovery(
+        &self,
+        pool_state: &PoolState,
+        exact_bpt_amount_in_raw: &U256,
+    ) -> Result<RemoveLiquidityResult, PoolError> {
+        remove_liquidity_recovery(exact_bpt_amount_in_raw, pool_state)
+    }
 }
 
 impl Default for Vault {
@@ -0,0 +1,65 @@
+//! Parallel quoting against many pools, available with the `parallel` feature
+
+use crate::common::errors::PoolError;
+use crate::common::types::*;
+use crate::hooks::types::HookState;
+use crate::vault::{PreparedPool, Vault};
+use alloy_primitives::U256;
+use rayon::prelude::*;
+
+/// A pool or buffer to quote against, with the hook state for its hook (if any)
+pub type PoolQuoteTarget<'a> = (&'a PoolStateOrBuffer, Option<&'a HookState>);
+
+impl Vault {
+    /// Quote one swap against many pools concurrently
+    ///
+    /// Results are returned in the same order as `pools`, and each result is identical to
+    /// calling `swap` with the same swap input, pool and hook state.
+    pub fn swap_pools_parallel(
+        &self,
+        swap_input: &SwapInput,
+        pools: &[PoolQuoteTarget],
+    ) -> Vec<Result<U256, PoolError>> {
+        pools
+            .par_iter()
+            .map(|(pool_state_or_buffer, hook_state)| {
+                self.swap(swap_input, pool_state_or_buffer, *hook_state)
+            })
+            .collect()
+    }
+
+    /// Quote a list of swaps against many pools concurrently
+    ///
+    /// Returns one list of results per pool, in the same order as `pools`, with results
+    /// for each pool in the same order as `swap_inputs`. Each pool is prepared once and
+    /// reused for all swap inputs, so a pool that fails to prepare reports that error for
+    /// every input.
+    pub fn swap_many_pools_parallel(
+        &self,
+        swap_inputs: &[SwapInput],
+        pools: &[PoolQuoteTarget],
+    ) -> Vec<Vec<Result<U256, PoolError>>> {
+        pools
+            .par_iter()
+            .map(
+                |(pool_state_or_buffer, hook_state)| match pool_state_or_buffer {
+                    PoolStateOrBuffer::Pool(pool_state) => {
+                        match PreparedPool::new(pool_state.as_ref().clone()) {
+                            Ok(prepared_pool) => swap_inputs
+                                .iter()
+                                .map(|swap_input| {
+                                    self.swap_prepared(swap_input, &prepared_pool, *hook_state)
+                                })
+                                .collect(),
+                            Err(error) => vec![Err(error); swap_inputs.len()],
+                        }
+                    }
+                    PoolStateOrBuffer::Buffer(_) => swap_inputs
+                        .iter()
+                        .map(|swap_input| self.swap(swap_input, pool_state_or_buffer, *hook_state))
+                        .collect(),
+                },
+            )
+            .collect()
+    }
+}
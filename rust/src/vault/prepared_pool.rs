@@ -0,0 +1,81 @@
+//! Prepared pools for repeated quoting against a single pool state
+
+use crate::common::errors::PoolError;
+use crate::common::pool_base::PoolBase;
+use crate::common::types::PoolState;
+use crate::pools::fixed_price_lbp::FixedPriceLBPPool;
+use crate::pools::gyro::GyroECLPPool;
+use crate::pools::liquidity_bootstrapping::LiquidityBootstrappingPool;
+use crate::pools::quantamm::QuantAmmPool;
+use crate::pools::reclamm::ReClammPool;
+use crate::pools::reclammv2::ReClammV2Pool;
+use crate::pools::stable::StablePool;
+use crate::pools::weighted::WeightedPool;
+
+/// Pool instance built once for a `PoolState` and reused across many quotes
+///
+/// Values that only depend on the pool state are computed when the pool is prepared:
+/// the stable invariant, the Gyro ECLP invariant and error, the ReClamm current virtual
+/// balances and the interpolated LBP/QuantAMM weights. Quotes served from a prepared pool
+/// are identical to the ones computed from the raw pool state.
+pub struct PreparedPool {
+    pool_state: PoolState,
+    pool: Box<dyn PoolBase>,
+}
+
+impl PreparedPool {
+    /// Prepare a pool for the given state
+    pub fn new(pool_state: PoolState) -> Result<Self, PoolError> {
+        let balances_live_scaled_18 = &pool_state.base().balances_live_scaled_18;
+
+        let pool: Box<dyn PoolBase> = match &pool_state {
+            PoolState::Weighted(weighted_state) => {
+                Box::new(WeightedPool::from(weighted_state.clone()))
+            }
+            PoolState::Stable(stable_state) => {
+                let mut pool = StablePool::new(stable_state.mutable.clone());
+                pool.prepare(balances_live_scaled_18)?;
+                Box::new(pool)
+            }
+            PoolState::GyroECLP(gyro_eclp_state) => {
+                let mut pool = GyroECLPPool::new(gyro_eclp_state.immutable.clone());
+                pool.prepare(balances_live_scaled_18)?;
+                Box::new(pool)
+            }
+            PoolState::QuantAmm(quant_amm_state) => {
+                Box::new(QuantAmmPool::new(quant_amm_state.clone())?)
+            }
+            PoolState::LiquidityBootstrapping(liquidity_bootstrapping_state) => Box::new(
+                LiquidityBootstrappingPool::new(liquidity_bootstrapping_state.clone())?,
+            ),
+            PoolState::FixedPriceLBP(fixed_price_lbp_state) => {
+                Box::new(FixedPriceLBPPool::from(fixed_price_lbp_state.clone()))
+            }
+            PoolState::ReClamm(re_clamm_state) => {
+                let mut pool = ReClammPool::new(re_clamm_state.clone());
+                pool.prepare(balances_live_scaled_18);
+                Box::new(pool)
+            }
+            PoolState::ReClammV2(re_clamm_v2_state) => {
+                let mut pool = ReClammV2Pool::new(re_clamm_v2_state.clone());
+                pool.prepare(balances_live_scaled_18);
+                Box::new(pool)
+            }
+            PoolState::Base(base_state) => {
+                return Err(PoolError::UnsupportedPoolType(base_state.pool_type.clone()))
+            }
+        };
+
+        Ok(Self { pool_state, pool })
+    }
+
+    /// Get the pool state the pool was prepared for
+    pub fn pool_state(&self) -> &PoolState {
+        &self.pool_state
+    }
+
+    /// Get the prepared pool implementation
+    pub fn pool(&self) -> &dyn PoolBase {
+        self.pool.as_ref()
+    }
+}
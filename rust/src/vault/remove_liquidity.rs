@@ -4,14 +4,17 @@ use crate::common::errors::PoolError;
 use crate::common::pool_base::PoolBase;
 use crate::common::types::*;
 use crate::common::utils::{
-    compute_and_charge_aggregate_swap_fees, copy_to_scaled18_apply_rate_round_up_array,
-    get_single_input_index, require_unbalanced_liquidity_enabled, to_raw_undo_rate_round_down,
+    aggregate_swap_fee_percentage, compute_and_charge_aggregate_swap_fees,
+    copy_to_scaled18_apply_rate_round_up_array, get_single_input_index, require_recovery_mode,
+    require_unbalanced_liquidity_enabled, require_unpaused, to_raw_undo_rate_round_down,
 };
 use crate::hooks::types::HookState;
 use crate::hooks::HookBase;
+use crate::pools::reclamm::ReClammPool;
+use crate::pools::reclammv2::ReClammV2Pool;
 use crate::vault::base_pool_math::{
     compute_proportional_amounts_out, compute_remove_liquidity_single_token_exact_in,
-    compute_remove_liquidity_single_token_exact_out,
+    compute_remove_liquidity_single_token_exact_out, compute_remove_liquidity_unbalanced,
 };
 use alloy_primitives::U256;
 
@@ -23,6 +26,7 @@ pub fn remove_liquidity(
     hook_class: &dyn HookBase,
     hook_state: Option<&HookState>,
 ) -> Result<RemoveLiquidityResult, PoolError> {
+    require_unpaused(pool_state)?;
     let base_state = pool_state.base();
 
     // Round down when removing liquidity:
@@ -129,6 +133,23 @@ pub fn remove_liquidity(
                     computed.swap_fee_amounts,
                 )
             }
+            RemoveLiquidityKind::Custom => {
+                require_unbalanced_liquidity_enabled(pool_state)?;
+                let amounts_out_scaled18 = min_amounts_out_scaled18.clone();
+                let computed = compute_remove_liquidity_unbalanced(
+                    &updated_balances_live_scaled18,
+                    &amounts_out_scaled18,
+                    &base_state.total_supply,
+                    &base_state.swap_fee,
+                    &pool_class.get_minimum_invariant_ratio(),
+                    &|balances, rounding| pool_class.compute_invariant(balances, rounding),
+                )?;
+                (
+                    computed.bpt_amount_in,
+                    amounts_out_scaled18,
+                    computed.swap_fee_amounts,
+                )
+            }
         };
 
     let mut amounts_out_raw = vec![U256::ZERO; base_state.tokens.len()];
@@ -145,7 +166,7 @@ pub fn remove_liquidity(
         // Computes protocol and pool creator fee which is eventually taken from pool balance
         let aggregate_swap_fee_amount_scaled18 = compute_and_charge_aggregate_swap_fees(
             &swap_fee_amounts_scaled18[i],
-            &base_state.aggregate_swap_fee,
+            &aggregate_swap_fee_percentage(pool_state),
             &base_state.scaling_factors,
             &base_state.token_rates,
             i,
@@ -155,6 +176,21 @@ pub fn remove_liquidity(
             amounts_out_scaled18[i] + aggregate_swap_fee_amount_scaled18;
     }
 
+    // ReClamm pools scale their virtual balances with the BPT supply before removing liquidity
+    let virtual_balances_update = match pool_state {
+        PoolState::ReClamm(re_clamm_state) => Some(
+            ReClammPool::new(re_clamm_state.clone()).virtual_balances_after_remove_liquidity(
+                &remove_liquidity_input.max_bpt_amount_in_raw,
+            )?,
+        ),
+        PoolState::ReClammV2(re_clamm_v2_state) => Some(
+            ReClammV2Pool::new(re_clamm_v2_state.clone()).virtual_balances_after_remove_liquidity(
+                &remove_liquidity_input.max_bpt_amount_in_raw,
+            )?,
+        ),
+        _ => None,
+    };
+
     // Call after remove liquidity hook if needed
     if hook_class.config().should_call_after_remove_liquidity {
         let hook_return = hook_class.on_after_remove_liquidity(
@@ -184,5 +220,51 @@ pub fn remove_liquidity(
     Ok(RemoveLiquidityResult {
         bpt_amount_in_raw: bpt_amount_in,
         amounts_out_raw,
+        virtual_balances_update,
+    })
+}
+
+/// Remove liquidity proportionally from a pool in recovery mode
+///
+/// As in the Vault, recovery exits are allowed while the pool is paused, hooks are not called
+/// and no swap or aggregate fees are charged. Amounts out are proportional to the raw balances,
+/// which are derived from the live balances (scaled 18) rounding down.
+pub fn remove_liquidity_recovery(
+    exact_bpt_amount_in_raw: &U256,
+    pool_state: &PoolState,
+) -> Result<RemoveLiquidityResult, PoolError> {
+    require_recovery_mode(pool_state)?;
+    let base_state = pool_state.base();
+
+    if *exact_bpt_amount_in_raw > base_state.total_supply {
+        return Err(PoolError::InvalidInput(
+            "exact BPT amount in exceeds total supply".to_string(),
+        ));
+    }
+
+    let balances_raw = base_state
+        .balances_live_scaled_18
+        .iter()
+        .enumerate()
+        .map(|(i, balance)| {
+            to_raw_undo_rate_round_down(
+                balance,
+                &base_state.scaling_factors[i],
+                &base_state.token_rates[i],
+            )
+        })
+        .collect::<Result<Vec<U256>, PoolError>>()?;
+
+    let amounts_out_raw = compute_proportional_amounts_out(
+        &balances_raw,
+        &base_state.total_supply,
+        exact_bpt_amount_in_raw,
+    )?;
+
+    // Recovery exits bypass the pool's hooks, so ReClamm virtual balances are not updated
+    Ok(RemoveLiquidityResult {
+        bpt_amount_in_raw: *exact_bpt_amount_in_raw,
+        amounts_out_raw,
+        virtual_balances_update: None,
     })
 }
@@ -6,8 +6,9 @@ use crate::common::maths::{complement_fixed, mul_div_up_fixed, mul_up_fixed};
 use crate::common::pool_base::PoolBase;
 use crate::common::types::*;
 use crate::common::utils::{
-    compute_and_charge_aggregate_swap_fees, find_case_insensitive_index_in_list,
-    to_raw_undo_rate_round_down, to_raw_undo_rate_round_up, to_scaled_18_apply_rate_round_down,
+    aggregate_swap_fee_percentage, compute_and_charge_aggregate_swap_fees,
+    find_case_insensitive_index_in_list, require_unpaused, to_raw_undo_rate_round_down,
+    to_raw_undo_rate_round_up, to_scaled_18_apply_rate_round_down,
     to_scaled_18_apply_rate_round_up,
 };
 use crate::hooks::types::{AfterSwapParams, HookState};
@@ -17,6 +18,42 @@ use alloy_primitives::{uint, U256};
 /// Minimum trade amount (scaled 18)
 pub const MINIMUM_TRADE_AMOUNT: U256 = uint!(1000000_U256); // 1e6
 
+/// Token indices and rates resolved once per token pair and swap kind
+struct SwapContext<'a> {
+    swap_kind: SwapKind,
+    token_in: &'a str,
+    token_out: &'a str,
+    input_index: usize,
+    output_index: usize,
+    /// Output token rate rounded up, used for ExactOut amounts and ExactIn results
+    output_rate_rounded_up: U256,
+}
+
+impl<'a> SwapContext<'a> {
+    fn new(
+        token_in: &'a str,
+        token_out: &'a str,
+        swap_kind: SwapKind,
+        base_state: &BasePoolState,
+    ) -> Result<Self, PoolError> {
+        // Find token indices (case insensitive)
+        let input_index = find_case_insensitive_index_in_list(&base_state.tokens, token_in)
+            .ok_or(PoolError::InputTokenNotFound)?;
+
+        let output_index = find_case_insensitive_index_in_list(&base_state.tokens, token_out)
+            .ok_or(PoolError::OutputTokenNotFound)?;
+
+        Ok(Self {
+            swap_kind,
+            token_in,
+            token_out,
+            input_index,
+            output_index,
+            output_rate_rounded_up: compute_rate_round_up(&base_state.token_rates[output_index]),
+        })
+    }
+}
+
 /// Perform a swap operation
 pub fn swap(
     swap_input: &SwapInput,
@@ -25,28 +62,123 @@ pub fn swap(
     hook_class: &dyn HookBase,
     hook_state: Option<&HookState>,
 ) -> Result<U256, PoolError> {
+    require_unpaused(pool_state)?;
+
     if swap_input.amount_raw.is_zero() {
         return Ok(U256::ZERO);
     }
 
-    let base_state = pool_state.base();
+    let context = SwapContext::new(
+        &swap_input.token_in,
+        &swap_input.token_out,
+        swap_input.swap_kind.clone(),
+        pool_state.base(),
+    )?;
 
-    // Find token indices (case insensitive)
-    let input_index = find_case_insensitive_index_in_list(&base_state.tokens, &swap_input.token_in)
-        .ok_or(PoolError::InputTokenNotFound)?;
+    Ok(swap_with_context(
+        &swap_input.amount_raw,
+        &context,
+        pool_state,
+        pool_class,
+        hook_class,
+        hook_state,
+    )?
+    .0)
+}
 
-    let output_index =
-        find_case_insensitive_index_in_list(&base_state.tokens, &swap_input.token_out)
-            .ok_or(PoolError::OutputTokenNotFound)?;
+/// Perform a swap operation, also returning the live balances of the pool after the swap
+/// (scaled 18)
+///
+/// Aggregate swap fees are taken out of the pool, so they are excluded from the balances.
+pub fn swap_with_balances(
+    swap_input: &SwapInput,
+    pool_state: &PoolState,
+    pool_class: &dyn PoolBase,
+    hook_class: &dyn HookBase,
+    hook_state: Option<&HookState>,
+) -> Result<(U256, Vec<U256>), PoolError> {
+    require_unpaused(pool_state)?;
 
-    // Compute amount given scaled to 18 decimals
-    let amount_given_scaled_18 = compute_amount_given_scaled_18(
-        &swap_input.amount_raw,
+    if swap_input.amount_raw.is_zero() {
+        return Ok((
+            U256::ZERO,
+            pool_state.base().balances_live_scaled_18.clone(),
+        ));
+    }
+
+    let context = SwapContext::new(
+        &swap_input.token_in,
+        &swap_input.token_out,
         swap_input.swap_kind.clone(),
+        pool_state.base(),
+    )?;
+
+    swap_with_context(
+        &swap_input.amount_raw,
+        &context,
+        pool_state,
+        pool_class,
+        hook_class,
+        hook_state,
+    )
+}
+
+/// Perform a swap operation for each amount of a ladder
+///
+/// Token lookup and rate rounding are done once for the whole ladder, so errors resolving
+/// `token_in` or `token_out` are returned for the ladder as a whole. Every other error is
+/// reported per amount, and each result is identical to calling `swap` with that amount.
+#[allow(clippy::too_many_arguments)]
+pub fn swap_ladder(
+    token_in: &str,
+    token_out: &str,
+    swap_kind: SwapKind,
+    amounts_raw: &[U256],
+    pool_state: &PoolState,
+    pool_class: &dyn PoolBase,
+    hook_class: &dyn HookBase,
+    hook_state: Option<&HookState>,
+) -> Result<Vec<Result<U256, PoolError>>, PoolError> {
+    require_unpaused(pool_state)?;
+    let context = SwapContext::new(token_in, token_out, swap_kind, pool_state.base())?;
+
+    Ok(amounts_raw
+        .iter()
+        .map(|amount_raw| {
+            if amount_raw.is_zero() {
+                return Ok(U256::ZERO);
+            }
+            swap_with_context(
+                amount_raw, &context, pool_state, pool_class, hook_class, hook_state,
+            )
+            .map(|(amount_calculated_raw, _)| amount_calculated_raw)
+        })
+        .collect())
+}
+
+/// Perform a swap operation for a non-zero amount once tokens are resolved, returning the
+/// amount calculated (raw) and the updated live balances (scaled 18)
+fn swap_with_context(
+    amount_raw: &U256,
+    context: &SwapContext,
+    pool_state: &PoolState,
+    pool_class: &dyn PoolBase,
+    hook_class: &dyn HookBase,
+    hook_state: Option<&HookState>,
+) -> Result<(U256, Vec<U256>), PoolError> {
+    let base_state = pool_state.base();
+    let input_index = context.input_index;
+    let output_index = context.output_index;
+
+    // Compute amount given scaled to 18 decimals
+    let amount_given_scaled_18 = scale_amount_given(
+        amount_raw,
+        &context.swap_kind,
         input_index,
         output_index,
         &base_state.scaling_factors,
         &base_state.token_rates,
+        &context.output_rate_rounded_up,
     )?;
 
     // Create updated balances
@@ -54,7 +186,7 @@ pub fn swap(
 
     // Create swap parameters
     let mut swap_params = SwapParams {
-        swap_kind: swap_input.swap_kind.clone(),
+        swap_kind: context.swap_kind.clone(),
         token_in_index: input_index,
         token_out_index: output_index,
         amount_scaled_18: amount_given_scaled_18,
@@ -99,14 +231,13 @@ pub fn swap(
     ensure_valid_swap_amount(&amount_calculated_scaled_18)?;
 
     // Convert result back to raw amount
-    let amount_calculated_raw = match swap_input.swap_kind {
+    let amount_calculated_raw = match context.swap_kind {
         SwapKind::GivenIn => {
             // For ExactIn the amount calculated is leaving the Vault, so we round down
-            let rate_rounded_up = compute_rate_round_up(&base_state.token_rates[output_index]);
             to_raw_undo_rate_round_down(
                 &amount_calculated_scaled_18,
                 &base_state.scaling_factors[output_index],
-                &rate_rounded_up,
+                &context.output_rate_rounded_up,
             )?
         }
         SwapKind::GivenOut => {
@@ -130,20 +261,21 @@ pub fn swap(
     // Compute and charge aggregate swap fees
     let aggregate_swap_fee_amount_scaled_18 = compute_and_charge_aggregate_swap_fees(
         &total_swap_fee_amount_scaled_18,
-        &base_state.aggregate_swap_fee,
+        &aggregate_swap_fee_percentage(pool_state),
         &base_state.scaling_factors,
         &base_state.token_rates,
         input_index,
     )?;
 
     // Update balances
-    let (balance_in_increment, balance_out_decrement) = match swap_input.swap_kind {
+    let (balance_in_increment, balance_out_decrement) = match context.swap_kind {
         SwapKind::GivenIn => (
             amount_given_scaled_18 - aggregate_swap_fee_amount_scaled_18,
             amount_calculated_scaled_18,
         ),
         SwapKind::GivenOut => (
-            amount_calculated_scaled_18 - aggregate_swap_fee_amount_scaled_18,
+            amount_calculated_scaled_18 + total_swap_fee_amount_scaled_18
+                - aggregate_swap_fee_amount_scaled_18,
             amount_given_scaled_18,
         ),
     };
@@ -155,9 +287,9 @@ pub fn swap(
     let mut final_amount_calculated_raw = amount_calculated_raw;
     if hook_class.config().should_call_after_swap {
         let after_swap_params = AfterSwapParams {
-            kind: swap_input.swap_kind.clone(),
-            token_in: swap_input.token_in.clone(),
-            token_out: swap_input.token_out.clone(),
+            kind: context.swap_kind.clone(),
+            token_in: context.token_in.to_string(),
+            token_out: context.token_out.to_string(),
             amount_in_scaled_18: amount_given_scaled_18,
             amount_out_scaled_18: amount_calculated_scaled_18,
             token_in_balance_scaled_18: updated_balances[input_index],
@@ -177,7 +309,7 @@ pub fn swap(
         }
     }
 
-    Ok(final_amount_calculated_raw)
+    Ok((final_amount_calculated_raw, updated_balances))
 }
 
 /// Compute amount given scaled to 18 decimals
@@ -188,6 +320,27 @@ pub fn compute_amount_given_scaled_18(
     index_out: usize,
     scaling_factors: &[U256],
     token_rates: &[U256],
+) -> Result<U256, PoolError> {
+    scale_amount_given(
+        amount_given_raw,
+        &swap_kind,
+        index_in,
+        index_out,
+        scaling_factors,
+        token_rates,
+        &compute_rate_round_up(&token_rates[index_out]),
+    )
+}
+
+/// Compute amount given scaled to 18 decimals with the output token rate already rounded up
+fn scale_amount_given(
+    amount_given_raw: &U256,
+    swap_kind: &SwapKind,
+    index_in: usize,
+    index_out: usize,
+    scaling_factors: &[U256],
+    token_rates: &[U256],
+    output_rate_rounded_up: &U256,
 ) -> Result<U256, PoolError> {
     match swap_kind {
         SwapKind::GivenIn => {
@@ -202,11 +355,10 @@ pub fn compute_amount_given_scaled_18(
         SwapKind::GivenOut => {
             // For ExactOut, round up to favor the pool
             // Round up the rate to ensure consistency with TypeScript implementation
-            let rate_rounded_up = compute_rate_round_up(&token_rates[index_out]);
             Ok(to_scaled_18_apply_rate_round_up(
                 amount_given_raw,
                 &scaling_factors[index_out],
-                &rate_rounded_up,
+                output_rate_rounded_up,
             )?)
         }
     }
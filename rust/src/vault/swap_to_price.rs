@@ -0,0 +1,324 @@
+//! Exact swaps that move a pool's marginal price to a target price
+//!
+//! Prices are marginal (spot) prices of a base token quoted in a quote token, excluding fees,
+//! computed from the live balances (scaled 18, rates applied) and returned in 18 decimals. This
+//! matches the convention of `calculate_reclamm_price`, where the base token is token 0.
+//!
+//! The trade is found by searching over exact `GivenIn` swaps, so the returned amounts are the
+//! ones the vault would quote (including swap fees and hooks) and are never truncated.
+
+use crate::common::constants::WAD;
+use crate::common::errors::PoolError;
+use crate::common::maths::div_down_fixed;
+use crate::common::pool_base::PoolBase;
+use crate::common::types::*;
+use crate::common::utils::{
+    find_case_insensitive_index_in_list, require_unpaused, to_scaled_18_apply_rate_round_down,
+    to_scaled_18_apply_rate_round_up,
+};
+use crate::hooks::types::HookState;
+use crate::hooks::HookBase;
+use crate::pools::gyro::{
+    calc_spot_price0in1, calculate_invariant_with_error, DerivedEclpParams, EclpParams,
+    GyroECLPPool,
+};
+use crate::pools::liquidity_bootstrapping::LiquidityBootstrappingPool;
+use crate::pools::quantamm::QuantAmmPool;
+use crate::pools::reclamm::ReClammPool;
+use crate::pools::reclammv2::ReClammV2Pool;
+use crate::pools::stable::{compute_invariant, AMP_PRECISION};
+use crate::vault::swap::swap;
+use alloy_primitives::U256;
+
+/// Largest raw amount in tried before giving up on reaching the target price
+const MAX_SEARCH_AMOUNT_RAW: U256 = U256::from_limbs([u64::MAX, u64::MAX, 0, 0]);
+
+/// Marginal price model of a pool, built once per pool state
+enum SpotPriceModel {
+    /// Weighted, LBP and QuantAMM pools, with the current normalized weights
+    Weighted(Vec<U256>),
+    /// Stable pools, with the amplification parameter
+    Stable(U256),
+    /// Gyro ECLP pools
+    GyroECLP(Box<(EclpParams, DerivedEclpParams)>),
+    /// ReClamm pools, with the current virtual balances. Virtual balances are only updated
+    /// once per block, so they stay fixed for swaps against the same state.
+    ReClamm(U256, U256),
+}
+
+impl SpotPriceModel {
+    fn new(pool_state: &PoolState) -> Result<Self, PoolError> {
+        let balances_live_scaled_18 = &pool_state.base().balances_live_scaled_18;
+
+        Ok(match pool_state {
+            PoolState::Weighted(weighted_state) => {
+                SpotPriceModel::Weighted(weighted_state.weights.clone())
+            }
+            PoolState::LiquidityBootstrapping(liquidity_bootstrapping_state) => {
+                SpotPriceModel::Weighted(
+                    LiquidityBootstrappingPool::new(liquidity_bootstrapping_state.clone())?
+                        .normalized_weights()
+                        .to_vec(),
+                )
+            }
+            PoolState::QuantAmm(quant_amm_state) => SpotPriceModel::Weighted(
+                QuantAmmPool::new(quant_amm_state.clone())?
+                    .normalized_weights()
+                    .to_vec(),
+            ),
+            PoolState::Stable(stable_state) => {
+                SpotPriceModel::Stable(stable_state.mutable.amplification_parameter())
+            }
+            PoolState::GyroECLP(gyro_eclp_state) => {
+                let pool = GyroECLPPool::new(gyro_eclp_state.immutable.clone());
+                SpotPriceModel::GyroECLP(Box::new((pool.params, pool.derived)))
+            }
+            PoolState::ReClamm(re_clamm_state) => {
+                let (virtual_balance_a, virtual_balance_b, _) =
+                    ReClammPool::new(re_clamm_state.clone())
+                        .current_virtual_balances(balances_live_scaled_18);
+                SpotPriceModel::ReClamm(virtual_balance_a, virtual_balance_b)
+            }
+            PoolState::ReClammV2(re_clamm_v2_state) => {
+                let (virtual_balance_a, virtual_balance_b, _) =
+                    ReClammV2Pool::new(re_clamm_v2_state.clone())
+                        .current_virtual_balances(balances_live_scaled_18);
+                SpotPriceModel::ReClamm(virtual_balance_a, virtual_balance_b)
+            }
+            _ => {
+                return Err(PoolError::UnsupportedPoolType(
+                    pool_state.base().pool_type.clone(),
+                ))
+            }
+        })
+    }
+
+    /// Price of `balances[base_index]` quoted in `balances[quote_index]` (scaled 18)
+    fn spot_price(
+        &self,
+        balances_live_scaled_18: &[U256],
+        base_index: usize,
+        quote_index: usize,
+    ) -> Result<U256, PoolError> {
+        let balance_base = balances_live_scaled_18[base_index];
+        let balance_quote = balances_live_scaled_18[quote_index];
+        if balance_base.is_zero() || balance_quote.is_zero() {
+            return Err(PoolError::ZeroInvariant);
+        }
+
+        match self {
+            SpotPriceModel::Weighted(weights) => {
+                // (balanceQuote / weightQuote) / (balanceBase / weightBase)
+                let numerator = balance_quote * weights[base_index] * WAD;
+                let denominator = balance_base * weights[quote_index];
+                Ok(numerator / denominator)
+            }
+            SpotPriceModel::Stable(amp) => {
+                // Ratio of the partial derivatives of the invariant equation:
+                // (A·n·x_b + D^(n+1) / (n^n·P)) · x_q / ((A·n·x_q + D^(n+1) / (n^n·P)) · x_b)
+                let invariant = compute_invariant(amp, balances_live_scaled_18)?;
+                let num_tokens = U256::from(balances_live_scaled_18.len());
+
+                let mut d_p = invariant;
+                for balance in balances_live_scaled_18 {
+                    d_p = (d_p * invariant) / (balance * num_tokens);
+                }
+
+                let amp_times_n = amp * num_tokens;
+                let numerator = amp_times_n * balance_base / U256::from(AMP_PRECISION) + d_p;
+                let denominator = amp_times_n * balance_quote / U256::from(AMP_PRECISION) + d_p;
+
+                Ok(numerator * WAD / denominator * balance_quote / balance_base)
+            }
+            SpotPriceModel::GyroECLP(params) => {
+                let (eclp_params, derived) = params.as_ref();
+                let (invariant, _) =
+                    calculate_invariant_with_error(balances_live_scaled_18, eclp_params, derived)?;
+                let price0in1 = calc_spot_price0in1(
+                    balances_live_scaled_18,
+                    eclp_params,
+                    derived,
+                    &invariant.into_raw(),
+                );
+
+                if base_index == 0 {
+                    Ok(price0in1)
+                } else {
+                    div_down_fixed(&WAD, &price0in1)
+                }
+            }
+            SpotPriceModel::ReClamm(virtual_balance_a, virtual_balance_b) => {
+                let virtual_balances = [*virtual_balance_a, *virtual_balance_b];
+                Ok((balance_quote + virtual_balances[quote_index]) * WAD
+                    / (balance_base + virtual_balances[base_index]))
+            }
+        }
+    }
+}
+
+/// Resolve the base and quote token indices of a price
+fn price_token_indices(
+    pool_state: &PoolState,
+    base_token: &str,
+    quote_token: &str,
+) -> Result<(usize, usize), PoolError> {
+    let tokens = &pool_state.base().tokens;
+    let base_index = find_case_insensitive_index_in_list(tokens, base_token)
+        .ok_or_else(|| PoolError::InvalidInput(format!("token {} not in pool", base_token)))?;
+    let quote_index = find_case_insensitive_index_in_list(tokens, quote_token)
+        .ok_or_else(|| PoolError::InvalidInput(format!("token {} not in pool", quote_token)))?;
+
+    if base_index == quote_index {
+        return Err(PoolError::InvalidInput(
+            "base and quote tokens must differ".to_string(),
+        ));
+    }
+
+    Ok((base_index, quote_index))
+}
+
+/// Compute the marginal price of `base_token` quoted in `quote_token`, excluding fees
+///
+/// Supported for weighted, stable, Gyro ECLP, LBP, QuantAMM, ReClamm and ReClammV2 pools.
+pub fn spot_price(
+    pool_state: &PoolState,
+    base_token: &str,
+    quote_token: &str,
+) -> Result<U256, PoolError> {
+    let (base_index, quote_index) = price_token_indices(pool_state, base_token, quote_token)?;
+    SpotPriceModel::new(pool_state)?.spot_price(
+        &pool_state.base().balances_live_scaled_18,
+        base_index,
+        quote_index,
+    )
+}
+
+/// Find the swap that moves the marginal price of `base_token` (quoted in `quote_token`) to
+/// `target_price_scaled_18`
+///
+/// When the price is below the target the quote token is swapped in for the base token, and the
+/// other way around when it is above. The result is the smallest `GivenIn` amount whose
+/// post-swap marginal price reaches the target, together with the exact amount out, both raw
+/// and after fees. Post-swap prices are computed from the balances credited with the amount in
+/// and debited with the amount out; protocol and creator fee splits are not deducted.
+///
+/// Returns zero amounts if the price is already at the target. If the pool cannot be moved to
+/// the target (e.g. a max in ratio or a balance limit is hit first), the error of the swap that
+/// went past the limit is returned.
+pub fn swap_to_price(
+    base_token: &str,
+    quote_token: &str,
+    target_price_scaled_18: &U256,
+    pool_state: &PoolState,
+    pool_class: &dyn PoolBase,
+    hook_class: &dyn HookBase,
+    hook_state: Option<&HookState>,
+) -> Result<SwapToTargetPriceResult, PoolError> {
+    let (base_index, quote_index) = price_token_indices(pool_state, base_token, quote_token)?;
+    require_unpaused(pool_state)?;
+    let base_state = pool_state.base();
+    let model = SpotPriceModel::new(pool_state)?;
+
+    let current_price =
+        model.spot_price(&base_state.balances_live_scaled_18, base_index, quote_index)?;
+
+    // Buying the base token raises its price, selling it lowers it
+    let price_increases = current_price < *target_price_scaled_18;
+    let (token_in_index, token_out_index) = if price_increases {
+        (quote_index, base_index)
+    } else {
+        (base_index, quote_index)
+    };
+
+    if current_price == *target_price_scaled_18 {
+        return Ok(SwapToTargetPriceResult {
+            token_in_index,
+            token_out_index,
+            amount_in_raw: U256::ZERO,
+            amount_out_raw: U256::ZERO,
+        });
+    }
+
+    let mut swap_input = SwapInput {
+        amount_raw: U256::ZERO,
+        swap_kind: SwapKind::GivenIn,
+        token_in: base_state.tokens[token_in_index].clone(),
+        token_out: base_state.tokens[token_out_index].clone(),
+    };
+
+    // Returns the amount out if swapping `amount_in_raw` reaches the target price
+    let mut reaches_target = |amount_in_raw: U256| -> Result<Option<U256>, PoolError> {
+        swap_input.amount_raw = amount_in_raw;
+        let amount_out_raw = match swap(&swap_input, pool_state, pool_class, hook_class, hook_state)
+        {
+            Ok(amount_out_raw) => amount_out_raw,
+            // Too small to trade, so too small to reach the target
+            Err(PoolError::TradeAmountTooSmall) => return Ok(None),
+            Err(error) => return Err(error),
+        };
+
+        let mut balances = base_state.balances_live_scaled_18.clone();
+        balances[token_in_index] += to_scaled_18_apply_rate_round_down(
+            &amount_in_raw,
+            &base_state.scaling_factors[token_in_index],
+            &base_state.token_rates[token_in_index],
+        )?;
+        balances[token_out_index] = balances[token_out_index]
+            .checked_sub(to_scaled_18_apply_rate_round_up(
+                &amount_out_raw,
+                &base_state.scaling_factors[token_out_index],
+                &base_state.token_rates[token_out_index],
+            )?)
+            .ok_or(PoolError::TokenAmountOutIsGreaterThanBalance)?;
+
+        let price = model.spot_price(&balances, base_index, quote_index)?;
+        let reached = if price_increases {
+            price >= *target_price_scaled_18
+        } else {
+            price <= *target_price_scaled_18
+        };
+
+        Ok(reached.then_some(amount_out_raw))
+    };
+
+    // Double the amount until the target is reached or a limit is hit. Errors only occur past
+    // pool limits, so they bound the search from above like an amount that reaches the target.
+    let mut lower = U256::ZERO;
+    let mut upper = U256::ONE;
+    let mut upper_result = loop {
+        match reaches_target(upper) {
+            Ok(Some(amount_out_raw)) => break Ok(amount_out_raw),
+            Ok(None) => {
+                if upper > MAX_SEARCH_AMOUNT_RAW {
+                    return Err(PoolError::InvalidSwapParameters);
+                }
+                lower = upper;
+                upper <<= 1;
+            }
+            Err(error) => break Err(error),
+        }
+    };
+
+    // Bisect down to the smallest amount that reaches the target
+    while upper - lower > U256::ONE {
+        let middle = lower + ((upper - lower) >> 1);
+        match reaches_target(middle) {
+            Ok(Some(amount_out_raw)) => {
+                upper = middle;
+                upper_result = Ok(amount_out_raw);
+            }
+            Ok(None) => lower = middle,
+            Err(error) => {
+                upper = middle;
+                upper_result = Err(error);
+            }
+        }
+    }
+
+    Ok(SwapToTargetPriceResult {
+        token_in_index,
+        token_out_index,
+        amount_in_raw: upper,
+        amount_out_raw: upper_result?,
+    })
+}
@@ -1,10 +1,12 @@
 use alloy_primitives::U256;
+use balancer_maths_rust::common::errors::PoolError;
 use balancer_maths_rust::common::types::PoolStateOrBuffer;
 use balancer_maths_rust::common::types::*;
 use balancer_maths_rust::vault::Vault;
 mod utils;
+use std::str::FromStr;
 use utils::read_test_data;
-use utils::{convert_to_pool_state, get_pool_address};
+use utils::{convert_to_pool_state, find_pool, get_pool_address};
 
 #[test]
 fn test_add_liquidity() {
@@ -90,3 +92,254 @@ fn test_add_liquidity() {
         }
     }
 }
+
+#[test]
+fn test_add_liquidity_proportional() {
+    let test_data = read_test_data().expect("Failed to read test data");
+    let vault = Vault::new();
+
+    for (name, pool) in &test_data.pools {
+        let pool_state = match convert_to_pool_state(pool) {
+            PoolStateOrBuffer::Pool(pool_state) => *pool_state,
+            PoolStateOrBuffer::Buffer(_) => continue,
+        };
+        let base = pool_state.base();
+        let bpt_amount_out = base.total_supply / U256::from(100);
+
+        let add_input = AddLiquidityInput {
+            pool: get_pool_address(pool),
+            max_amounts_in_raw: vec![U256::ZERO; base.tokens.len()],
+            min_bpt_amount_out_raw: bpt_amount_out,
+            kind: AddLiquidityKind::Proportional,
+        };
+        // Proportional joins are allowed without unbalanced liquidity support (e.g. ReClamm)
+        let result = vault
+            .add_liquidity(&add_input, &pool_state, test_data.hook_state.as_ref())
+            .unwrap_or_else(|e| panic!("Proportional add failed for {}: {:?}", name, e));
+
+        assert_eq!(result.bpt_amount_out_raw, bpt_amount_out);
+        for i in 0..base.tokens.len() {
+            // balance * bptOut / totalSupply, rounded up in scaled 18 and again in raw
+            let amount_in_scaled18 =
+                (base.balances_live_scaled_18[i] * bpt_amount_out).div_ceil(base.total_supply);
+            let amount_in_raw = (amount_in_scaled18 * U256::from(10).pow(U256::from(18)))
+                .div_ceil(base.scaling_factors[i] * base.token_rates[i]);
+            assert_eq!(
+                result.amounts_in_raw[i], amount_in_raw,
+                "Amount in mismatch for {} token {}",
+                name, i
+            );
+        }
+
+        // Exiting proportionally with the same BPT never returns more than was added
+        let remove_input = RemoveLiquidityInput {
+            pool: get_pool_address(pool),
+            min_amounts_out_raw: vec![U256::ZERO; base.tokens.len()],
+            max_bpt_amount_in_raw: bpt_amount_out,
+            kind: RemoveLiquidityKind::Proportional,
+        };
+        if let Ok(removed) =
+            vault.remove_liquidity(&remove_input, &pool_state, test_data.hook_state.as_ref())
+        {
+            for i in 0..base.tokens.len() {
+                assert!(removed.amounts_out_raw[i] <= result.amounts_in_raw[i]);
+            }
+        }
+    }
+}
+
+fn donate(pool_state: &PoolState) -> Result<AddLiquidityResult, PoolError> {
+    let base = pool_state.base();
+    Vault::new().add_liquidity(
+        &AddLiquidityInput {
+            pool: base.pool_address.clone(),
+            max_amounts_in_raw: vec![U256::from(1_000_000u64), U256::from(2_000_000u64)],
+            min_bpt_amount_out_raw: U256::ZERO,
+            kind: AddLiquidityKind::Donation,
+        },
+        pool_state,
+        None,
+    )
+}
+
+#[test]
+fn test_add_liquidity_donation() {
+    let mut pool_state = find_pool("11155111-7439300-Weighted-USDC-DAI.json");
+    pool_state.base_mut().supports_donation = true;
+
+    // Donations add the amounts as given without minting BPT
+    let result = donate(&pool_state).unwrap();
+    assert_eq!(result.bpt_amount_out_raw, U256::ZERO);
+    assert_eq!(
+        result.amounts_in_raw,
+        vec![U256::from(1_000_000u64), U256::from(2_000_000u64)]
+    );
+    assert_eq!(result.virtual_balances_update, None);
+}
+
+#[test]
+fn test_add_liquidity_donation_disabled() {
+    let donation_disabled = PoolError::Custom("DoesNotSupportDonation".to_string());
+
+    let mut pool_state = find_pool("11155111-7439300-Weighted-USDC-DAI.json");
+    pool_state.base_mut().supports_donation = false;
+    assert_eq!(donate(&pool_state).unwrap_err(), donation_disabled);
+
+    // ReClamm pools disable donations
+    for name in [
+        "8453-31094200-ReClamm-WETH-USDC-In-Range.json",
+        "8453-32632378-ReClamm-V2.json",
+    ] {
+        assert_eq!(donate(&find_pool(name)).unwrap_err(), donation_disabled);
+    }
+}
+
+/// Add 1% of the token 0 balance of a test pool with both single token exact in methods,
+/// checking them against the unbalanced and exact out adds they solve
+fn add_single_token_exact_in(
+    name: &str,
+) -> (
+    AddLiquiditySingleTokenExactInResult,
+    AddLiquiditySingleTokenExactInResult,
+) {
+    let vault = Vault::new();
+    let pool_state = find_pool(name);
+    let base = pool_state.base();
+    let add = |max_amounts_in_raw: Vec<U256>, min_bpt_amount_out_raw, kind| {
+        vault.add_liquidity(
+            &AddLiquidityInput {
+                pool: base.pool_address.clone(),
+                max_amounts_in_raw,
+                min_bpt_amount_out_raw,
+                kind,
+            },
+            &pool_state,
+            None,
+        )
+    };
+
+    let exact_amount_in_raw = base.balances_live_scaled_18[0] * U256::from(10).pow(U256::from(16))
+        / (base.scaling_factors[0] * base.token_rates[0]);
+    let mut max_amounts_in_raw = vec![U256::ZERO; base.tokens.len()];
+    max_amounts_in_raw[0] = exact_amount_in_raw;
+
+    let unbalanced = vault
+        .add_liquidity_single_token_exact_in(
+            &pool_state,
+            &base.tokens[0],
+            &exact_amount_in_raw,
+            SingleTokenExactInMethod::Unbalanced,
+            None,
+        )
+        .unwrap();
+    assert_eq!(unbalanced.amount_in_raw, exact_amount_in_raw);
+    assert_eq!(
+        unbalanced.bpt_amount_out_raw,
+        add(
+            max_amounts_in_raw.clone(),
+            U256::ZERO,
+            AddLiquidityKind::Unbalanced
+        )
+        .unwrap()
+        .bpt_amount_out_raw
+    );
+
+    // The inverse solve is what an exact out add for its BPT takes, and one more BPT does not fit
+    let inverse = vault
+        .add_liquidity_single_token_exact_in(
+            &pool_state,
+            &base.tokens[0],
+            &exact_amount_in_raw,
+            SingleTokenExactInMethod::InverseExactOut,
+            None,
+        )
+        .unwrap();
+    assert!(inverse.amount_in_raw <= exact_amount_in_raw);
+    assert_eq!(
+        add(
+            max_amounts_in_raw.clone(),
+            inverse.bpt_amount_out_raw,
+            AddLiquidityKind::SingleTokenExactOut
+        )
+        .unwrap()
+        .amounts_in_raw[0],
+        inverse.amount_in_raw
+    );
+    assert!(
+        add(
+            max_amounts_in_raw,
+            inverse.bpt_amount_out_raw + U256::ONE,
+            AddLiquidityKind::SingleTokenExactOut
+        )
+        .unwrap()
+        .amounts_in_raw[0]
+            > exact_amount_in_raw
+    );
+
+    // Grossing up the fee on the taxable amount charges slightly more than the unbalanced add
+    // does, so the inverse solve mints at most as much BPT
+    assert!(inverse.bpt_amount_out_raw <= unbalanced.bpt_amount_out_raw);
+
+    (unbalanced, inverse)
+}
+
+fn assert_exact_in(result: &AddLiquiditySingleTokenExactInResult, expected: [&str; 4]) {
+    let expected = expected.map(|value| U256::from_str(value).unwrap());
+    assert_eq!(
+        [
+            result.bpt_amount_out_raw,
+            result.amount_in_raw,
+            result.swap_fee_amount_raw,
+            result.aggregate_swap_fee_amount_raw,
+        ],
+        expected
+    );
+}
+
+#[test]
+fn test_add_liquidity_single_token_exact_in_weighted() {
+    let (unbalanced, inverse) =
+        add_single_token_exact_in("11155111-7439300-Weighted-USDC-DAI.json");
+    assert_exact_in(
+        &unbalanced,
+        ["32580358271956041978", "69163843", "346679", "0"],
+    );
+    assert_exact_in(
+        &inverse,
+        ["32579539629398236152", "69163843", "348412", "0"],
+    );
+}
+
+#[test]
+fn test_add_liquidity_single_token_exact_in_stable() {
+    let (unbalanced, inverse) =
+        add_single_token_exact_in("11155111-7439300-Stable-stataUSDC-stataUSDT.json");
+    assert_exact_in(
+        &unbalanced,
+        ["201771359465388369854", "170465943", "135597", "0"],
+    );
+    assert_exact_in(
+        &inverse,
+        ["201771326604249247626", "170465943", "135625", "0"],
+    );
+}
+
+#[test]
+fn test_add_liquidity_single_token_exact_in_gyro_eclp() {
+    let (unbalanced, inverse) = add_single_token_exact_in("11155111-7748718-GyroECLP.json");
+    assert_exact_in(
+        &unbalanced,
+        ["2665107479872", "10000000000000000", "50003790059077", "0"],
+    );
+    assert_exact_in(
+        &inverse,
+        ["2665040180940", "9999999999996789", "50255046126181", "0"],
+    );
+}
+
+#[test]
+fn test_add_liquidity_single_token_exact_in_quantamm() {
+    let (unbalanced, inverse) = add_single_token_exact_in("1-22524240-QuantAMM.json");
+    assert_exact_in(&unbalanced, ["59966031249527931", "900794", "5846", "0"]);
+    assert_exact_in(&inverse, ["59960683596398278", "900794", "5926", "0"]);
+}
@@ -0,0 +1,152 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::errors::PoolError;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::common::utils::u256_to_f64;
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::convert_to_pool_state;
+use utils::read_test_data;
+
+/// Tolerance from the documented bounds: integer rounding of the exact result, a relative
+/// term and an absolute term proportional to the pool balances
+fn tolerance(pool_state_or_buffer: &PoolStateOrBuffer, swap_input: &SwapInput, exact: f64) -> f64 {
+    let balance_term = match pool_state_or_buffer {
+        PoolStateOrBuffer::Pool(pool_state) => {
+            let base = pool_state.base();
+            let calculated_token = match swap_input.swap_kind {
+                SwapKind::GivenIn => &swap_input.token_out,
+                SwapKind::GivenOut => &swap_input.token_in,
+            };
+            let index = base
+                .tokens
+                .iter()
+                .position(|token| token.eq_ignore_ascii_case(calculated_token))
+                .unwrap();
+            let scale = u256_to_f64(&base.scaling_factors[index])
+                * u256_to_f64(&base.token_rates[index])
+                / 1e18;
+            let max_balance = base
+                .balances_live_scaled_18
+                .iter()
+                .map(u256_to_f64)
+                .fold(0.0, f64::max);
+            let balance_factor = match pool_state.as_ref() {
+                PoolState::Weighted(_)
+                | PoolState::LiquidityBootstrapping(_)
+                | PoolState::QuantAmm(_) => 1e-13,
+                PoolState::Stable(_) => 1e-12,
+                PoolState::GyroECLP(_) => 1e-10,
+                _ => 0.0,
+            };
+            balance_factor * max_balance / scale
+        }
+        PoolStateOrBuffer::Buffer(_) => 0.0,
+    };
+
+    1.0 + 1e-9 * exact + balance_term
+}
+
+#[test]
+fn test_approx_swaps_match_vault_swaps() {
+    let test_data = read_test_data().expect("Failed to read test data");
+    let vault = Vault::new();
+
+    for swap_test in test_data.swaps {
+        let pool_data = test_data
+            .pools
+            .get(&swap_test.test)
+            .unwrap_or_else(|| panic!("Pool not found for test: {}", swap_test.test));
+        let pool_state_or_buffer = convert_to_pool_state(pool_data);
+
+        // Hooks are not applied by the approximation
+        if let PoolStateOrBuffer::Pool(pool_state) = &pool_state_or_buffer {
+            if pool_state.base().hook_type.is_some() {
+                continue;
+            }
+        }
+
+        for amount_raw in [
+            swap_test.amount_raw,
+            swap_test.amount_raw / U256::from(7),
+            swap_test.amount_raw * U256::from(3),
+        ] {
+            let swap_input = SwapInput {
+                amount_raw,
+                token_in: swap_test.token_in.clone(),
+                token_out: swap_test.token_out.clone(),
+                swap_kind: match swap_test.swap_kind {
+                    0 => SwapKind::GivenIn,
+                    _ => SwapKind::GivenOut,
+                },
+            };
+
+            let exact = vault.swap(
+                &swap_input,
+                &pool_state_or_buffer,
+                test_data.hook_state.as_ref(),
+            );
+            let approx = vault.swap_approx(&swap_input, &pool_state_or_buffer);
+
+            match (exact, approx) {
+                (Ok(exact), Ok(approx)) => {
+                    let exact = u256_to_f64(&exact);
+                    let tolerance = tolerance(&pool_state_or_buffer, &swap_input, exact);
+                    assert!(
+                        (approx - exact).abs() <= tolerance,
+                        "Approximation out of bounds for test: {} (amount: {}, exact: {}, approx: {})",
+                        swap_test.test,
+                        amount_raw,
+                        exact,
+                        approx
+                    );
+                }
+                // The approximation reports the amounts of a max trade size violation in f64
+                (
+                    Err(PoolError::MaxTradeSizeRatioExceeded { .. }),
+                    Err(PoolError::MaxTradeSizeRatioExceeded { .. }),
+                ) => {}
+                (Err(exact), Err(approx)) => assert_eq!(
+                    approx, exact,
+                    "Error mismatch for test: {} (amount: {})",
+                    swap_test.test, amount_raw
+                ),
+                (exact, approx) => panic!(
+                    "Result mismatch for test: {} (amount: {}, exact: {:?}, approx: {:?})",
+                    swap_test.test, amount_raw, exact, approx
+                ),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_approx_swap_errors_match_vault_swaps_on_reclamm() {
+    let test_data = read_test_data().expect("Failed to read test data");
+    let vault = Vault::new();
+
+    for pool_file in [
+        "8453-31094200-ReClamm-WETH-USDC-In-Range.json",
+        "8453-32632378-ReClamm-V2.json",
+    ] {
+        let pool_state_or_buffer = convert_to_pool_state(&test_data.pools[pool_file]);
+        let PoolStateOrBuffer::Pool(pool_state) = &pool_state_or_buffer else {
+            panic!("Not a pool");
+        };
+        let base = pool_state.base();
+
+        // More than the whole balance out fails in the ReClamm math, in both versions
+        let swap_input = SwapInput {
+            swap_kind: SwapKind::GivenOut,
+            amount_raw: base.balances_live_scaled_18[1] / base.scaling_factors[1] * U256::from(2),
+            token_in: base.tokens[0].clone(),
+            token_out: base.tokens[1].clone(),
+        };
+        let exact = vault
+            .swap(&swap_input, &pool_state_or_buffer, None)
+            .unwrap_err();
+        let approx = vault
+            .swap_approx(&swap_input, &pool_state_or_buffer)
+            .unwrap_err();
+        assert_eq!(approx, exact, "{}", pool_file);
+    }
+}
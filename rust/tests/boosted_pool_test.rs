@@ -0,0 +1,409 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::errors::PoolError;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::pools::buffer::{BufferState, BufferWrapOrUnwrapPath};
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::{find_buffer, find_pool};
+
+const UNDERLYING_TOKENS: [&str; 2] = [
+    "0x94a9D9AC8a22534E3FaCa9F4e7F2E2cf85d5E4C8",
+    "0xaA8E23Fb1079EA71e0a56F48a2aA51851D8433D0",
+];
+
+/// A stable pool of two ERC4626 tokens, with a buffer for each at the pool's token rates
+fn find_boosted_pool() -> (PoolState, Vec<BufferState>) {
+    let pool_state = find_pool("11155111-7439300-Stable-stataUSDC-stataUSDT.json");
+    let template = find_buffer();
+
+    let base = pool_state.base();
+    let buffers = (0..2)
+        .map(|i| {
+            let mut buffer_state = template.clone();
+            buffer_state.mutable.rate = base.token_rates[i];
+            buffer_state.immutable.pool_address = base.tokens[i].clone();
+            buffer_state.immutable.tokens =
+                vec![base.tokens[i].clone(), UNDERLYING_TOKENS[i].to_string()];
+            buffer_state
+        })
+        .collect();
+    (pool_state, buffers)
+}
+
+fn buffer_swap(
+    buffer_state: &BufferState,
+    is_wrap: bool,
+    swap_kind: SwapKind,
+    amount_raw: U256,
+) -> U256 {
+    let wrapped = buffer_state.immutable.pool_address.clone();
+    let underlying = buffer_state.immutable.tokens[1].clone();
+    let (token_in, token_out) = if is_wrap {
+        (underlying, wrapped)
+    } else {
+        (wrapped, underlying)
+    };
+    Vault::new()
+        .swap(
+            &SwapInput {
+                swap_kind,
+                amount_raw,
+                token_in,
+                token_out,
+            },
+            &PoolStateOrBuffer::Buffer(Box::new(buffer_state.clone())),
+            None,
+        )
+        .unwrap()
+}
+
+fn pool_swap(pool_state: &PoolState, swap_kind: SwapKind, amount_raw: U256) -> U256 {
+    let base = pool_state.base();
+    Vault::new()
+        .swap(
+            &SwapInput {
+                swap_kind,
+                amount_raw,
+                token_in: base.tokens[0].clone(),
+                token_out: base.tokens[1].clone(),
+            },
+            &PoolStateOrBuffer::Pool(Box::new(pool_state.clone())),
+            None,
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_boosted_swap() {
+    let vault = Vault::new();
+    let (pool_state, buffers) = find_boosted_pool();
+    let amount_raw = U256::from(1_000_000u64);
+
+    // Exact in: wrap, swap, then unwrap
+    let result = vault
+        .swap_boosted(
+            &SwapInput {
+                swap_kind: SwapKind::GivenIn,
+                amount_raw,
+                token_in: UNDERLYING_TOKENS[0].to_string(),
+                token_out: UNDERLYING_TOKENS[1].to_string(),
+            },
+            &pool_state,
+            &buffers,
+            None,
+        )
+        .unwrap();
+    let wrapped_in = buffer_swap(&buffers[0], true, SwapKind::GivenIn, amount_raw);
+    let wrapped_out = pool_swap(&pool_state, SwapKind::GivenIn, wrapped_in);
+    assert_eq!(result.wrapped_amount_in_raw, wrapped_in);
+    assert_eq!(result.wrapped_amount_out_raw, wrapped_out);
+    assert_eq!(
+        result.amount_calculated_raw,
+        buffer_swap(&buffers[1], false, SwapKind::GivenIn, wrapped_out)
+    );
+
+    // Exact out: solved backwards from the underlying amount out
+    let result = vault
+        .swap_boosted(
+            &SwapInput {
+                swap_kind: SwapKind::GivenOut,
+                amount_raw,
+                token_in: UNDERLYING_TOKENS[0].to_string(),
+                token_out: UNDERLYING_TOKENS[1].to_string(),
+            },
+            &pool_state,
+            &buffers,
+            None,
+        )
+        .unwrap();
+    let wrapped_out = buffer_swap(&buffers[1], false, SwapKind::GivenOut, amount_raw);
+    let wrapped_in = pool_swap(&pool_state, SwapKind::GivenOut, wrapped_out);
+    assert_eq!(result.wrapped_amount_out_raw, wrapped_out);
+    assert_eq!(result.wrapped_amount_in_raw, wrapped_in);
+    assert_eq!(
+        result.amount_calculated_raw,
+        buffer_swap(&buffers[0], true, SwapKind::GivenOut, wrapped_in)
+    );
+}
+
+#[test]
+fn test_boosted_swap_reports_buffers() {
+    let vault = Vault::new();
+    let (pool_state, mut buffers) = find_boosted_pool();
+    // The buffer in holds enough wrapped tokens for the wrap, the buffer out has no balances
+    buffers[0].mutable.underlying_balance = U256::from(1_000_000_000u64);
+    buffers[0].mutable.wrapped_balance = U256::from(1_000_000_000u64);
+    let swap_input = SwapInput {
+        swap_kind: SwapKind::GivenIn,
+        amount_raw: U256::from(1_000_000u64),
+        token_in: UNDERLYING_TOKENS[0].to_string(),
+        token_out: UNDERLYING_TOKENS[1].to_string(),
+    };
+
+    let result = vault
+        .swap_boosted(&swap_input, &pool_state, &buffers, None)
+        .unwrap();
+    let wrap_result = result.wrap_result.unwrap();
+    let unwrap_result = result.unwrap_result.unwrap();
+    assert_eq!(wrap_result.path, BufferWrapOrUnwrapPath::Buffer);
+    assert_eq!(unwrap_result.path, BufferWrapOrUnwrapPath::Vault);
+    assert_eq!(
+        wrap_result.amount_calculated_raw,
+        result.wrapped_amount_in_raw
+    );
+    assert_eq!(
+        unwrap_result.amount_calculated_raw,
+        result.amount_calculated_raw
+    );
+    assert_eq!(
+        wrap_result.buffer_state.mutable.underlying_balance,
+        U256::from(1_001_000_000u64)
+    );
+    assert_eq!(
+        wrap_result.buffer_state.mutable.wrapped_balance,
+        U256::from(1_000_000_000u64) - result.wrapped_amount_in_raw
+    );
+
+    // Routes chain the updated buffer states into the next swap
+    let buffers = vec![wrap_result.buffer_state, unwrap_result.buffer_state];
+    let next = vault
+        .swap_boosted(&swap_input, &pool_state, &buffers, None)
+        .unwrap();
+    assert_eq!(
+        next.wrap_result
+            .unwrap()
+            .buffer_state
+            .mutable
+            .underlying_balance,
+        U256::from(1_002_000_000u64)
+    );
+}
+
+#[test]
+fn test_boosted_swap_partially_boosted() {
+    let vault = Vault::new();
+    let (pool_state, buffers) = find_boosted_pool();
+    let amount_raw = U256::from(1_000_000u64);
+
+    // Only the token in has a buffer, and the token out is taken as the pool token
+    let result = vault
+        .swap_boosted(
+            &SwapInput {
+                swap_kind: SwapKind::GivenIn,
+                amount_raw,
+                token_in: UNDERLYING_TOKENS[0].to_string(),
+                token_out: pool_state.base().tokens[1].clone(),
+            },
+            &pool_state,
+            &buffers[..1],
+            None,
+        )
+        .unwrap();
+    assert_eq!(result.amount_calculated_raw, result.wrapped_amount_out_raw);
+    assert!(result.wrap_result.is_some());
+    assert_eq!(result.unwrap_result, None);
+    assert_eq!(
+        result.wrapped_amount_out_raw,
+        pool_swap(
+            &pool_state,
+            SwapKind::GivenIn,
+            buffer_swap(&buffers[0], true, SwapKind::GivenIn, amount_raw)
+        )
+    );
+
+    // Without its buffer, the underlying token out is not a token of the pool
+    assert_eq!(
+        vault
+            .swap_boosted(
+                &SwapInput {
+                    swap_kind: SwapKind::GivenIn,
+                    amount_raw,
+                    token_in: UNDERLYING_TOKENS[0].to_string(),
+                    token_out: UNDERLYING_TOKENS[1].to_string(),
+                },
+                &pool_state,
+                &buffers[..1],
+                None,
+            )
+            .unwrap_err(),
+        PoolError::OutputTokenNotFound
+    );
+}
+
+#[test]
+fn test_boosted_add_liquidity() {
+    let vault = Vault::new();
+    let (pool_state, buffers) = find_boosted_pool();
+    let total_supply = pool_state.base().total_supply;
+
+    // Exact underlying amounts in are wrapped, then added
+    let amounts_in_raw = vec![U256::from(1_000_000u64), U256::from(2_000_000u64)];
+    let result = vault
+        .add_liquidity_boosted(
+            &AddLiquidityInput {
+                pool: pool_state.base().pool_address.clone(),
+                max_amounts_in_raw: amounts_in_raw.clone(),
+                min_bpt_amount_out_raw: U256::ZERO,
+                kind: AddLiquidityKind::Unbalanced,
+            },
+            &pool_state,
+            &buffers,
+            None,
+        )
+        .unwrap();
+    let wrapped_amounts_in_raw: Vec<U256> = (0..2)
+        .map(|i| buffer_swap(&buffers[i], true, SwapKind::GivenIn, amounts_in_raw[i]))
+        .collect();
+    assert_eq!(result.amounts_in_raw, amounts_in_raw);
+    assert_eq!(result.wrapped_amounts_in_raw, wrapped_amounts_in_raw);
+    assert_eq!(
+        result.bpt_amount_out_raw,
+        vault
+            .add_liquidity(
+                &AddLiquidityInput {
+                    pool: pool_state.base().pool_address.clone(),
+                    max_amounts_in_raw: wrapped_amounts_in_raw,
+                    min_bpt_amount_out_raw: U256::ZERO,
+                    kind: AddLiquidityKind::Unbalanced,
+                },
+                &pool_state,
+                None,
+            )
+            .unwrap()
+            .bpt_amount_out_raw
+    );
+
+    // An exact BPT out asks the pool for wrapped amounts, which are minted from the underlying
+    let bpt_amount_out_raw = total_supply / U256::from(1_000);
+    let result = vault
+        .add_liquidity_boosted(
+            &AddLiquidityInput {
+                pool: pool_state.base().pool_address.clone(),
+                max_amounts_in_raw: vec![U256::MAX; 2],
+                min_bpt_amount_out_raw: bpt_amount_out_raw,
+                kind: AddLiquidityKind::Proportional,
+            },
+            &pool_state,
+            &buffers,
+            None,
+        )
+        .unwrap();
+    assert_eq!(result.bpt_amount_out_raw, bpt_amount_out_raw);
+    for (i, buffer_state) in buffers.iter().enumerate() {
+        assert_eq!(
+            result.amounts_in_raw[i],
+            buffer_swap(
+                buffer_state,
+                true,
+                SwapKind::GivenOut,
+                result.wrapped_amounts_in_raw[i]
+            )
+        );
+    }
+}
+
+#[test]
+fn test_boosted_donation() {
+    let vault = Vault::new();
+    let (mut pool_state, buffers) = find_boosted_pool();
+    let donate = |pool_state: &PoolState| {
+        vault.add_liquidity_boosted(
+            &AddLiquidityInput {
+                pool: pool_state.base().pool_address.clone(),
+                max_amounts_in_raw: vec![U256::from(1_000_000u64); 2],
+                min_bpt_amount_out_raw: U256::ZERO,
+                kind: AddLiquidityKind::Donation,
+            },
+            pool_state,
+            &buffers,
+            None,
+        )
+    };
+
+    assert_eq!(
+        donate(&pool_state).unwrap_err(),
+        PoolError::Custom("DoesNotSupportDonation".to_string())
+    );
+
+    pool_state.base_mut().supports_donation = true;
+    let result = donate(&pool_state).unwrap();
+    assert_eq!(result.bpt_amount_out_raw, U256::ZERO);
+    assert_eq!(result.amounts_in_raw, vec![U256::from(1_000_000u64); 2]);
+}
+
+#[test]
+fn test_boosted_remove_liquidity() {
+    let vault = Vault::new();
+    let (pool_state, buffers) = find_boosted_pool();
+    let total_supply = pool_state.base().total_supply;
+
+    // Exact BPT in: the pool pays out wrapped amounts, which are redeemed
+    let bpt_amount_in_raw = total_supply / U256::from(1_000);
+    let result = vault
+        .remove_liquidity_boosted(
+            &RemoveLiquidityInput {
+                pool: pool_state.base().pool_address.clone(),
+                min_amounts_out_raw: vec![U256::ZERO; 2],
+                max_bpt_amount_in_raw: bpt_amount_in_raw,
+                kind: RemoveLiquidityKind::Proportional,
+            },
+            &pool_state,
+            &buffers,
+            None,
+        )
+        .unwrap();
+    assert_eq!(result.bpt_amount_in_raw, bpt_amount_in_raw);
+    for (i, buffer_state) in buffers.iter().enumerate() {
+        assert_eq!(
+            result.amounts_out_raw[i],
+            buffer_swap(
+                buffer_state,
+                false,
+                SwapKind::GivenIn,
+                result.wrapped_amounts_out_raw[i]
+            )
+        );
+    }
+
+    // Exact underlying out: the wrapped amount to withdraw is removed from the pool
+    let amount_out_raw = U256::from(1_000_000u64);
+    let result = vault
+        .remove_liquidity_boosted(
+            &RemoveLiquidityInput {
+                pool: pool_state.base().pool_address.clone(),
+                min_amounts_out_raw: vec![U256::ZERO, amount_out_raw],
+                max_bpt_amount_in_raw: U256::MAX,
+                kind: RemoveLiquidityKind::SingleTokenExactOut,
+            },
+            &pool_state,
+            &buffers,
+            None,
+        )
+        .unwrap();
+    let wrapped_amount_out_raw =
+        buffer_swap(&buffers[1], false, SwapKind::GivenOut, amount_out_raw);
+    assert_eq!(result.amounts_out_raw, vec![U256::ZERO, amount_out_raw]);
+    assert_eq!(
+        result.wrapped_amounts_out_raw,
+        vec![U256::ZERO, wrapped_amount_out_raw]
+    );
+    // Nothing is unwrapped for the token without an amount out
+    assert_eq!(result.unwrap_results[0], None);
+    assert!(result.unwrap_results[1].is_some());
+    assert_eq!(
+        result.bpt_amount_in_raw,
+        vault
+            .remove_liquidity(
+                &RemoveLiquidityInput {
+                    pool: pool_state.base().pool_address.clone(),
+                    min_amounts_out_raw: vec![U256::ZERO, wrapped_amount_out_raw],
+                    max_bpt_amount_in_raw: U256::MAX,
+                    kind: RemoveLiquidityKind::SingleTokenExactOut,
+                },
+                &pool_state,
+                None,
+            )
+            .unwrap()
+            .bpt_amount_in_raw
+    );
+}
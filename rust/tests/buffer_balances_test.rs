@@ -0,0 +1,237 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::errors::PoolError;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::pools::buffer::{
+    get_buffer_underlying_surplus, BufferState, BufferWrapOrUnwrapPath,
+};
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::find_buffer;
+
+const WAD: u128 = 1_000_000_000_000_000_000;
+
+fn wrap(buffer_state: &BufferState, swap_kind: SwapKind, amount_raw: u64) -> SwapInput {
+    SwapInput {
+        swap_kind,
+        amount_raw: U256::from(amount_raw),
+        token_in: buffer_state.immutable.tokens[1].clone(),
+        token_out: buffer_state.immutable.pool_address.clone(),
+    }
+}
+
+fn unwrap(buffer_state: &BufferState, swap_kind: SwapKind, amount_raw: u64) -> SwapInput {
+    SwapInput {
+        swap_kind,
+        amount_raw: U256::from(amount_raw),
+        token_in: buffer_state.immutable.pool_address.clone(),
+        token_out: buffer_state.immutable.tokens[1].clone(),
+    }
+}
+
+/// The ERC4626 preview for a swap, which a buffer without balances or limits gives
+fn preview(swap_input: &SwapInput, buffer_state: &BufferState) -> U256 {
+    let mut empty = buffer_state.clone();
+    empty.mutable.underlying_balance = U256::ZERO;
+    empty.mutable.wrapped_balance = U256::ZERO;
+    empty.mutable.max_deposit = None;
+    empty.mutable.max_mint = None;
+    empty.mutable.max_withdraw = None;
+    empty.mutable.max_redeem = None;
+    Vault::new()
+        .swap(
+            swap_input,
+            &PoolStateOrBuffer::Buffer(Box::new(empty)),
+            None,
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_buffer_without_balances_goes_through_vault() {
+    let vault = Vault::new();
+    let buffer_state = find_buffer();
+
+    for swap_input in [
+        unwrap(&buffer_state, SwapKind::GivenIn, 1_000_000_000_000_000_000),
+        unwrap(&buffer_state, SwapKind::GivenOut, 1_000_000),
+        wrap(&buffer_state, SwapKind::GivenIn, 1_000_000),
+        wrap(&buffer_state, SwapKind::GivenOut, 1_000_000),
+    ] {
+        let result = vault
+            .buffer_wrap_or_unwrap(&swap_input, &buffer_state)
+            .unwrap();
+        assert_eq!(
+            result.amount_calculated_raw,
+            vault
+                .swap(
+                    &swap_input,
+                    &PoolStateOrBuffer::Buffer(Box::new(buffer_state.clone())),
+                    None,
+                )
+                .unwrap()
+        );
+        assert_eq!(result.path, BufferWrapOrUnwrapPath::Vault);
+        assert_eq!(result.buffer_state, buffer_state);
+    }
+}
+
+#[test]
+fn test_buffer_serves_from_balances() {
+    let vault = Vault::new();
+    let mut buffer_state = find_buffer();
+    buffer_state.mutable.underlying_balance = U256::from(1_000_000_000u64);
+    buffer_state.mutable.wrapped_balance = U256::from(1_000_000_000u64);
+    // Limits only apply to the ERC4626 vault, which the buffer does not call
+    buffer_state.mutable.max_deposit = Some(U256::ZERO);
+    buffer_state.mutable.max_mint = Some(U256::ZERO);
+    buffer_state.mutable.max_withdraw = Some(U256::ZERO);
+    buffer_state.mutable.max_redeem = Some(U256::ZERO);
+
+    for swap_input in [
+        wrap(&buffer_state, SwapKind::GivenIn, 10_000_000),
+        wrap(&buffer_state, SwapKind::GivenOut, 10_000_000),
+        unwrap(&buffer_state, SwapKind::GivenIn, 10_000_000),
+        unwrap(&buffer_state, SwapKind::GivenOut, 10_000_000),
+    ] {
+        let result = vault
+            .buffer_wrap_or_unwrap(&swap_input, &buffer_state)
+            .unwrap();
+        assert_eq!(result.path, BufferWrapOrUnwrapPath::Buffer);
+        assert_eq!(
+            result.amount_calculated_raw,
+            preview(&swap_input, &buffer_state)
+        );
+
+        let (amount_in, amount_out) = match swap_input.swap_kind {
+            SwapKind::GivenIn => (swap_input.amount_raw, result.amount_calculated_raw),
+            SwapKind::GivenOut => (result.amount_calculated_raw, swap_input.amount_raw),
+        };
+        let (underlying_delta, wrapped_delta) =
+            if swap_input.token_in == buffer_state.immutable.pool_address {
+                (
+                    -(amount_out.to::<u64>() as i64),
+                    amount_in.to::<u64>() as i64,
+                )
+            } else {
+                (
+                    amount_in.to::<u64>() as i64,
+                    -(amount_out.to::<u64>() as i64),
+                )
+            };
+        let after = &result.buffer_state.mutable;
+        assert_eq!(
+            after.underlying_balance.to::<u64>() as i64,
+            1_000_000_000 + underlying_delta
+        );
+        assert_eq!(
+            after.wrapped_balance.to::<u64>() as i64,
+            1_000_000_000 + wrapped_delta
+        );
+    }
+}
+
+#[test]
+fn test_exhausted_buffer_rebalances_through_vault() {
+    let vault = Vault::new();
+    let mut buffer_state = find_buffer();
+    let rate = buffer_state.mutable.rate;
+    // Mostly underlying, with too few wrapped tokens for the wrap
+    buffer_state.mutable.underlying_balance = U256::from(1_000_000_000u64);
+    buffer_state.mutable.wrapped_balance = U256::from(1_000_000u64);
+    let surplus = get_buffer_underlying_surplus(&buffer_state);
+    let wrapped_as_underlying = (U256::from(1_000_000u64) * rate).div_ceil(U256::from(WAD));
+    assert_eq!(
+        surplus,
+        (U256::from(1_000_000_000u64) - wrapped_as_underlying) / U256::from(2)
+    );
+
+    for swap_kind in [SwapKind::GivenIn, SwapKind::GivenOut] {
+        let swap_input = wrap(&buffer_state, swap_kind.clone(), 100_000_000);
+        let result = vault
+            .buffer_wrap_or_unwrap(&swap_input, &buffer_state)
+            .unwrap();
+        assert_eq!(result.path, BufferWrapOrUnwrapPath::Vault);
+        // The user gets the preview amount even though the surplus rides along with the wrap
+        assert_eq!(
+            result.amount_calculated_raw,
+            preview(&swap_input, &buffer_state)
+        );
+
+        // Half of the surplus leaves the buffer as underlying and comes back wrapped, and the
+        // buffer keeps the rounding difference of the combined deposit or mint
+        let amount = swap_input.amount_raw;
+        let surplus_wrapped = surplus * U256::from(WAD) / rate;
+        let (underlying_balance, wrapped_balance) = match swap_kind {
+            SwapKind::GivenIn => {
+                let total_out = (amount + surplus) * U256::from(WAD) / rate;
+                (
+                    U256::from(1_000_000_000u64) - surplus,
+                    U256::from(1_000_000u64) + total_out - result.amount_calculated_raw,
+                )
+            }
+            SwapKind::GivenOut => {
+                let total_in = ((amount + surplus_wrapped) * rate).div_ceil(U256::from(WAD));
+                (
+                    U256::from(1_000_000_000u64) + result.amount_calculated_raw - total_in,
+                    U256::from(1_000_000u64) + surplus_wrapped,
+                )
+            }
+        };
+        let after = &result.buffer_state.mutable;
+        assert_eq!(after.underlying_balance, underlying_balance);
+        assert_eq!(after.wrapped_balance, wrapped_balance);
+    }
+
+    // The ERC4626 deposit covers the amount and the surplus, so the limit applies to both
+    buffer_state.mutable.max_deposit = Some(U256::from(200_000_000u64));
+    assert_eq!(
+        vault
+            .buffer_wrap_or_unwrap(
+                &wrap(&buffer_state, SwapKind::GivenIn, 100_000_000),
+                &buffer_state
+            )
+            .unwrap_err(),
+        PoolError::ERC4626ExceededMaxDeposit {
+            assets: U256::from(100_000_000u64) + surplus,
+            max_assets: U256::from(200_000_000u64),
+        }
+    );
+
+    // The unwrap side is covered by the underlying balance
+    let result = vault
+        .buffer_wrap_or_unwrap(
+            &unwrap(&buffer_state, SwapKind::GivenIn, 100_000_000),
+            &buffer_state,
+        )
+        .unwrap();
+    assert_eq!(result.path, BufferWrapOrUnwrapPath::Buffer);
+}
+
+#[test]
+fn test_vault_path_rounding_stays_in_buffer() {
+    let vault = Vault::new();
+    let mut buffer_state = find_buffer();
+    // At a rate of 1.1, the amount and the surplus each round down by most of a wrapped token,
+    // so wrapping them together yields one more than wrapping them separately
+    buffer_state.mutable.rate = U256::from(1_100_000_000_000_000_000u64);
+    buffer_state.mutable.underlying_balance = U256::from(1_100_020u64);
+    buffer_state.mutable.wrapped_balance = U256::from(1_000_000u64);
+    assert_eq!(get_buffer_underlying_surplus(&buffer_state), U256::from(10));
+
+    let swap_input = wrap(&buffer_state, SwapKind::GivenIn, 100_000_000);
+    let result = vault
+        .buffer_wrap_or_unwrap(&swap_input, &buffer_state)
+        .unwrap();
+    assert_eq!(result.path, BufferWrapOrUnwrapPath::Vault);
+    assert_eq!(result.amount_calculated_raw, U256::from(90_909_090u64));
+    assert_eq!(
+        result.amount_calculated_raw,
+        preview(&swap_input, &buffer_state)
+    );
+
+    // The deposit of 100,000,010 yields 90,909,100 wrapped: 90,909,090 go to the user and the
+    // buffer keeps the other 10, one more than its surplus converts to on its own
+    let after = &result.buffer_state.mutable;
+    assert_eq!(after.underlying_balance, U256::from(1_100_010u64));
+    assert_eq!(after.wrapped_balance, U256::from(1_000_010u64));
+}
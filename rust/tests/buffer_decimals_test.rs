@@ -0,0 +1,119 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::pools::buffer::BufferState;
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::find_buffer;
+
+const WAD: u128 = 1_000_000_000_000_000_000;
+
+/// The waEthUSDC buffer, with the wrapped and underlying decimals set
+fn find_buffer_with_decimals(wrapped_decimals: u8, underlying_decimals: u8) -> BufferState {
+    let mut buffer_state = find_buffer();
+    buffer_state.immutable.wrapped_decimals = Some(wrapped_decimals);
+    buffer_state.immutable.underlying_decimals = Some(underlying_decimals);
+    buffer_state
+}
+
+fn swap(buffer_state: &BufferState, is_wrap: bool, swap_kind: SwapKind, amount_raw: U256) -> U256 {
+    let (token_in, token_out) = if is_wrap {
+        (
+            buffer_state.immutable.tokens[1].clone(),
+            buffer_state.immutable.pool_address.clone(),
+        )
+    } else {
+        (
+            buffer_state.immutable.pool_address.clone(),
+            buffer_state.immutable.tokens[1].clone(),
+        )
+    };
+    let swap_input = SwapInput {
+        swap_kind,
+        amount_raw,
+        token_in,
+        token_out,
+    };
+    let pool_state = PoolStateOrBuffer::Buffer(Box::new(buffer_state.clone()));
+    let vault = Vault::new();
+    let amount_calculated = vault.swap(&swap_input, &pool_state, None).unwrap();
+
+    let approx = vault.swap_approx(&swap_input, &pool_state).unwrap();
+    let exact = amount_calculated.to::<u128>() as f64;
+    assert!((approx - exact).abs() <= exact * 1e-12 + 1.0);
+
+    amount_calculated
+}
+
+#[test]
+fn test_buffer_with_same_decimals() {
+    let unset = {
+        let mut buffer_state = find_buffer_with_decimals(6, 6);
+        buffer_state.immutable.wrapped_decimals = None;
+        buffer_state.immutable.underlying_decimals = None;
+        buffer_state
+    };
+    let same = find_buffer_with_decimals(6, 6);
+
+    for (is_wrap, swap_kind) in [
+        (true, SwapKind::GivenIn),
+        (true, SwapKind::GivenOut),
+        (false, SwapKind::GivenIn),
+        (false, SwapKind::GivenOut),
+    ] {
+        let amount_raw = U256::from(1_000_000u64);
+        assert_eq!(
+            swap(&same, is_wrap, swap_kind.clone(), amount_raw),
+            swap(&unset, is_wrap, swap_kind, amount_raw)
+        );
+    }
+}
+
+#[test]
+fn test_buffer_with_decimals_offset() {
+    // 18-decimal shares over 6-decimal assets, with the rate per whole share
+    let buffer_state = find_buffer_with_decimals(18, 6);
+    let rate = buffer_state.mutable.rate;
+    let offset = U256::from(1_000_000_000_000u64);
+    let one_share = U256::from(WAD);
+    let one_usdc = U256::from(1_000_000u64);
+
+    // previewRedeem rounds down and previewMint rounds up
+    let redeemed = swap(&buffer_state, false, SwapKind::GivenIn, one_share);
+    assert_eq!(redeemed, rate / offset);
+    let minted_for = swap(&buffer_state, true, SwapKind::GivenOut, one_share);
+    assert_eq!(minted_for, rate.div_ceil(offset));
+
+    // previewDeposit rounds down and previewWithdraw rounds up, keeping all 18 share decimals
+    let deposited = swap(&buffer_state, true, SwapKind::GivenIn, one_usdc);
+    assert_eq!(deposited, one_usdc * one_share * offset / rate);
+    let withdrawn_for = swap(&buffer_state, false, SwapKind::GivenOut, one_usdc);
+    assert_eq!(withdrawn_for, deposited + U256::ONE);
+
+    // The same vault quoted without the offset only resolves whole 6-decimal shares
+    let unscaled = find_buffer_with_decimals(6, 6);
+    assert_eq!(
+        swap(&unscaled, true, SwapKind::GivenIn, one_usdc),
+        deposited / offset
+    );
+}
+
+#[test]
+fn test_buffer_with_negative_decimals_offset() {
+    // 6-decimal shares over 18-decimal assets
+    let buffer_state = find_buffer_with_decimals(6, 18);
+    let rate = buffer_state.mutable.rate;
+    let one_share = U256::from(1_000_000u64);
+
+    assert_eq!(
+        swap(&buffer_state, false, SwapKind::GivenIn, one_share),
+        rate
+    );
+    assert_eq!(
+        swap(&buffer_state, true, SwapKind::GivenIn, rate),
+        one_share
+    );
+    assert_eq!(
+        swap(&buffer_state, true, SwapKind::GivenOut, one_share),
+        rate
+    );
+}
@@ -0,0 +1,146 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::errors::PoolError;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::pools::buffer::{get_buffer_wrapped_surplus, BufferState};
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::find_buffer;
+
+fn unwrap(buffer_state: &BufferState, swap_kind: SwapKind, amount_raw: u64) -> SwapInput {
+    SwapInput {
+        swap_kind,
+        amount_raw: U256::from(amount_raw),
+        token_in: buffer_state.immutable.pool_address.clone(),
+        token_out: buffer_state.immutable.tokens[1].clone(),
+    }
+}
+
+fn swap(swap_input: &SwapInput, buffer_state: &BufferState) -> Result<U256, PoolError> {
+    Vault::new().swap(
+        swap_input,
+        &PoolStateOrBuffer::Buffer(Box::new(buffer_state.clone())),
+        None,
+    )
+}
+
+#[test]
+fn test_unwrap_limits() {
+    let mut buffer_state = find_buffer();
+    let redeem = unwrap(&buffer_state, SwapKind::GivenIn, 10_000_000);
+    let withdraw = unwrap(&buffer_state, SwapKind::GivenOut, 10_000_000);
+
+    // Limits at the amounts still pass
+    buffer_state.mutable.max_redeem = Some(U256::from(10_000_000u64));
+    buffer_state.mutable.max_withdraw = Some(U256::from(10_000_000u64));
+    assert!(swap(&redeem, &buffer_state).is_ok());
+    assert!(swap(&withdraw, &buffer_state).is_ok());
+
+    buffer_state.mutable.max_redeem = Some(U256::from(9_999_999u64));
+    buffer_state.mutable.max_withdraw = Some(U256::from(9_999_999u64));
+    assert_eq!(
+        swap(&redeem, &buffer_state).unwrap_err(),
+        PoolError::ERC4626ExceededMaxRedeem {
+            shares: U256::from(10_000_000u64),
+            max_shares: U256::from(9_999_999u64),
+        }
+    );
+    assert_eq!(
+        swap(&withdraw, &buffer_state).unwrap_err(),
+        PoolError::ERC4626ExceededMaxWithdraw {
+            assets: U256::from(10_000_000u64),
+            max_assets: U256::from(9_999_999u64),
+        }
+    );
+
+    // Wraps are limited by max_deposit and max_mint only
+    let wrap = SwapInput {
+        swap_kind: SwapKind::GivenIn,
+        amount_raw: U256::from(10_000_000u64),
+        token_in: buffer_state.immutable.tokens[1].clone(),
+        token_out: buffer_state.immutable.pool_address.clone(),
+    };
+    assert!(swap(&wrap, &buffer_state).is_ok());
+
+    // The approximation applies the same limits
+    for swap_input in [&redeem, &withdraw] {
+        assert_eq!(
+            Vault::new()
+                .swap_approx(
+                    swap_input,
+                    &PoolStateOrBuffer::Buffer(Box::new(buffer_state.clone())),
+                )
+                .unwrap_err(),
+            swap(swap_input, &buffer_state).unwrap_err()
+        );
+    }
+}
+
+#[test]
+fn test_unwrap_limits_with_buffer_balances() {
+    let vault = Vault::new();
+    let mut buffer_state = find_buffer();
+    buffer_state.mutable.max_redeem = Some(U256::from(100_000_000u64));
+
+    // A buffer holding enough underlying serves the unwrap without calling the ERC4626 vault
+    buffer_state.mutable.underlying_balance = U256::from(1_000_000_000u64);
+    buffer_state.mutable.wrapped_balance = U256::from(1_000_000_000u64);
+    let redeem = unwrap(&buffer_state, SwapKind::GivenIn, 200_000_000);
+    assert!(vault.buffer_wrap_or_unwrap(&redeem, &buffer_state).is_ok());
+
+    // Otherwise the redeem covers the amount and the wrapped surplus
+    buffer_state.mutable.underlying_balance = U256::from(1_000_000u64);
+    let surplus = get_buffer_wrapped_surplus(&buffer_state);
+    assert!(!surplus.is_zero());
+    assert_eq!(
+        vault
+            .buffer_wrap_or_unwrap(&redeem, &buffer_state)
+            .unwrap_err(),
+        PoolError::ERC4626ExceededMaxRedeem {
+            shares: U256::from(200_000_000u64) + surplus,
+            max_shares: U256::from(100_000_000u64),
+        }
+    );
+}
+
+#[test]
+fn test_wrap_limits() {
+    let mut buffer_state = find_buffer();
+    let wrap = |swap_kind: SwapKind| SwapInput {
+        swap_kind,
+        amount_raw: U256::from(10_000_000u64),
+        token_in: buffer_state.immutable.tokens[1].clone(),
+        token_out: buffer_state.immutable.pool_address.clone(),
+    };
+    let deposit = wrap(SwapKind::GivenIn);
+    let mint = wrap(SwapKind::GivenOut);
+
+    buffer_state.mutable.max_deposit = Some(U256::from(9_999_999u64));
+    buffer_state.mutable.max_mint = Some(U256::from(9_999_999u64));
+    assert_eq!(
+        swap(&deposit, &buffer_state).unwrap_err(),
+        PoolError::ERC4626ExceededMaxDeposit {
+            assets: U256::from(10_000_000u64),
+            max_assets: U256::from(9_999_999u64),
+        }
+    );
+    assert_eq!(
+        swap(&mint, &buffer_state).unwrap_err(),
+        PoolError::ERC4626ExceededMaxMint {
+            shares: U256::from(10_000_000u64),
+            max_shares: U256::from(9_999_999u64),
+        }
+    );
+
+    // The approximation applies the same limits
+    for swap_input in [&deposit, &mint] {
+        assert_eq!(
+            Vault::new()
+                .swap_approx(
+                    swap_input,
+                    &PoolStateOrBuffer::Buffer(Box::new(buffer_state.clone())),
+                )
+                .unwrap_err(),
+            swap(swap_input, &buffer_state).unwrap_err()
+        );
+    }
+}
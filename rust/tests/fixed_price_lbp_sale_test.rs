@@ -0,0 +1,137 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::errors::PoolError;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::pools::fixed_price_lbp::{FixedPriceLBPPool, FixedPriceLBPState};
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::convert_to_pool_state;
+use utils::read_test_data;
+
+const WAD: u128 = 1_000_000_000_000_000_000;
+
+fn find_fixed_price_lbp() -> FixedPriceLBPState {
+    let test_data = read_test_data().expect("Failed to read test data");
+    match convert_to_pool_state(&test_data.pools["11155111-10434442-FixedPriceLBP-MTK-USDC.json"]) {
+        PoolStateOrBuffer::Pool(pool_state) => match *pool_state {
+            PoolState::FixedPriceLBP(state) => state,
+            _ => panic!("Not a FixedPriceLBP pool"),
+        },
+        PoolStateOrBuffer::Buffer(_) => panic!("Not a FixedPriceLBP pool"),
+    }
+}
+
+fn buy(
+    state: &FixedPriceLBPState,
+    swap_kind: SwapKind,
+    amount_raw: U256,
+) -> Result<U256, PoolError> {
+    let immutable = &state.immutable;
+    Vault::new().swap(
+        &SwapInput {
+            swap_kind,
+            amount_raw,
+            token_in: state.base.tokens[immutable.reserve_token_index].clone(),
+            token_out: state.base.tokens[immutable.project_token_index].clone(),
+        },
+        &PoolStateOrBuffer::Pool(Box::new(PoolState::FixedPriceLBP(state.clone()))),
+        None,
+    )
+}
+
+#[test]
+fn test_fixed_price_lbp_sale_status() {
+    let mut state = find_fixed_price_lbp();
+    let project = state.immutable.project_token_index;
+    let reserve = state.immutable.reserve_token_index;
+    let now = state.mutable.current_timestamp;
+
+    // Nothing sold yet: every project token is left and the raise is all ahead
+    let status = FixedPriceLBPPool::new(state.clone()).sale_status().unwrap();
+    assert_eq!(status.current_timestamp, now);
+    assert!(status.is_sale_active);
+    assert_eq!(status.time_until_start, U256::ZERO);
+    assert_eq!(status.time_remaining, state.immutable.end_time - now);
+    assert_eq!(
+        status.project_tokens_remaining_raw,
+        U256::from(10_000u64) * U256::from(WAD)
+    );
+    assert_eq!(status.reserve_raised_raw, U256::ZERO);
+    assert_eq!(status.final_raise_raw, U256::from(10_000_000_000u64));
+    assert_eq!(status.raise_progress, U256::ZERO);
+
+    // A 10 USDC buy raises 10 USDC, swap fee included, and leaves the final raise ahead of it
+    let amount_in_raw = U256::from(10_000_000u64);
+    let amount_out_raw = buy(&state, SwapKind::GivenIn, amount_in_raw).unwrap();
+    state.base.balances_live_scaled_18[reserve] +=
+        amount_in_raw * state.base.scaling_factors[reserve];
+    state.base.balances_live_scaled_18[project] -= amount_out_raw;
+
+    let status = FixedPriceLBPPool::new(state.clone()).sale_status().unwrap();
+    assert_eq!(
+        status.project_tokens_remaining_raw,
+        U256::from(10_000u64) * U256::from(WAD) - amount_out_raw
+    );
+    assert_eq!(status.reserve_raised_raw, amount_in_raw);
+    assert_eq!(status.final_raise_raw, U256::from(10_000_100_000u64));
+    assert_eq!(
+        status.raise_progress,
+        U256::from(10_000_000u64) * U256::from(WAD) / U256::from(10_000_100_000u64)
+    );
+}
+
+#[test]
+fn test_fixed_price_lbp_max_buy() {
+    let state = find_fixed_price_lbp();
+    let project = state.immutable.project_token_index;
+
+    let max_buy = Vault::new().fixed_price_lbp_max_buy(&state).unwrap();
+    assert_eq!(
+        max_buy.project_amount_out_raw,
+        state.base.balances_live_scaled_18[project]
+    );
+    assert_eq!(
+        max_buy.reserve_amount_in_raw,
+        buy(&state, SwapKind::GivenOut, max_buy.project_amount_out_raw).unwrap()
+    );
+    // 10,000 project tokens at one USDC each, plus the 1% swap fee
+    assert_eq!(max_buy.reserve_amount_in_raw, U256::from(10_101_010_102u64));
+}
+
+#[test]
+fn test_fixed_price_lbp_outside_sale_window() {
+    let mut state = find_fixed_price_lbp();
+    let start_time = state.immutable.start_time;
+    let end_time = state.immutable.end_time;
+
+    for (current_timestamp, is_started) in [
+        (start_time - U256::from(1), false),
+        (end_time + U256::from(1), true),
+    ] {
+        state.mutable.current_timestamp = current_timestamp;
+        let expected = PoolError::SaleWindowClosed {
+            current_timestamp,
+            start_time,
+            end_time,
+        };
+        assert_eq!(
+            buy(&state, SwapKind::GivenIn, U256::from(10_000_000u64)).unwrap_err(),
+            expected
+        );
+        assert_eq!(
+            Vault::new().fixed_price_lbp_max_buy(&state).unwrap_err(),
+            expected
+        );
+        let pool = FixedPriceLBPPool::new(state.clone());
+
+        let status = pool.sale_status().unwrap();
+        assert!(!status.is_sale_active);
+        assert_eq!(status.time_until_start.is_zero(), is_started);
+        assert_eq!(status.time_remaining.is_zero(), is_started);
+    }
+
+    // Both ends of the window are open for trading
+    for current_timestamp in [start_time, end_time] {
+        state.mutable.current_timestamp = current_timestamp;
+        assert!(buy(&state, SwapKind::GivenIn, U256::from(10_000_000u64)).is_ok());
+    }
+}
@@ -0,0 +1,126 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::errors::PoolError;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::pools::gyro::GyroECLPPool;
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::find_eclp_pool;
+
+const ECLP_POOLS: [&str; 2] = [
+    "11155111-7748718-GyroECLP.json",
+    "1-23511249-GyroECLP-Barter.json",
+];
+
+fn assert_close(actual: U256, expected: U256, tolerance_divisor: u64, message: &str) {
+    assert!(
+        actual.abs_diff(expected) <= expected / U256::from(tolerance_divisor) + U256::from(1_000),
+        "{}: {} vs {}",
+        message,
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn test_eclp_analytics() {
+    let vault = Vault::new();
+    let num_points = 201;
+
+    for name in ECLP_POOLS {
+        let state = find_eclp_pool(name);
+        let balances = &state.base.balances_live_scaled_18;
+        let pool = GyroECLPPool::new(state.immutable.clone());
+        let analytics = pool.analytics(balances, num_points).unwrap();
+
+        let spot_price = vault
+            .spot_price(
+                &PoolState::GyroECLP(state.clone()),
+                &state.base.tokens[0],
+                &state.base.tokens[1],
+            )
+            .unwrap();
+        assert_eq!(analytics.current_price, spot_price);
+        assert!(!analytics.is_at_alpha_bound && !analytics.is_at_beta_bound);
+
+        // At each bound the pool holds all of its value in one token
+        assert!(analytics.balances_at_alpha[0] > balances[0]);
+        assert_eq!(analytics.balances_at_alpha[1], U256::ZERO);
+        assert_eq!(analytics.balances_at_beta[0], U256::ZERO);
+        assert!(analytics.balances_at_beta[1] > balances[1]);
+
+        // Samples run from alpha to beta, selling token 0 for token 1 as the price rises
+        let points = &analytics.liquidity_density;
+        assert_eq!(points.len(), num_points);
+        assert_eq!(points[0].price, state.immutable.alpha.into_raw());
+        assert_eq!(
+            points[num_points - 1].price,
+            state.immutable.beta.into_raw()
+        );
+        assert_close(
+            points[0].balances[0],
+            analytics.balances_at_alpha[0],
+            1_000_000,
+            "Balance at alpha",
+        );
+        assert_close(
+            points[num_points - 1].balances[1],
+            analytics.balances_at_beta[1],
+            1_000_000,
+            "Balance at beta",
+        );
+        for window in points.windows(2) {
+            assert!(window[0].price < window[1].price);
+            assert!(window[0].balances[0] >= window[1].balances[0]);
+            assert!(window[0].balances[1] <= window[1].balances[1]);
+        }
+
+        // The current balances sit on the curve between the samples around the current price
+        let upper = points
+            .iter()
+            .position(|point| point.price >= analytics.current_price)
+            .unwrap();
+        assert!(upper > 0);
+        assert!(points[upper - 1].balances[1] <= balances[1]);
+        assert!(points[upper].balances[1] >= balances[1]);
+        assert!(points[upper - 1].balances[0] >= balances[0]);
+        assert!(points[upper].balances[0] <= balances[0]);
+
+        // Integrating the density over the range gives all of token 1
+        let integral = points
+            .windows(2)
+            .map(|window| {
+                (window[0].liquidity_density + window[1].liquidity_density)
+                    * (window[1].price - window[0].price)
+                    / U256::from(2_000_000_000_000_000_000u128)
+            })
+            .fold(U256::ZERO, |sum, amount| sum + amount);
+        assert_close(
+            integral,
+            analytics.balances_at_beta[1],
+            100,
+            "Integrated density",
+        );
+    }
+}
+
+#[test]
+fn test_eclp_analytics_at_bounds() {
+    let state = find_eclp_pool(ECLP_POOLS[0]);
+    let pool = GyroECLPPool::new(state.immutable.clone());
+    let analytics = pool
+        .analytics(&state.base.balances_live_scaled_18, 2)
+        .unwrap();
+
+    let at_alpha = pool.analytics(&analytics.balances_at_alpha, 2).unwrap();
+    assert!(at_alpha.is_at_alpha_bound);
+    assert!(!at_alpha.is_at_beta_bound);
+
+    let at_beta = pool.analytics(&analytics.balances_at_beta, 2).unwrap();
+    assert!(at_beta.is_at_beta_bound);
+    assert!(!at_beta.is_at_alpha_bound);
+
+    assert!(matches!(
+        pool.analytics(&state.base.balances_live_scaled_18, 1),
+        Err(PoolError::InvalidInput(_))
+    ));
+}
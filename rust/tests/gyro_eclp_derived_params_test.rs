@@ -0,0 +1,147 @@
+use alloy_primitives::{I256, U256};
+use balancer_maths_rust::common::errors::PoolError;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::pools::gyro::{GyroECLPImmutable, GyroECLPState};
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::find_eclp_pool;
+
+const ECLP_POOLS: [&str; 2] = [
+    "11155111-7748718-GyroECLP.json",
+    "1-23511249-GyroECLP-Barter.json",
+];
+
+fn from_params(immutable: &GyroECLPImmutable) -> Result<GyroECLPImmutable, PoolError> {
+    GyroECLPImmutable::from_params(
+        immutable.alpha,
+        immutable.beta,
+        immutable.c,
+        immutable.s,
+        immutable.lambda,
+    )
+}
+
+#[test]
+fn test_derived_params_match_deployed_pools() {
+    // The deployed values are computed off-chain with more precision, so allow a few ulps
+    let tolerance = I256::try_from(100).unwrap();
+
+    for name in ECLP_POOLS {
+        let state = find_eclp_pool(name);
+        let expected = &state.immutable;
+        let derived = from_params(expected)
+            .unwrap_or_else(|error| panic!("Derivation failed for {}: {:?}", name, error));
+
+        let pairs = [
+            ("tau_alpha_x", derived.tau_alpha_x, expected.tau_alpha_x),
+            ("tau_alpha_y", derived.tau_alpha_y, expected.tau_alpha_y),
+            ("tau_beta_x", derived.tau_beta_x, expected.tau_beta_x),
+            ("tau_beta_y", derived.tau_beta_y, expected.tau_beta_y),
+            ("u", derived.u, expected.u),
+            ("v", derived.v, expected.v),
+            ("w", derived.w, expected.w),
+            ("z", derived.z, expected.z),
+        ];
+        for (field, actual, expected) in pairs {
+            assert!(
+                (actual - expected).abs() <= tolerance,
+                "{} mismatch for {}: {} vs {}",
+                field,
+                name,
+                actual,
+                expected
+            );
+        }
+        assert_eq!(derived.d_sq, expected.d_sq, "d_sq mismatch for {}", name);
+    }
+}
+
+#[test]
+fn test_derived_params_swap() {
+    let vault = Vault::new();
+
+    for name in ECLP_POOLS {
+        let state = find_eclp_pool(name);
+        let derived_state = GyroECLPState {
+            immutable: from_params(&state.immutable).unwrap(),
+            ..state.clone()
+        };
+
+        // Sell the scarcer token, so the pool moves away from its price bound
+        let base = &state.base;
+        let swap_input = SwapInput {
+            amount_raw: base.balances_live_scaled_18[1] / base.scaling_factors[1] / U256::from(100),
+            swap_kind: SwapKind::GivenIn,
+            token_in: base.tokens[1].clone(),
+            token_out: base.tokens[0].clone(),
+        };
+        let quote = |state: &GyroECLPState| {
+            vault
+                .swap(
+                    &swap_input,
+                    &PoolStateOrBuffer::Pool(Box::new(PoolState::GyroECLP(state.clone()))),
+                    None,
+                )
+                .unwrap()
+        };
+
+        let expected = quote(&state);
+        let actual = quote(&derived_state);
+        assert!(
+            actual.abs_diff(expected) <= expected / U256::from(1_000_000_000_000u64),
+            "Swap mismatch for {}: {} vs {}",
+            name,
+            actual,
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_invalid_params() {
+    let immutable = find_eclp_pool(ECLP_POOLS[0]).immutable;
+    let one = I256::try_from(1_000_000_000_000_000_000u128).unwrap();
+
+    let error = |message: &str| Err(PoolError::InvalidInput(message.to_string()));
+
+    assert_eq!(
+        GyroECLPImmutable::from_params(
+            immutable.beta,
+            immutable.alpha,
+            immutable.c,
+            immutable.s,
+            immutable.lambda
+        ),
+        error("Price bounds wrong")
+    );
+    assert_eq!(
+        GyroECLPImmutable::from_params(
+            immutable.alpha,
+            immutable.beta,
+            immutable.c,
+            -immutable.s,
+            immutable.lambda
+        ),
+        error("Rotation vector s wrong")
+    );
+    assert_eq!(
+        GyroECLPImmutable::from_params(
+            immutable.alpha,
+            immutable.beta,
+            immutable.c,
+            immutable.s / I256::try_from(2).unwrap(),
+            immutable.lambda
+        ),
+        error("Rotation vector not normalized")
+    );
+    assert_eq!(
+        GyroECLPImmutable::from_params(
+            immutable.alpha,
+            immutable.beta,
+            immutable.c,
+            immutable.s,
+            one * I256::try_from(200_000_000).unwrap()
+        ),
+        error("Stretching factor wrong")
+    );
+}
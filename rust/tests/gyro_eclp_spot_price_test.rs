@@ -0,0 +1,76 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::pools::gyro::{
+    calc_spot_price0in1, calculate_invariant_with_error, DerivedEclpParams, EclpParams,
+    GyroECLPImmutable, Vector2,
+};
+use balancer_maths_rust::vault::Vault;
+use std::str::FromStr;
+mod utils;
+use utils::find_eclp_pool;
+
+fn params(immutable: &GyroECLPImmutable) -> (EclpParams, DerivedEclpParams) {
+    (
+        EclpParams {
+            alpha: immutable.alpha,
+            beta: immutable.beta,
+            c: immutable.c,
+            s: immutable.s,
+            lambda: immutable.lambda,
+        },
+        DerivedEclpParams {
+            tau_alpha: Vector2 {
+                x: immutable.tau_alpha_x,
+                y: immutable.tau_alpha_y,
+            },
+            tau_beta: Vector2 {
+                x: immutable.tau_beta_x,
+                y: immutable.tau_beta_y,
+            },
+            u: immutable.u,
+            v: immutable.v,
+            w: immutable.w,
+            z: immutable.z,
+            d_sq: immutable.d_sq,
+        },
+    )
+}
+
+#[test]
+fn test_calc_spot_price0in1() {
+    // calcSpotPrice0in1 of the TypeScript implementation, with 1e18 unit vectors and division
+    // rounding towards zero as in the contract
+    let pools = [
+        ("11155111-7748718-GyroECLP.json", "999849345069669509"),
+        ("1-23511249-GyroECLP-Barter.json", "2510205747782636170051"),
+    ];
+
+    for (name, expected) in pools {
+        let state = find_eclp_pool(name);
+        let balances = &state.base.balances_live_scaled_18;
+        let (eclp_params, derived) = params(&state.immutable);
+        let (invariant, _) =
+            calculate_invariant_with_error(balances, &eclp_params, &derived).unwrap();
+
+        let spot_price =
+            calc_spot_price0in1(balances, &eclp_params, &derived, &invariant.into_raw());
+        assert_eq!(
+            spot_price,
+            U256::from_str(expected).unwrap(),
+            "Spot price mismatch for {}",
+            name
+        );
+
+        // The vault quotes the same price for token 0 in token 1
+        assert_eq!(
+            Vault::new()
+                .spot_price(
+                    &PoolState::GyroECLP(state.clone()),
+                    &state.base.tokens[0],
+                    &state.base.tokens[1],
+                )
+                .unwrap(),
+            spot_price
+        );
+    }
+}
@@ -0,0 +1,335 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::errors::PoolError;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::common::utils::{
+    copy_to_scaled18_apply_rate_round_down_array, to_raw_undo_rate_round_down,
+};
+use balancer_maths_rust::pools::reclamm::{
+    compute_theoretical_price_ratio_and_balances, ReClammPriceParams,
+};
+use balancer_maths_rust::vault::initialize::POOL_MINIMUM_TOTAL_SUPPLY;
+use balancer_maths_rust::vault::Vault;
+use std::str::FromStr;
+mod utils;
+use utils::find_pool;
+
+const WAD: u128 = 1_000_000_000_000_000_000;
+
+fn raw_amounts(pool_state: &PoolState, amounts_scaled18: &[U256]) -> Vec<U256> {
+    let base = pool_state.base();
+    amounts_scaled18
+        .iter()
+        .enumerate()
+        .map(|(i, amount)| {
+            to_raw_undo_rate_round_down(amount, &base.scaling_factors[i], &base.token_rates[i])
+                .unwrap()
+        })
+        .collect()
+}
+
+fn initialize_input(pool_state: &PoolState, exact_amounts_in_raw: Vec<U256>) -> InitializeInput {
+    InitializeInput {
+        pool: pool_state.pool_address().to_string(),
+        exact_amounts_in_raw,
+        reclamm_price_params: None,
+    }
+}
+
+/// Initialize a test pool with its current balances and with twice them, returning the BPT out of
+/// each
+fn initialize_live_balances(name: &str) -> [U256; 2] {
+    let vault = Vault::new();
+    let pool_state = find_pool(name);
+    let base = pool_state.base();
+    let exact_amounts_in_raw = raw_amounts(&pool_state, &base.balances_live_scaled_18);
+    let result = vault
+        .initialize(
+            &initialize_input(&pool_state, exact_amounts_in_raw.clone()),
+            &pool_state,
+        )
+        .unwrap_or_else(|error| panic!("Initialize failed for {}: {:?}", name, error));
+
+    let amounts_in_scaled18 = copy_to_scaled18_apply_rate_round_down_array(
+        &exact_amounts_in_raw,
+        &base.scaling_factors,
+        &base.token_rates,
+    )
+    .unwrap();
+    let initialized_base = result.pool_state.base();
+    assert_eq!(
+        initialized_base.balances_live_scaled_18,
+        amounts_in_scaled18
+    );
+    assert_eq!(
+        initialized_base.total_supply,
+        result.bpt_amount_out_raw + POOL_MINIMUM_TOTAL_SUPPLY,
+        "Total supply mismatch for {}",
+        name
+    );
+    assert!(result.virtual_balances.is_none());
+
+    // The invariant is linear in the balances, so doubling them doubles the supply (up to
+    // interpolated QuantAMM weights that do not sum to exactly one)
+    let doubled_amounts_in_raw = exact_amounts_in_raw
+        .iter()
+        .map(|amount| amount * U256::from(2))
+        .collect();
+    let doubled = vault
+        .initialize(
+            &initialize_input(&pool_state, doubled_amounts_in_raw),
+            &pool_state,
+        )
+        .unwrap();
+    let expected_supply = initialized_base.total_supply * U256::from(2);
+    assert!(
+        doubled
+            .pool_state
+            .base()
+            .total_supply
+            .abs_diff(expected_supply)
+            <= expected_supply / U256::from(1_000),
+        "Doubled supply mismatch for {}",
+        name
+    );
+
+    [result.bpt_amount_out_raw, doubled.bpt_amount_out_raw]
+}
+
+fn assert_initialize(name: &str, expected: [&str; 2]) {
+    assert_eq!(
+        initialize_live_balances(name),
+        expected.map(|value| U256::from_str(value).unwrap()),
+        "BPT out mismatch for {}",
+        name
+    );
+}
+
+#[test]
+fn test_initialize_weighted() {
+    assert_initialize(
+        "11155111-7439300-Weighted-USDC-DAI.json",
+        ["6569839937709427181516", "13139679875418855367762"],
+    );
+}
+
+#[test]
+fn test_initialize_stable() {
+    assert_initialize(
+        "11155111-7439300-Stable-stataUSDC-stataUSDT.json",
+        ["103437444549758199001668", "206874889099516399003338"],
+    );
+}
+
+#[test]
+fn test_initialize_gyro_eclp() {
+    assert_initialize(
+        "11155111-7748718-GyroECLP.json",
+        ["535740807545474", "1071481616090951"],
+    );
+}
+
+#[test]
+fn test_initialize_liquidity_bootstrapping() {
+    assert_initialize(
+        "11155111-8085514-LBP-BAL-DAI.json",
+        ["999999999998979998", "1999999999998959992"],
+    );
+}
+
+#[test]
+fn test_initialize_quantamm() {
+    assert_initialize(
+        "1-22524240-QuantAMM.json",
+        ["21345201471527227274", "42692933025649586553"],
+    );
+}
+
+#[test]
+fn test_initialize_minimum_total_supply() {
+    let vault = Vault::new();
+    let pool_state = find_pool("11155111-7439300-Weighted-USDC-DAI.json");
+    let token_count = pool_state.base().tokens.len();
+
+    assert_eq!(
+        vault
+            .initialize(
+                &initialize_input(&pool_state, vec![U256::ONE; token_count]),
+                &pool_state
+            )
+            .unwrap_err(),
+        PoolError::PoolTotalSupplyTooLow
+    );
+    assert!(matches!(
+        vault.initialize(
+            &initialize_input(&pool_state, vec![U256::ONE; token_count + 1]),
+            &pool_state
+        ),
+        Err(PoolError::InvalidInput(_))
+    ));
+}
+
+/// Initialize a ReClamm test pool with 10 WETH between 1000 and 4000 USDC, returning the BPT out and
+/// the initial virtual balances
+fn initialize_reclamm(name: &str) -> (U256, Vec<U256>) {
+    let vault = Vault::new();
+    let pool_state = find_pool(name);
+    assert!(matches!(
+        pool_state,
+        PoolState::ReClamm(_) | PoolState::ReClammV2(_)
+    ));
+
+    // WETH/USDC between 1000 and 4000, at 2500
+    let price_params = ReClammPriceParams {
+        initial_min_price: U256::from(1000 * WAD),
+        initial_max_price: U256::from(4000 * WAD),
+        initial_target_price: U256::from(2500 * WAD),
+    };
+    let (theoretical_balances, _, _, fourth_root_price_ratio) =
+        compute_theoretical_price_ratio_and_balances(
+            &price_params.initial_min_price,
+            &price_params.initial_max_price,
+            &price_params.initial_target_price,
+        )
+        .unwrap();
+    // Fourth root of 4
+    assert!(
+        fourth_root_price_ratio.abs_diff(U256::from(1_414_213_562_373_095_048u128))
+            <= U256::from(1_000)
+    );
+
+    let tokens = pool_state.base().tokens.clone();
+
+    // Seed 10 WETH and the matching amount of USDC
+    let amounts_scaled18: Vec<U256> = theoretical_balances
+        .iter()
+        .map(|balance| balance * U256::from(10 * WAD) / theoretical_balances[0])
+        .collect();
+    let exact_amounts_in_raw = raw_amounts(&pool_state, &amounts_scaled18);
+    let input = InitializeInput {
+        reclamm_price_params: Some(price_params.clone()),
+        ..initialize_input(&pool_state, exact_amounts_in_raw.clone())
+    };
+    let result = vault
+        .initialize(&input, &pool_state)
+        .unwrap_or_else(|error| panic!("Initialize failed for {}: {:?}", name, error));
+
+    let virtual_balances = result.virtual_balances.clone().unwrap();
+    let (last_virtual_balances, start_fourth_root_price_ratio) = match &result.pool_state {
+        PoolState::ReClamm(state) => (
+            state.mutable.last_virtual_balances.clone(),
+            state.mutable.start_fourth_root_price_ratio,
+        ),
+        PoolState::ReClammV2(state) => (
+            state.mutable.last_virtual_balances.clone(),
+            state.mutable.start_fourth_root_price_ratio,
+        ),
+        _ => unreachable!(),
+    };
+    assert_eq!(last_virtual_balances, virtual_balances);
+    assert_eq!(start_fourth_root_price_ratio, fourth_root_price_ratio);
+
+    // Supply is the invariant (Ra + Va)(Rb + Vb), rounded down
+    let balances = &result.pool_state.base().balances_live_scaled_18;
+    let invariant =
+        (balances[0] + virtual_balances[0]) * (balances[1] + virtual_balances[1]) / U256::from(WAD);
+    assert_eq!(result.pool_state.base().total_supply, invariant);
+    assert_eq!(
+        result.bpt_amount_out_raw,
+        invariant - POOL_MINIMUM_TOTAL_SUPPLY
+    );
+
+    // The pool starts at the target price, with the configured price range
+    let tolerance = U256::from(WAD / 10_000);
+    let price = vault
+        .spot_price(&result.pool_state, &tokens[0], &tokens[1])
+        .unwrap();
+    assert!(
+        price.abs_diff(price_params.initial_target_price)
+            <= price_params.initial_target_price * tolerance / U256::from(WAD),
+        "Initial price {} mismatch for {}",
+        price,
+        name
+    );
+    let min_price = virtual_balances[1] * virtual_balances[1] / invariant;
+    let max_price =
+        invariant * U256::from(WAD) / virtual_balances[0] * U256::from(WAD) / virtual_balances[0];
+    assert!(
+        min_price.abs_diff(price_params.initial_min_price)
+            <= price_params.initial_min_price * tolerance / U256::from(WAD)
+    );
+    assert!(
+        max_price.abs_diff(price_params.initial_max_price)
+            <= price_params.initial_max_price * tolerance / U256::from(WAD)
+    );
+
+    // Balances off the target price ratio are rejected
+    let mut unbalanced_amounts_in_raw = exact_amounts_in_raw.clone();
+    unbalanced_amounts_in_raw[1] *= U256::from(2);
+    let unbalanced_input = InitializeInput {
+        exact_amounts_in_raw: unbalanced_amounts_in_raw,
+        ..input.clone()
+    };
+    assert_eq!(
+        vault
+            .initialize(&unbalanced_input, &pool_state)
+            .unwrap_err(),
+        PoolError::Custom("BalanceRatioExceedsTolerance".to_string())
+    );
+
+    // Price params are required and must be a valid range
+    assert!(matches!(
+        vault.initialize(
+            &initialize_input(&pool_state, exact_amounts_in_raw.clone()),
+            &pool_state
+        ),
+        Err(PoolError::InvalidInput(_))
+    ));
+    let inverted_input = InitializeInput {
+        reclamm_price_params: Some(ReClammPriceParams {
+            initial_min_price: price_params.initial_max_price,
+            initial_max_price: price_params.initial_min_price,
+            ..price_params.clone()
+        }),
+        ..input.clone()
+    };
+    assert_eq!(
+        vault.initialize(&inverted_input, &pool_state).unwrap_err(),
+        PoolError::Custom("InvalidInitialPrice".to_string())
+    );
+
+    (result.bpt_amount_out_raw, virtual_balances)
+}
+
+fn assert_initialize_reclamm(name: &str, expected_bpt_out: &str, expected_virtual: [&str; 2]) {
+    assert_eq!(
+        initialize_reclamm(name),
+        (
+            U256::from_str(expected_bpt_out).unwrap(),
+            expected_virtual
+                .map(|value| U256::from_str(value).unwrap())
+                .to_vec()
+        ),
+        "Initialization mismatch for {}",
+        name
+    );
+}
+
+// The seed amounts are set in scaled 18, so both pools start from the same state whatever the
+// decimals of their tokens
+#[test]
+fn test_initialize_reclamm() {
+    assert_initialize_reclamm(
+        "8453-31094200-ReClamm-WETH-USDC-In-Range.json",
+        "5699802364583046325950779",
+        ["37748517734455000000", "75497035468910000000000"],
+    );
+}
+
+#[test]
+fn test_initialize_reclamm_v2() {
+    assert_initialize_reclamm(
+        "8453-32632378-ReClamm-V2.json",
+        "5699802364583046325950779",
+        ["37748517734455000000", "75497035468910000000000"],
+    );
+}
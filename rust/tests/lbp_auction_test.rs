@@ -0,0 +1,217 @@
+use alloy_primitives::{I256, U256};
+use balancer_maths_rust::common::errors::PoolError;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::pools::liquidity_bootstrapping::{
+    LiquidityBootstrappingPool, LiquidityBootstrappingState,
+};
+use balancer_maths_rust::vault::lbp_auction::{LbpOrder, LbpOrderSide};
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::find_lbp;
+
+const WAD: u128 = 1_000_000_000_000_000_000;
+
+/// A state at the start of the sale, before any trades
+fn sale_start() -> LiquidityBootstrappingState {
+    let mut state = find_lbp();
+    state.mutable.current_timestamp = state.immutable.start_time;
+    state.mutable.is_swap_enabled = true;
+    state
+}
+
+fn buy(timestamp: U256, swap_kind: SwapKind, amount_raw: u128) -> LbpOrder {
+    LbpOrder {
+        timestamp,
+        side: LbpOrderSide::Buy,
+        swap_kind,
+        amount_raw: U256::from(amount_raw),
+    }
+}
+
+#[test]
+fn test_lbp_auction_buys() {
+    let vault = Vault::new();
+    let state = sale_start();
+    let project = state.immutable.project_token_index;
+    let reserve = 1 - project;
+    let start = state.immutable.start_time;
+    let end = state.immutable.end_time;
+    let step = (end - start) / U256::from(4);
+
+    let orders = vec![
+        buy(start, SwapKind::GivenIn, WAD / 100),
+        buy(start + step, SwapKind::GivenIn, WAD / 50),
+        buy(start + step * U256::from(2), SwapKind::GivenOut, WAD / 100),
+        buy(end, SwapKind::GivenIn, WAD / 100),
+    ];
+    let result = vault.simulate_lbp_auction(&state, &orders).unwrap();
+    assert_eq!(result.fills.len(), orders.len());
+
+    // Each fill is the vault quote against the state left by the previous orders
+    let mut chained = state.clone();
+    let mut reserve_proceeds = U256::ZERO;
+    let mut project_sold = U256::ZERO;
+    for (order, fill) in orders.iter().zip(&result.fills) {
+        chained.mutable.current_timestamp = order.timestamp;
+        let price_before = LiquidityBootstrappingPool::new(chained.clone())
+            .unwrap()
+            .price_curve(&[order.timestamp])
+            .unwrap()[0]
+            .project_token_price;
+        let quote = vault
+            .swap(
+                &SwapInput {
+                    swap_kind: order.swap_kind.clone(),
+                    amount_raw: order.amount_raw,
+                    token_in: state.base.tokens[reserve].clone(),
+                    token_out: state.base.tokens[project].clone(),
+                },
+                &PoolStateOrBuffer::Pool(Box::new(PoolState::LiquidityBootstrapping(
+                    chained.clone(),
+                ))),
+                None,
+            )
+            .unwrap();
+        match order.swap_kind {
+            SwapKind::GivenIn => {
+                assert_eq!(fill.amount_in_raw, order.amount_raw);
+                assert_eq!(fill.amount_out_raw, quote);
+            }
+            SwapKind::GivenOut => {
+                assert_eq!(fill.amount_in_raw, quote);
+                assert_eq!(fill.amount_out_raw, order.amount_raw);
+            }
+        }
+        assert_eq!(
+            fill.clearing_price,
+            fill.amount_in_raw * U256::from(WAD) / fill.amount_out_raw
+        );
+
+        // Buyers pay at least the spot price, and push it up
+        assert!(fill.clearing_price > price_before);
+        assert!(fill.spot_price_after > fill.clearing_price);
+
+        chained.base.balances_live_scaled_18[reserve] += fill.amount_in_raw;
+        chained.base.balances_live_scaled_18[project] -= fill.amount_out_raw;
+        reserve_proceeds += fill.amount_in_raw;
+        project_sold += fill.amount_out_raw;
+    }
+
+    // Without aggregate fees, the whole of each trade stays in the pool
+    assert_eq!(state.base.aggregate_swap_fee, U256::ZERO);
+    assert_eq!(
+        result.final_state.base.balances_live_scaled_18,
+        chained.base.balances_live_scaled_18
+    );
+    assert_eq!(
+        result.reserve_proceeds_raw,
+        I256::try_from(reserve_proceeds).unwrap()
+    );
+    assert_eq!(
+        result.project_tokens_sold_raw,
+        I256::try_from(project_sold).unwrap()
+    );
+    assert_eq!(result.final_state.mutable.current_timestamp, end);
+    assert!(result.final_state.mutable.is_swap_enabled);
+}
+
+#[test]
+fn test_lbp_auction_runs_to_end_time() {
+    let vault = Vault::new();
+    let state = sale_start();
+    let end = state.immutable.end_time;
+
+    let result = vault.simulate_lbp_auction(&state, &[]).unwrap();
+    assert!(result.fills.is_empty());
+    assert_eq!(result.reserve_proceeds_raw, I256::ZERO);
+    assert_eq!(result.project_tokens_sold_raw, I256::ZERO);
+    assert_eq!(result.final_state.mutable.current_timestamp, end);
+    assert_eq!(
+        LiquidityBootstrappingPool::new(result.final_state)
+            .unwrap()
+            .normalized_weights(),
+        state.immutable.end_weights.as_slice()
+    );
+}
+
+#[test]
+fn test_lbp_auction_sells() {
+    let vault = Vault::new();
+    let mut state = sale_start();
+    let project = state.immutable.project_token_index;
+    let start = state.immutable.start_time;
+    let middle = start + (state.immutable.end_time - start) / U256::from(2);
+
+    let orders = vec![
+        buy(start, SwapKind::GivenIn, WAD / 10),
+        LbpOrder {
+            timestamp: middle,
+            side: LbpOrderSide::Sell,
+            swap_kind: SwapKind::GivenIn,
+            amount_raw: U256::from(WAD / 20),
+        },
+    ];
+
+    state.immutable.is_project_token_swap_in_blocked = true;
+    assert_eq!(
+        vault.simulate_lbp_auction(&state, &orders).unwrap_err(),
+        PoolError::InvalidSwapParameters
+    );
+
+    state.immutable.is_project_token_swap_in_blocked = false;
+    let result = vault.simulate_lbp_auction(&state, &orders).unwrap();
+    let (bought, sold) = (&result.fills[0], &result.fills[1]);
+    assert_eq!(sold.side, LbpOrderSide::Sell);
+    assert_eq!(sold.amount_in_raw, U256::from(WAD / 20));
+    assert_eq!(
+        sold.clearing_price,
+        sold.amount_out_raw * U256::from(WAD) / sold.amount_in_raw
+    );
+    // Sellers receive less than the spot price, and push it down
+    assert!(sold.spot_price_after < sold.clearing_price);
+    assert_eq!(
+        result.project_tokens_sold_raw,
+        I256::try_from(bought.amount_out_raw).unwrap()
+            - I256::try_from(sold.amount_in_raw).unwrap()
+    );
+    assert_eq!(
+        result.reserve_proceeds_raw,
+        I256::try_from(bought.amount_in_raw).unwrap()
+            - I256::try_from(sold.amount_out_raw).unwrap()
+    );
+    assert!(
+        result.final_state.base.balances_live_scaled_18[project]
+            > state.base.balances_live_scaled_18[project] - U256::from(WAD / 10)
+    );
+}
+
+#[test]
+fn test_lbp_auction_invalid_orders() {
+    let vault = Vault::new();
+    let state = sale_start();
+    let start = state.immutable.start_time;
+    let end = state.immutable.end_time;
+
+    // Orders out of timestamp order
+    assert!(matches!(
+        vault.simulate_lbp_auction(
+            &state,
+            &[
+                buy(start + U256::from(10), SwapKind::GivenIn, WAD / 100),
+                buy(start, SwapKind::GivenIn, WAD / 100),
+            ],
+        ),
+        Err(PoolError::InvalidInput(_))
+    ));
+
+    // Orders after the sale window
+    assert_eq!(
+        vault
+            .simulate_lbp_auction(
+                &state,
+                &[buy(end + U256::from(1), SwapKind::GivenIn, WAD / 100)]
+            )
+            .unwrap_err(),
+        PoolError::InvalidSwapParameters
+    );
+}
@@ -0,0 +1,106 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::errors::PoolError;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::pools::liquidity_bootstrapping::LiquidityBootstrappingPool;
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::find_lbp;
+
+const WAD: u128 = 1_000_000_000_000_000_000;
+
+#[test]
+fn test_lbp_price_curve() {
+    let state = find_lbp();
+    let immutable = &state.immutable;
+    let project = immutable.project_token_index;
+    let reserve = 1 - project;
+    let start = immutable.start_time;
+    let end = immutable.end_time;
+    let duration = end - start;
+
+    let timestamps: Vec<U256> = vec![
+        start - U256::from(100),
+        start,
+        start + duration / U256::from(4),
+        start + duration / U256::from(2),
+        end,
+        end + U256::from(100),
+    ];
+    let pool = LiquidityBootstrappingPool::new(state.clone()).unwrap();
+    let curve = pool.price_curve(&timestamps).unwrap();
+    assert_eq!(curve.len(), timestamps.len());
+
+    // Weights are held before the start and after the end of the schedule
+    assert_eq!(curve[0].normalized_weights, immutable.start_weights);
+    assert_eq!(curve[1].normalized_weights, immutable.start_weights);
+    assert_eq!(curve[4].normalized_weights, immutable.end_weights);
+    assert_eq!(curve[5].normalized_weights, immutable.end_weights);
+
+    for (timestamp, point) in timestamps.iter().zip(&curve) {
+        assert_eq!(point.timestamp, *timestamp);
+        assert_eq!(
+            point.normalized_weights[0] + point.normalized_weights[1],
+            U256::from(WAD)
+        );
+    }
+
+    // With the project weight falling, the zero-trade price falls over the sale
+    for window in curve[1..5].windows(2) {
+        assert!(window[1].normalized_weights[project] < window[0].normalized_weights[project]);
+        assert!(window[1].project_token_price < window[0].project_token_price);
+    }
+
+    // Equal balances, so the price is the weight ratio: 50/50 at the start and 10/90 at the end
+    assert_eq!(
+        state.base.balances_live_scaled_18[0],
+        state.base.balances_live_scaled_18[1]
+    );
+    assert_eq!(curve[1].project_token_price, U256::from(WAD));
+    assert_eq!(curve[4].project_token_price, U256::from(WAD / 9));
+
+    // The curve agrees with the spot price at the current timestamp
+    let now = pool
+        .price_curve(&[state.mutable.current_timestamp])
+        .unwrap()
+        .remove(0);
+    assert_eq!(now.normalized_weights, pool.normalized_weights());
+    let spot_price = Vault::new()
+        .spot_price(
+            &PoolState::LiquidityBootstrapping(state.clone()),
+            &state.base.tokens[project],
+            &state.base.tokens[reserve],
+        )
+        .unwrap();
+    assert_eq!(now.project_token_price, spot_price);
+}
+
+#[test]
+fn test_lbp_price_curve_undoes_rates() {
+    let mut state = find_lbp();
+    let project = state.immutable.project_token_index;
+    let reserve = 1 - project;
+    let start = state.immutable.start_time;
+
+    // The same live balance is half as many reserve tokens when each is worth two
+    let base_price = LiquidityBootstrappingPool::new(state.clone())
+        .unwrap()
+        .price_curve(&[start])
+        .unwrap()[0]
+        .project_token_price;
+    state.base.token_rates[reserve] = U256::from(2 * WAD);
+    let rated_price = LiquidityBootstrappingPool::new(state.clone())
+        .unwrap()
+        .price_curve(&[start])
+        .unwrap()[0]
+        .project_token_price;
+    assert_eq!(rated_price, base_price / U256::from(2));
+
+    state.base.balances_live_scaled_18[project] = U256::ZERO;
+    assert_eq!(
+        LiquidityBootstrappingPool::new(state)
+            .unwrap()
+            .price_curve(&[start])
+            .unwrap_err(),
+        PoolError::ZeroInvariant
+    );
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "parallel")]
+
+use alloy_primitives::U256;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::vault::parallel::PoolQuoteTarget;
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::convert_to_pool_state;
+use utils::read_test_data;
+
+#[test]
+fn test_parallel_swaps_match_vault_swaps() {
+    let test_data = read_test_data().expect("Failed to read test data");
+    let vault = Vault::new();
+
+    let pools: Vec<PoolStateOrBuffer> = test_data
+        .swaps
+        .iter()
+        .map(|swap_test| {
+            convert_to_pool_state(
+                test_data
+                    .pools
+                    .get(&swap_test.test)
+                    .unwrap_or_else(|| panic!("Pool not found for test: {}", swap_test.test)),
+            )
+        })
+        .collect();
+    let targets: Vec<PoolQuoteTarget> = pools
+        .iter()
+        .map(|pool| (pool, test_data.hook_state.as_ref()))
+        .collect();
+
+    // Each test swap is quoted against every pool, so most pairs hit token errors
+    let swap_inputs: Vec<SwapInput> = test_data
+        .swaps
+        .iter()
+        .flat_map(|swap_test| {
+            [U256::ZERO, swap_test.amount_raw].map(|amount_raw| SwapInput {
+                amount_raw,
+                token_in: swap_test.token_in.clone(),
+                token_out: swap_test.token_out.clone(),
+                swap_kind: match swap_test.swap_kind {
+                    0 => SwapKind::GivenIn,
+                    _ => SwapKind::GivenOut,
+                },
+            })
+        })
+        .collect();
+
+    let many_results = vault.swap_many_pools_parallel(&swap_inputs, &targets);
+    assert_eq!(many_results.len(), pools.len());
+
+    for (input_index, swap_input) in swap_inputs.iter().enumerate() {
+        let results = vault.swap_pools_parallel(swap_input, &targets);
+        assert_eq!(results.len(), pools.len());
+
+        for (pool_index, pool) in pools.iter().enumerate() {
+            let expected = vault.swap(swap_input, pool, test_data.hook_state.as_ref());
+            assert_eq!(results[pool_index], expected);
+            assert_eq!(many_results[pool_index][input_index], expected);
+        }
+    }
+}
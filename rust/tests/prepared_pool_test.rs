@@ -0,0 +1,57 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::vault::{PreparedPool, Vault};
+mod utils;
+use utils::convert_to_pool_state;
+use utils::read_test_data;
+
+#[test]
+fn test_prepared_pool_swaps_match_vault_swaps() {
+    let test_data = read_test_data().expect("Failed to read test data");
+    let vault = Vault::new();
+
+    for swap_test in test_data.swaps {
+        let pool_data = test_data
+            .pools
+            .get(&swap_test.test)
+            .unwrap_or_else(|| panic!("Pool not found for test: {}", swap_test.test));
+
+        let pool_state = match convert_to_pool_state(pool_data) {
+            PoolStateOrBuffer::Pool(pool_state) => *pool_state,
+            PoolStateOrBuffer::Buffer(_) => continue,
+        };
+        let pool_state_or_buffer = PoolStateOrBuffer::Pool(Box::new(pool_state.clone()));
+        let prepared_pool = PreparedPool::new(pool_state).expect("Failed to prepare pool");
+
+        // Quote the test amount as well as smaller and larger sizes against the same state
+        for amount_raw in [
+            swap_test.amount_raw,
+            swap_test.amount_raw / U256::from(3),
+            swap_test.amount_raw * U256::from(2),
+        ] {
+            let swap_input = SwapInput {
+                amount_raw,
+                token_in: swap_test.token_in.clone(),
+                token_out: swap_test.token_out.clone(),
+                swap_kind: match swap_test.swap_kind {
+                    0 => SwapKind::GivenIn,
+                    _ => SwapKind::GivenOut,
+                },
+            };
+
+            let expected = vault.swap(
+                &swap_input,
+                &pool_state_or_buffer,
+                test_data.hook_state.as_ref(),
+            );
+            let prepared =
+                vault.swap_prepared(&swap_input, &prepared_pool, test_data.hook_state.as_ref());
+
+            assert_eq!(
+                prepared, expected,
+                "Prepared swap mismatch for test: {} (amount: {})",
+                swap_test.test, amount_raw
+            );
+        }
+    }
+}
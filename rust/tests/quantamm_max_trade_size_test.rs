@@ -0,0 +1,174 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::errors::PoolError;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::common::utils::f64_to_u256;
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::convert_to_pool_state;
+use utils::read_test_data;
+
+const WAD: u128 = 1_000_000_000_000_000_000;
+const TOKEN_INDEX: usize = 2;
+
+fn find_quantamm() -> PoolState {
+    let test_data = read_test_data().expect("Failed to read test data");
+    match convert_to_pool_state(&test_data.pools["1-22524240-QuantAMM.json"]) {
+        PoolStateOrBuffer::Pool(pool_state) => *pool_state,
+        PoolStateOrBuffer::Buffer(_) => panic!("Not a QuantAMM pool"),
+    }
+}
+
+/// Largest amount the pool trades against the balance of the token
+fn max_amount_scaled_18(pool_state: &PoolState) -> U256 {
+    let max_trade_size_ratio = match pool_state {
+        PoolState::QuantAmm(state) => state.immutable.max_trade_size_ratio,
+        _ => unreachable!(),
+    };
+    pool_state.base().balances_live_scaled_18[TOKEN_INDEX] * max_trade_size_ratio / U256::from(WAD)
+}
+
+fn add_single_token(
+    vault: &Vault,
+    pool_state: &PoolState,
+    bpt_amount_out_raw: U256,
+) -> Result<AddLiquidityResult, PoolError> {
+    let base = pool_state.base();
+    let mut max_amounts_in_raw = vec![U256::ZERO; base.tokens.len()];
+    max_amounts_in_raw[TOKEN_INDEX] = U256::MAX;
+    vault.add_liquidity(
+        &AddLiquidityInput {
+            pool: base.pool_address.clone(),
+            max_amounts_in_raw,
+            min_bpt_amount_out_raw: bpt_amount_out_raw,
+            kind: AddLiquidityKind::SingleTokenExactOut,
+        },
+        pool_state,
+        None,
+    )
+}
+
+fn remove_single_token(
+    vault: &Vault,
+    pool_state: &PoolState,
+    bpt_amount_in_raw: U256,
+) -> Result<RemoveLiquidityResult, PoolError> {
+    let base = pool_state.base();
+    let mut min_amounts_out_raw = vec![U256::ZERO; base.tokens.len()];
+    min_amounts_out_raw[TOKEN_INDEX] = U256::ONE;
+    vault.remove_liquidity(
+        &RemoveLiquidityInput {
+            pool: base.pool_address.clone(),
+            min_amounts_out_raw,
+            max_bpt_amount_in_raw: bpt_amount_in_raw,
+            kind: RemoveLiquidityKind::SingleTokenExactIn,
+        },
+        pool_state,
+        None,
+    )
+}
+
+#[test]
+fn test_quantamm_max_trade_size_on_swaps() {
+    let vault = Vault::new();
+    let pool_state = find_quantamm();
+    let base = pool_state.base();
+    let max_amount = max_amount_scaled_18(&pool_state);
+
+    // USDC has 6 decimals, so twice the limit in raw amount is well over it after fees
+    let amount_raw = max_amount * U256::from(2) / base.scaling_factors[TOKEN_INDEX];
+    let swap_input = SwapInput {
+        swap_kind: SwapKind::GivenIn,
+        amount_raw,
+        token_in: base.tokens[TOKEN_INDEX].clone(),
+        token_out: base.tokens[0].clone(),
+    };
+    let pool = PoolStateOrBuffer::Pool(Box::new(pool_state.clone()));
+    let error = vault.swap(&swap_input, &pool, None).unwrap_err();
+    let PoolError::MaxTradeSizeRatioExceeded {
+        amount_scaled_18,
+        max_amount_scaled_18,
+    } = error
+    else {
+        panic!("Unexpected error: {:?}", error);
+    };
+    assert_eq!(max_amount_scaled_18, max_amount);
+    assert!(amount_scaled_18 > max_amount_scaled_18);
+
+    // Approximate quotes report the same amounts, up to f64 precision
+    let approx_error = vault.swap_approx(&swap_input, &pool).unwrap_err();
+    let PoolError::MaxTradeSizeRatioExceeded {
+        amount_scaled_18: approx_amount_scaled_18,
+        max_amount_scaled_18: approx_max_amount_scaled_18,
+    } = approx_error
+    else {
+        panic!("Unexpected error: {:?}", approx_error);
+    };
+    let tolerance = |amount: U256| amount / U256::from(1_000_000_000u64);
+    assert!(approx_amount_scaled_18.abs_diff(amount_scaled_18) <= tolerance(amount_scaled_18));
+    assert!(
+        approx_max_amount_scaled_18.abs_diff(max_amount_scaled_18)
+            <= tolerance(max_amount_scaled_18)
+    );
+}
+
+#[test]
+fn test_f64_to_u256() {
+    assert_eq!(f64_to_u256(0.0), Ok(U256::ZERO));
+    assert_eq!(f64_to_u256(1.6), Ok(U256::from(2)));
+
+    // Amounts past u128::MAX are converted, not saturated
+    let amount = f64_to_u256(2f64.powi(200)).unwrap();
+    assert_eq!(amount, U256::ONE << 200);
+
+    for value in [-1.0, f64::NAN, f64::INFINITY, 2f64.powi(256)] {
+        assert_eq!(f64_to_u256(value), Err(PoolError::MathOverflow));
+    }
+}
+
+#[test]
+fn test_quantamm_max_trade_size_on_single_token_liquidity() {
+    let vault = Vault::new();
+    let pool_state = find_quantamm();
+    let total_supply = pool_state.base().total_supply;
+    let max_amount = max_amount_scaled_18(&pool_state);
+
+    // With a weight of about 0.3, 1% of the supply moves the balance by about 3.4%
+    let small_bpt_amount = total_supply / U256::from(100);
+    let add = add_single_token(&vault, &pool_state, small_bpt_amount).unwrap();
+    assert!(!add.amounts_in_raw[TOKEN_INDEX].is_zero());
+    let remove = remove_single_token(&vault, &pool_state, small_bpt_amount).unwrap();
+    assert!(!remove.amounts_out_raw[TOKEN_INDEX].is_zero());
+
+    // 5% of the supply moves it by over 15%, past the 10% max trade size ratio
+    let large_bpt_amount = total_supply / U256::from(20);
+    for error in [
+        add_single_token(&vault, &pool_state, large_bpt_amount).unwrap_err(),
+        remove_single_token(&vault, &pool_state, large_bpt_amount).unwrap_err(),
+    ] {
+        match error {
+            PoolError::MaxTradeSizeRatioExceeded {
+                amount_scaled_18,
+                max_amount_scaled_18,
+            } => {
+                assert_eq!(max_amount_scaled_18, max_amount);
+                assert!(amount_scaled_18 > max_amount * U256::from(15) / U256::from(10));
+            }
+            error => panic!("Unexpected error: {:?}", error),
+        }
+    }
+
+    // Proportional operations move every balance by the same ratio and are not limited
+    let base = pool_state.base();
+    vault
+        .add_liquidity(
+            &AddLiquidityInput {
+                pool: base.pool_address.clone(),
+                max_amounts_in_raw: vec![U256::MAX; base.tokens.len()],
+                min_bpt_amount_out_raw: total_supply / U256::from(2),
+                kind: AddLiquidityKind::Proportional,
+            },
+            &pool_state,
+            None,
+        )
+        .unwrap();
+}
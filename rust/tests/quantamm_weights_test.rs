@@ -0,0 +1,207 @@
+use alloy_primitives::{I256, U256};
+use balancer_maths_rust::common::errors::PoolError;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::pools::quantamm::{
+    get_first_four_weights_and_multipliers, QuantAmmMutable, QuantAmmPool, QuantAmmState,
+};
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::convert_to_pool_state;
+use utils::read_test_data;
+
+const WAD: u128 = 1_000_000_000_000_000_000;
+const DAY: u64 = 86_400;
+
+fn find_quantamm() -> QuantAmmState {
+    let test_data = read_test_data().expect("Failed to read test data");
+    match convert_to_pool_state(&test_data.pools["1-22524240-QuantAMM.json"]) {
+        PoolStateOrBuffer::Pool(pool_state) => match *pool_state {
+            PoolState::QuantAmm(state) => state,
+            _ => panic!("Not a QuantAMM pool"),
+        },
+        PoolStateOrBuffer::Buffer(_) => panic!("Not a QuantAMM pool"),
+    }
+}
+
+#[test]
+fn test_quantamm_weights_at() {
+    let state = find_quantamm();
+    let mutable = &state.mutable;
+    let pool = QuantAmmPool::new(state.clone()).unwrap();
+
+    assert_eq!(
+        pool.weights_at(&mutable.current_timestamp).unwrap(),
+        pool.normalized_weights()
+    );
+    let (base_weights, _) = get_first_four_weights_and_multipliers(
+        &state.base.tokens,
+        &mutable.first_four_weights_and_multipliers,
+    );
+    let at_update = pool.weights_at(&mutable.last_update_time).unwrap();
+    for (weight, base_weight) in at_update.iter().zip(&base_weights) {
+        assert_eq!(*weight, base_weight.into_raw());
+    }
+
+    // Weights move linearly until the last interpolation time, then hold
+    let duration = mutable.last_interop_time - mutable.last_update_time;
+    let timestamps: Vec<U256> = (0..=4)
+        .map(|step| mutable.last_update_time + duration * U256::from(step) / U256::from(4))
+        .collect();
+    let trajectory = pool.weight_trajectory(&timestamps).unwrap();
+    for window in trajectory.windows(2) {
+        assert!(window[1][0] > window[0][0]);
+        assert_eq!(window[1][1], window[0][1]);
+        assert!(window[1][2] < window[0][2]);
+    }
+    let at_end = &trajectory[4];
+    assert_eq!(
+        &pool
+            .weights_at(&(mutable.last_interop_time + U256::from(DAY)))
+            .unwrap(),
+        at_end
+    );
+
+    assert!(matches!(
+        pool.weights_at(&(mutable.last_update_time - U256::from(1))),
+        Err(PoolError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn test_quantamm_apply_weight_update() {
+    let vault = Vault::new();
+    let state = find_quantamm();
+    let now = state.mutable.current_timestamp;
+    let pool = QuantAmmPool::new(state.clone()).unwrap();
+    let current_weights = pool.normalized_weights().to_vec();
+
+    let target_weights = vec![
+        U256::from(600_000_000_000_000_000u128),
+        U256::from(100_000_000_000_000_000u128),
+        U256::from(300_000_000_000_000_000u128),
+    ];
+    let update_interval = U256::from(DAY);
+    let guard_rail = U256::from(WAD / 100);
+    let updated = pool
+        .apply_weight_update(&target_weights, &update_interval, &guard_rail)
+        .unwrap();
+
+    assert_eq!(updated.mutable.last_update_time, now);
+    assert_eq!(updated.mutable.first_four_weights_and_multipliers.len(), 8);
+    assert_eq!(
+        updated.mutable.second_four_weights_and_multipliers,
+        vec![I256::ZERO; 8]
+    );
+    let (_, multipliers) = get_first_four_weights_and_multipliers(
+        &updated.base.tokens,
+        &updated.mutable.first_four_weights_and_multipliers,
+    );
+    for (multiplier, (current, target)) in multipliers
+        .iter()
+        .zip(current_weights.iter().zip(&target_weights))
+    {
+        // Packed with 9 decimals, and moving towards the target
+        assert!((*multiplier % I256::from_raw(U256::from(1_000_000_000u64))).is_zero());
+        assert_eq!(multiplier.is_negative(), target < current);
+    }
+
+    // The weights start from the current weights and reach the target after the interval, up
+    // to the packed precision
+    let updated_pool = QuantAmmPool::new(updated.clone()).unwrap();
+    let tolerance = (update_interval + U256::from(1)) * U256::from(1_000_000_000u64);
+    for (weight, current) in updated_pool
+        .normalized_weights()
+        .iter()
+        .zip(&current_weights)
+    {
+        assert!(weight.abs_diff(*current) < U256::from(1_000_000_000u64));
+    }
+    let at_target = updated_pool.weights_at(&(now + update_interval)).unwrap();
+    for (weight, target) in at_target.iter().zip(&target_weights) {
+        assert!(weight.abs_diff(*target) <= tolerance);
+    }
+
+    // Interpolation continues past the target and stops before a weight crosses a guard rail
+    let last_interop_time = updated.mutable.last_interop_time;
+    assert!(last_interop_time > now + update_interval);
+    let upper_guard_rail = U256::from(WAD) - guard_rail * U256::from(2);
+    let at_last = updated_pool.weights_at(&last_interop_time).unwrap();
+    assert!(at_last
+        .iter()
+        .all(|weight| *weight >= guard_rail && *weight <= upper_guard_rail));
+    let unclamped = QuantAmmPool::new(QuantAmmState {
+        mutable: QuantAmmMutable {
+            last_interop_time: U256::MAX,
+            ..updated.mutable.clone()
+        },
+        ..updated.clone()
+    })
+    .unwrap()
+    .weights_at(&(last_interop_time + U256::from(1)))
+    .unwrap();
+    assert!(unclamped
+        .iter()
+        .any(|weight| *weight < guard_rail || *weight > upper_guard_rail));
+
+    // The updated state quotes swaps at the new weights
+    let swap_input = SwapInput {
+        swap_kind: SwapKind::GivenIn,
+        amount_raw: U256::from(100_000u64),
+        token_in: state.base.tokens[0].clone(),
+        token_out: state.base.tokens[1].clone(),
+    };
+    let later = QuantAmmState {
+        mutable: QuantAmmMutable {
+            current_timestamp: now + update_interval,
+            ..updated.mutable.clone()
+        },
+        ..updated
+    };
+    let before_update = vault
+        .swap(
+            &swap_input,
+            &PoolStateOrBuffer::Pool(Box::new(PoolState::QuantAmm(state))),
+            None,
+        )
+        .unwrap();
+    let after_update = vault
+        .swap(
+            &swap_input,
+            &PoolStateOrBuffer::Pool(Box::new(PoolState::QuantAmm(later))),
+            None,
+        )
+        .unwrap();
+    // Token 0 loses weight and token 1 gains it, so token 0 buys less of token 1
+    assert!(target_weights[0] < current_weights[0]);
+    assert!(after_update < before_update);
+}
+
+#[test]
+fn test_quantamm_apply_weight_update_invalid() {
+    let pool = QuantAmmPool::new(find_quantamm()).unwrap();
+    let day = U256::from(DAY);
+    let guard_rail = U256::from(WAD / 100);
+    let third = U256::from(WAD / 3);
+    let balanced = vec![third, third, U256::from(WAD) - third * U256::from(2)];
+
+    for (target_weights, update_interval, guard_rail) in [
+        (vec![third, third], day, guard_rail),
+        (vec![third, third, third], day, guard_rail),
+        (balanced.clone(), U256::ZERO, guard_rail),
+        (balanced.clone(), day, U256::from(WAD / 2)),
+        (
+            vec![
+                U256::from(WAD / 200),
+                third,
+                U256::from(WAD) - third - U256::from(WAD / 200),
+            ],
+            day,
+            guard_rail,
+        ),
+    ] {
+        assert!(matches!(
+            pool.apply_weight_update(&target_weights, &update_interval, &guard_rail),
+            Err(PoolError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,193 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::common::utils::copy_to_scaled18_apply_rate_round_down_array;
+use balancer_maths_rust::pools::reclamm::ReClammStatus;
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::{find_pool, reclamm_status};
+
+const WAD: u128 = 1_000_000_000_000_000_000;
+
+const RECLAMM_POOLS: [&str; 4] = [
+    "8453-31094200-ReClamm-WETH-USDC-In-Range.json",
+    "8453-31094381-ReClamm-WETH-USDC-Out-Of-Range.json",
+    "8453-32632378-ReClamm-V2.json",
+    "8453-32641745-ReClamm-V2-Out-Of-Range.json",
+];
+
+/// Apply a liquidity operation to the pool state, as the next block would see it
+fn apply(
+    pool_state: &PoolState,
+    balance_deltas_raw: &[U256],
+    is_add: bool,
+    bpt_amount: &U256,
+    update: Option<&VirtualBalancesUpdate>,
+) -> PoolState {
+    let mut pool_state = pool_state.clone();
+    let base = pool_state.base_mut();
+    let balance_deltas = copy_to_scaled18_apply_rate_round_down_array(
+        balance_deltas_raw,
+        &base.scaling_factors,
+        &base.token_rates,
+    )
+    .unwrap();
+    for (balance, delta) in base.balances_live_scaled_18.iter_mut().zip(balance_deltas) {
+        if is_add {
+            *balance += delta;
+        } else {
+            *balance -= delta;
+        }
+    }
+    if is_add {
+        base.total_supply += bpt_amount;
+    } else {
+        base.total_supply -= bpt_amount;
+    }
+
+    if let Some(update) = update {
+        match &mut pool_state {
+            PoolState::ReClamm(state) => {
+                state.mutable.last_virtual_balances = update.virtual_balances.clone();
+                state.mutable.last_timestamp = update.last_timestamp;
+            }
+            PoolState::ReClammV2(state) => {
+                state.mutable.last_virtual_balances = update.virtual_balances.clone();
+                state.mutable.last_timestamp = update.last_timestamp;
+            }
+            _ => unreachable!(),
+        }
+    }
+    pool_state
+}
+
+/// Kept up to the rounding of raw amounts, which have 6 decimals for USDC and matter for the
+/// small balances of some test pools
+fn assert_price_kept(before: &ReClammStatus, after: &ReClammStatus, name: &str) {
+    assert!(
+        after.spot_price.abs_diff(before.spot_price)
+            <= before.spot_price / U256::from(1_000_000u64),
+        "Price moved for {}: {} vs {}",
+        name,
+        after.spot_price,
+        before.spot_price
+    );
+    assert!(
+        after.centeredness.abs_diff(before.centeredness)
+            <= before.centeredness / U256::from(100_000),
+        "Centeredness moved for {}",
+        name
+    );
+}
+
+#[test]
+fn test_reclamm_proportional_liquidity_updates_virtual_balances() {
+    let vault = Vault::new();
+
+    for name in RECLAMM_POOLS {
+        let pool_state = find_pool(name);
+        let base = pool_state.base().clone();
+        let before = reclamm_status(&pool_state);
+        let bpt_amount = base.total_supply / U256::from(10);
+        let wad = U256::from(WAD);
+        // Proportion of the supply, rounded to shrink the virtual balances
+        let add_proportion = bpt_amount * wad / base.total_supply;
+        let remove_proportion = (bpt_amount * wad).div_ceil(base.total_supply);
+
+        let add = vault
+            .add_liquidity(
+                &AddLiquidityInput {
+                    pool: base.pool_address.clone(),
+                    max_amounts_in_raw: vec![U256::MAX; base.tokens.len()],
+                    min_bpt_amount_out_raw: bpt_amount,
+                    kind: AddLiquidityKind::Proportional,
+                },
+                &pool_state,
+                None,
+            )
+            .unwrap();
+        let add_update = add.virtual_balances_update.clone().unwrap();
+        assert_eq!(add_update.last_timestamp, before.timestamp);
+        for (updated, current) in add_update
+            .virtual_balances
+            .iter()
+            .zip(&before.virtual_balances)
+        {
+            assert_eq!(*updated, current * (wad + add_proportion) / wad);
+        }
+
+        let remove = vault
+            .remove_liquidity(
+                &RemoveLiquidityInput {
+                    pool: base.pool_address.clone(),
+                    min_amounts_out_raw: vec![U256::ZERO; base.tokens.len()],
+                    max_bpt_amount_in_raw: bpt_amount,
+                    kind: RemoveLiquidityKind::Proportional,
+                },
+                &pool_state,
+                None,
+            )
+            .unwrap();
+        let remove_update = remove.virtual_balances_update.clone().unwrap();
+        assert_eq!(remove_update.last_timestamp, before.timestamp);
+        for (updated, current) in remove_update
+            .virtual_balances
+            .iter()
+            .zip(&before.virtual_balances)
+        {
+            assert_eq!(*updated, current * (wad - remove_proportion) / wad);
+        }
+
+        // Chained, the pool keeps its price and centeredness
+        let after_add = apply(
+            &pool_state,
+            &add.amounts_in_raw,
+            true,
+            &bpt_amount,
+            Some(&add_update),
+        );
+        assert_price_kept(&before, &reclamm_status(&after_add), name);
+        let after_remove = apply(
+            &pool_state,
+            &remove.amounts_out_raw,
+            false,
+            &bpt_amount,
+            Some(&remove_update),
+        );
+        assert_price_kept(&before, &reclamm_status(&after_remove), name);
+
+        // Without the update, the stale virtual balances move the price range
+        let stale = reclamm_status(&apply(
+            &pool_state,
+            &add.amounts_in_raw,
+            true,
+            &bpt_amount,
+            None,
+        ));
+        assert!(
+            stale.min_price.abs_diff(before.min_price) > before.min_price / U256::from(1_000),
+            "Stale price range for {}",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_other_pools_have_no_virtual_balances_update() {
+    let vault = Vault::new();
+    let pool_state = find_pool("11155111-7439300-Weighted-USDC-DAI.json");
+    let base = pool_state.base().clone();
+
+    let add = vault
+        .add_liquidity(
+            &AddLiquidityInput {
+                pool: base.pool_address.clone(),
+                max_amounts_in_raw: vec![U256::MAX; base.tokens.len()],
+                min_bpt_amount_out_raw: base.total_supply / U256::from(10),
+                kind: AddLiquidityKind::Proportional,
+            },
+            &pool_state,
+            None,
+        )
+        .unwrap();
+    assert!(add.virtual_balances_update.is_none());
+}
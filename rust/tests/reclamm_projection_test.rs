@@ -0,0 +1,105 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::errors::PoolError;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::pools::reclamm::{ReClammPool, ReClammStatus};
+use balancer_maths_rust::pools::reclammv2::ReClammV2Pool;
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::{find_pool, reclamm_status};
+
+const HOUR: u64 = 3_600;
+
+fn current_timestamp(pool_state: &PoolState) -> U256 {
+    match pool_state {
+        PoolState::ReClamm(state) => state.mutable.current_timestamp,
+        PoolState::ReClammV2(state) => state.mutable.current_timestamp,
+        _ => panic!("Not a ReClamm pool"),
+    }
+}
+
+fn with_timestamp(pool_state: &PoolState, timestamp: U256) -> PoolState {
+    let mut pool_state = pool_state.clone();
+    match &mut pool_state {
+        PoolState::ReClamm(state) => state.mutable.current_timestamp = timestamp,
+        PoolState::ReClammV2(state) => state.mutable.current_timestamp = timestamp,
+        _ => panic!("Not a ReClamm pool"),
+    }
+    pool_state
+}
+
+fn project(pool_state: &PoolState, timestamps: &[U256]) -> Result<Vec<ReClammStatus>, PoolError> {
+    match pool_state {
+        PoolState::ReClamm(state) => ReClammPool::new(state.clone()).project(timestamps),
+        PoolState::ReClammV2(state) => ReClammV2Pool::new(state.clone()).project(timestamps),
+        _ => panic!("Not a ReClamm pool"),
+    }
+}
+
+#[test]
+fn test_out_of_range_pools_drift_back_into_range() {
+    let vault = Vault::new();
+
+    for name in [
+        "8453-31094381-ReClamm-WETH-USDC-Out-Of-Range.json",
+        "8453-32641745-ReClamm-V2-Out-Of-Range.json",
+    ] {
+        let pool_state = find_pool(name);
+        let tokens = &pool_state.base().tokens;
+        let now = current_timestamp(&pool_state);
+        let timestamps: Vec<U256> = (0..=30 * 24)
+            .map(|hours| now + U256::from(hours * HOUR))
+            .collect();
+
+        let projection = project(&pool_state, &timestamps).unwrap();
+        assert_eq!(projection.len(), timestamps.len());
+        assert_eq!(projection[0], reclamm_status(&pool_state));
+        assert!(!projection[0].is_pool_in_range);
+
+        // Each projected status is the status of the pool at that timestamp, and its marginal
+        // price is what a swap would see
+        for (timestamp, projected) in timestamps.iter().zip(&projection).step_by(24) {
+            assert_eq!(projected.timestamp, *timestamp);
+            let pool_state_at = with_timestamp(&pool_state, *timestamp);
+            assert_eq!(*projected, reclamm_status(&pool_state_at));
+            assert_eq!(
+                projected.spot_price,
+                vault
+                    .spot_price(&pool_state_at, &tokens[0], &tokens[1])
+                    .unwrap()
+            );
+        }
+
+        // The range tracks the market price, so the centeredness rises until the pool is in range
+        let reentry = projection
+            .iter()
+            .position(|status| status.is_pool_in_range)
+            .unwrap_or_else(|| panic!("{} does not re-enter range", name));
+        assert!(reentry > 0);
+        for window in projection[..=reentry].windows(2) {
+            assert!(window[1].centeredness > window[0].centeredness);
+            assert_ne!(window[1].virtual_balances, window[0].virtual_balances);
+        }
+    }
+}
+
+#[test]
+fn test_in_range_pool_does_not_drift() {
+    for name in [
+        "8453-31094200-ReClamm-WETH-USDC-In-Range.json",
+        "8453-32632378-ReClamm-V2.json",
+    ] {
+        let pool_state = find_pool(name);
+        let now = current_timestamp(&pool_state);
+        let current = reclamm_status(&pool_state);
+
+        let projection = project(&pool_state, &[now + U256::from(7 * 24 * HOUR)]).unwrap();
+        assert!(projection[0].is_pool_in_range);
+        assert_eq!(projection[0].virtual_balances, current.virtual_balances);
+        assert_eq!(projection[0].spot_price, current.spot_price);
+
+        assert!(matches!(
+            project(&pool_state, &[U256::ZERO]),
+            Err(PoolError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,146 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::pools::reclamm::ReClammPool;
+use balancer_maths_rust::pools::reclammv2::ReClammV2Pool;
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::{find_pool, reclamm_status};
+
+const WAD: u128 = 1_000_000_000_000_000_000;
+
+fn current_virtual_balances(pool_state: &PoolState) -> Vec<U256> {
+    let balances = &pool_state.base().balances_live_scaled_18;
+    let (virtual_balance_a, virtual_balance_b, _) = match pool_state {
+        PoolState::ReClamm(state) => {
+            ReClammPool::new(state.clone()).current_virtual_balances(balances)
+        }
+        PoolState::ReClammV2(state) => {
+            ReClammV2Pool::new(state.clone()).current_virtual_balances(balances)
+        }
+        _ => panic!("Not a ReClamm pool"),
+    };
+    vec![virtual_balance_a, virtual_balance_b]
+}
+
+#[test]
+fn test_reclamm_status() {
+    let vault = Vault::new();
+    let pools = [
+        ("8453-31094200-ReClamm-WETH-USDC-In-Range.json", true),
+        ("8453-31094381-ReClamm-WETH-USDC-Out-Of-Range.json", false),
+        ("8453-32632378-ReClamm-V2.json", true),
+        ("8453-32641745-ReClamm-V2-Out-Of-Range.json", false),
+    ];
+
+    for (name, is_in_range) in pools {
+        let pool_state = find_pool(name);
+        let status = reclamm_status(&pool_state);
+        let virtual_balances = current_virtual_balances(&pool_state);
+        let tokens = &pool_state.base().tokens;
+
+        assert_eq!(status.virtual_balances, virtual_balances);
+        assert_eq!(
+            status.is_pool_in_range, is_in_range,
+            "Range mismatch for {}",
+            name
+        );
+        assert_eq!(
+            status.is_pool_in_range,
+            status.centeredness >= status.centeredness_margin
+        );
+        assert!(status.centeredness <= U256::from(WAD));
+        assert!(!status.is_price_ratio_updating);
+
+        let spot_price = vault
+            .spot_price(&pool_state, &tokens[0], &tokens[1])
+            .unwrap();
+        assert_eq!(status.spot_price, spot_price);
+        assert!(status.min_price < status.spot_price);
+        assert!(status.spot_price < status.max_price);
+
+        // The target price is the center of the range, the min price times the square root of
+        // the price ratio, and the pool holds more token A when its price is below the target
+        assert_eq!(
+            status.target_price,
+            (status.min_price * status.max_price).root(2)
+        );
+        let center_price = status.min_price * status.fourth_root_price_ratio / U256::from(WAD)
+            * status.fourth_root_price_ratio
+            / U256::from(WAD);
+        assert!(
+            status.target_price.abs_diff(center_price) <= center_price / U256::from(10_000),
+            "Target price mismatch for {}: {} vs {}",
+            name,
+            status.target_price,
+            center_price
+        );
+        assert!(status.min_price < status.target_price);
+        assert!(status.target_price < status.max_price);
+        assert_eq!(
+            status.is_pool_above_center,
+            status.spot_price < status.target_price
+        );
+
+        // The price range spans the price ratio, the fourth root raised to the fourth power
+        let sqrt_price_ratio =
+            status.fourth_root_price_ratio * status.fourth_root_price_ratio / U256::from(WAD);
+        let price_ratio = sqrt_price_ratio * sqrt_price_ratio / U256::from(WAD);
+        let range_ratio = status.max_price * U256::from(WAD) / status.min_price;
+        assert!(
+            range_ratio.abs_diff(price_ratio) <= price_ratio / U256::from(1_000),
+            "Price ratio mismatch for {}: {} vs {}",
+            name,
+            range_ratio,
+            price_ratio
+        );
+    }
+}
+
+#[test]
+fn test_reclamm_status_price_ratio_update() {
+    for name in [
+        "8453-31094200-ReClamm-WETH-USDC-In-Range.json",
+        "8453-32632378-ReClamm-V2.json",
+    ] {
+        let mut pool_state = find_pool(name);
+        let before = reclamm_status(&pool_state);
+
+        // Doubling the current price ratio over a day, halfway through
+        let end_fourth_root_price_ratio = match &mut pool_state {
+            PoolState::ReClamm(state) => {
+                let mutable = &mut state.mutable;
+                mutable.start_fourth_root_price_ratio = before.fourth_root_price_ratio;
+                mutable.end_fourth_root_price_ratio = before.fourth_root_price_ratio
+                    * U256::from(1_189_207_115_002_721_066u128)
+                    / U256::from(WAD);
+                mutable.price_ratio_update_start_time =
+                    mutable.current_timestamp - U256::from(43_200);
+                mutable.price_ratio_update_end_time =
+                    mutable.current_timestamp + U256::from(43_200);
+                mutable.end_fourth_root_price_ratio
+            }
+            PoolState::ReClammV2(state) => {
+                let mutable = &mut state.mutable;
+                mutable.start_fourth_root_price_ratio = before.fourth_root_price_ratio;
+                mutable.end_fourth_root_price_ratio = before.fourth_root_price_ratio
+                    * U256::from(1_189_207_115_002_721_066u128)
+                    / U256::from(WAD);
+                mutable.price_ratio_update_start_time =
+                    mutable.current_timestamp - U256::from(43_200);
+                mutable.price_ratio_update_end_time =
+                    mutable.current_timestamp + U256::from(43_200);
+                mutable.end_fourth_root_price_ratio
+            }
+            _ => unreachable!(),
+        };
+
+        let updating = reclamm_status(&pool_state);
+        assert!(updating.is_price_ratio_updating);
+        assert!(updating.fourth_root_price_ratio > before.fourth_root_price_ratio);
+        assert!(updating.fourth_root_price_ratio < end_fourth_root_price_ratio);
+        assert!(
+            updating.max_price * U256::from(WAD) / updating.min_price
+                > before.max_price * U256::from(WAD) / before.min_price
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::errors::PoolError;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::common::utils::to_raw_undo_rate_round_down;
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::find_pool;
+
+#[test]
+fn test_paused_pool_reverts() {
+    let vault = Vault::new();
+    let mut pool_state = find_pool("11155111-7439300-Weighted-USDC-DAI.json");
+    pool_state.base_mut().is_pool_paused = true;
+    let base = pool_state.base().clone();
+    let token_count = base.tokens.len();
+
+    let swap_input = SwapInput {
+        amount_raw: U256::from(1_000_000u64),
+        swap_kind: SwapKind::GivenIn,
+        token_in: base.tokens[0].clone(),
+        token_out: base.tokens[1].clone(),
+    };
+    let pool = PoolStateOrBuffer::Pool(Box::new(pool_state.clone()));
+    assert_eq!(
+        vault.swap(&swap_input, &pool, None).unwrap_err(),
+        PoolError::PoolPaused
+    );
+    assert_eq!(
+        vault
+            .swap_ladder(
+                &pool,
+                &base.tokens[0],
+                &base.tokens[1],
+                SwapKind::GivenIn,
+                &[U256::from(1_000_000u64)],
+                None
+            )
+            .unwrap_err(),
+        PoolError::PoolPaused
+    );
+    assert_eq!(
+        vault.swap_approx(&swap_input, &pool).unwrap_err(),
+        PoolError::PoolPaused
+    );
+
+    for kind in [AddLiquidityKind::Unbalanced, AddLiquidityKind::Proportional] {
+        let add_liquidity_input = AddLiquidityInput {
+            pool: base.pool_address.clone(),
+            max_amounts_in_raw: vec![U256::from(1_000_000u64); token_count],
+            min_bpt_amount_out_raw: U256::from(1_000_000u64),
+            kind,
+        };
+        assert_eq!(
+            vault
+                .add_liquidity(&add_liquidity_input, &pool_state, None)
+                .unwrap_err(),
+            PoolError::PoolPaused
+        );
+    }
+
+    let remove_liquidity_input = RemoveLiquidityInput {
+        pool: base.pool_address.clone(),
+        min_amounts_out_raw: vec![U256::ZERO; token_count],
+        max_bpt_amount_in_raw: U256::from(1_000_000u64),
+        kind: RemoveLiquidityKind::Proportional,
+    };
+    assert_eq!(
+        vault
+            .remove_liquidity(&remove_liquidity_input, &pool_state, None)
+            .unwrap_err(),
+        PoolError::PoolPaused
+    );
+
+    // Prices can still be read
+    assert!(vault
+        .spot_price(&pool_state, &base.tokens[0], &base.tokens[1])
+        .is_ok());
+}
+
+#[test]
+fn test_remove_liquidity_recovery() {
+    let vault = Vault::new();
+    let pool_state = find_pool("11155111-7439300-Weighted-USDC-DAI.json");
+    let base = pool_state.base().clone();
+    let bpt_amount_in_raw = base.total_supply / U256::from(10);
+
+    assert_eq!(
+        vault
+            .remove_liquidity_recovery(&pool_state, &bpt_amount_in_raw)
+            .unwrap_err(),
+        PoolError::PoolNotInRecoveryMode
+    );
+
+    // Recovery exits are allowed while paused
+    let mut recovery_state = pool_state.clone();
+    recovery_state.base_mut().is_pool_in_recovery_mode = true;
+    recovery_state.base_mut().is_pool_paused = true;
+    let result = vault
+        .remove_liquidity_recovery(&recovery_state, &bpt_amount_in_raw)
+        .unwrap();
+    assert_eq!(result.bpt_amount_in_raw, bpt_amount_in_raw);
+
+    // Cannot burn more BPT than exists
+    assert!(matches!(
+        vault
+            .remove_liquidity_recovery(&recovery_state, &(base.total_supply + U256::ONE))
+            .unwrap_err(),
+        PoolError::InvalidInput(_)
+    ));
+
+    for (i, amount_out_raw) in result.amounts_out_raw.iter().enumerate() {
+        let balance_raw = to_raw_undo_rate_round_down(
+            &base.balances_live_scaled_18[i],
+            &base.scaling_factors[i],
+            &base.token_rates[i],
+        )
+        .unwrap();
+        assert_eq!(
+            *amount_out_raw,
+            balance_raw * bpt_amount_in_raw / base.total_supply
+        );
+    }
+
+    // Matches a regular proportional exit, up to raw rounding
+    let regular = vault
+        .remove_liquidity(
+            &RemoveLiquidityInput {
+                pool: base.pool_address.clone(),
+                min_amounts_out_raw: vec![U256::ZERO; base.tokens.len()],
+                max_bpt_amount_in_raw: bpt_amount_in_raw,
+                kind: RemoveLiquidityKind::Proportional,
+            },
+            &pool_state,
+            None,
+        )
+        .unwrap();
+    for (recovery_amount, regular_amount) in
+        result.amounts_out_raw.iter().zip(&regular.amounts_out_raw)
+    {
+        assert!(recovery_amount.abs_diff(*regular_amount) <= U256::ONE);
+    }
+
+    // Hooks are bypassed, so no hook state is needed for hooked pools
+    let mut hooked_state = find_pool("1-22247251-StableSurgeHook.json");
+    assert!(hooked_state.base().hook_type.is_some());
+    hooked_state.base_mut().is_pool_in_recovery_mode = true;
+    let hooked_bpt_amount_in_raw = hooked_state.base().total_supply / U256::from(10);
+    let hooked = vault
+        .remove_liquidity_recovery(&hooked_state, &hooked_bpt_amount_in_raw)
+        .unwrap();
+    assert!(hooked
+        .amounts_out_raw
+        .iter()
+        .all(|amount| !amount.is_zero()));
+}
+
+#[test]
+fn test_recovery_mode_skips_aggregate_fees() {
+    let vault = Vault::new();
+    let pool_state = find_pool("1-23511249-GyroECLP-Barter.json");
+    let base = pool_state.base().clone();
+    assert!(!base.aggregate_swap_fee.is_zero());
+
+    let exact_amount_in_raw = to_raw_undo_rate_round_down(
+        &(base.balances_live_scaled_18[1] / U256::from(100)),
+        &base.scaling_factors[1],
+        &base.token_rates[1],
+    )
+    .unwrap();
+    let add = |pool_state: &PoolState| {
+        vault
+            .add_liquidity_single_token_exact_in(
+                pool_state,
+                &base.tokens[1],
+                &exact_amount_in_raw,
+                SingleTokenExactInMethod::Unbalanced,
+                None,
+            )
+            .unwrap()
+    };
+
+    let regular = add(&pool_state);
+    assert!(!regular.aggregate_swap_fee_amount_raw.is_zero());
+
+    let mut recovery_state = pool_state.clone();
+    recovery_state.base_mut().is_pool_in_recovery_mode = true;
+    let recovery = add(&recovery_state);
+    assert_eq!(recovery.aggregate_swap_fee_amount_raw, U256::ZERO);
+    assert_eq!(recovery.bpt_amount_out_raw, regular.bpt_amount_out_raw);
+    assert_eq!(recovery.swap_fee_amount_raw, regular.swap_fee_amount_raw);
+}
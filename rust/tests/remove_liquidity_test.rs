@@ -1,9 +1,16 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::pool_base::PoolBase;
 use balancer_maths_rust::common::types::PoolStateOrBuffer;
 use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::pools::weighted::WeightedPool;
+use balancer_maths_rust::vault::base_pool_math::{
+    compute_remove_liquidity_single_token_exact_out, compute_remove_liquidity_unbalanced,
+};
 use balancer_maths_rust::vault::Vault;
 mod utils;
+use std::str::FromStr;
 use utils::read_test_data;
-use utils::{convert_to_pool_state, get_pool_address};
+use utils::{convert_to_pool_state, find_pool, get_pool_address};
 
 #[test]
 fn test_remove_liquidity() {
@@ -61,3 +68,218 @@ fn test_remove_liquidity() {
         assert_eq!(result.amounts_out_raw, remove_test.amounts_out_raw);
     }
 }
+
+/// Remove 1% of the BPT of a test pool proportionally, then remove its first two amounts out and
+/// all of its amounts out with `Custom`, returning the BPT in of each
+fn remove_custom(name: &str) -> [U256; 3] {
+    let vault = Vault::new();
+    let pool_state = find_pool(name);
+    let base = pool_state.base();
+    let num_tokens = base.tokens.len();
+    let remove = |min_amounts_out_raw: Vec<U256>, max_bpt_amount_in_raw, kind| {
+        vault.remove_liquidity(
+            &RemoveLiquidityInput {
+                pool: base.pool_address.clone(),
+                min_amounts_out_raw,
+                max_bpt_amount_in_raw,
+                kind,
+            },
+            &pool_state,
+            None,
+        )
+    };
+
+    let proportional = remove(
+        vec![U256::ZERO; num_tokens],
+        base.total_supply / U256::from(100),
+        RemoveLiquidityKind::Proportional,
+    )
+    .unwrap();
+
+    let mut pair_amounts_out_raw = vec![U256::ZERO; num_tokens];
+    pair_amounts_out_raw[0] = proportional.amounts_out_raw[0];
+    pair_amounts_out_raw[1] = proportional.amounts_out_raw[1];
+    let pair = remove(
+        pair_amounts_out_raw.clone(),
+        U256::ZERO,
+        RemoveLiquidityKind::Custom,
+    )
+    .unwrap();
+    assert_eq!(pair.amounts_out_raw, pair_amounts_out_raw);
+
+    // Removing the second token on top of the first burns more BPT
+    let mut single_amounts_out_raw = vec![U256::ZERO; num_tokens];
+    single_amounts_out_raw[0] = proportional.amounts_out_raw[0];
+    let single = remove(
+        single_amounts_out_raw,
+        U256::ZERO,
+        RemoveLiquidityKind::Custom,
+    )
+    .unwrap();
+    assert!(pair.bpt_amount_in_raw > single.bpt_amount_in_raw);
+
+    // Proportional amounts out cost about the proportional BPT. Raw amounts out are rounded
+    // down, and the interpolated QuantAMM weights do not sum exactly to one, which adds up
+    // to 1e-4.
+    let custom = remove(
+        proportional.amounts_out_raw.clone(),
+        U256::ZERO,
+        RemoveLiquidityKind::Custom,
+    )
+    .unwrap();
+    assert_eq!(custom.amounts_out_raw, proportional.amounts_out_raw);
+    assert!(
+        custom
+            .bpt_amount_in_raw
+            .abs_diff(proportional.bpt_amount_in_raw)
+            <= proportional.bpt_amount_in_raw / U256::from(10_000u64),
+        "Custom remove mismatch for {}: {} vs {}",
+        name,
+        custom.bpt_amount_in_raw,
+        proportional.bpt_amount_in_raw
+    );
+
+    // Removing more than the pool holds fails
+    let mut too_much = vec![U256::ZERO; num_tokens];
+    too_much[num_tokens - 1] = U256::MAX / U256::from(10).pow(U256::from(40));
+    assert!(remove(too_much, U256::ZERO, RemoveLiquidityKind::Custom).is_err());
+
+    [
+        pair.bpt_amount_in_raw,
+        custom.bpt_amount_in_raw,
+        proportional.bpt_amount_in_raw,
+    ]
+}
+
+fn assert_remove_custom(name: &str, expected: [&str; 3]) {
+    assert_eq!(
+        remove_custom(name),
+        expected.map(|value| U256::from_str(value).unwrap()),
+        "BPT in mismatch for {}",
+        name
+    );
+}
+
+#[test]
+fn test_remove_liquidity_custom_weighted() {
+    assert_remove_custom(
+        "11155111-7439300-Weighted-USDC-DAI.json",
+        [
+            "65651474864039129671",
+            "65651474864039129671",
+            "65651475175438636494",
+        ],
+    );
+}
+
+#[test]
+fn test_remove_liquidity_custom_stable() {
+    assert_remove_custom(
+        "11155111-7439300-Stable-stataUSDC-stataUSDT.json",
+        [
+            "987223632801890647266",
+            "987223632801890647266",
+            "987223634533874639624",
+        ],
+    );
+}
+
+#[test]
+fn test_remove_liquidity_custom_gyro_eclp() {
+    assert_remove_custom(
+        "11155111-7748718-GyroECLP.json",
+        ["5357408085458", "5357408085458", "5357408085454"],
+    );
+}
+
+#[test]
+fn test_remove_liquidity_custom_quantamm() {
+    assert_remove_custom(
+        "1-22524240-QuantAMM.json",
+        [
+            "63613062160897290",
+            "89363045412148705",
+            "89355475423871771",
+        ],
+    );
+}
+
+#[test]
+fn test_remove_liquidity_custom_single_token() {
+    let vault = Vault::new();
+
+    for name in [
+        "11155111-7439300-Weighted-USDC-DAI.json",
+        "11155111-7439300-Stable-stataUSDC-stataUSDT.json",
+    ] {
+        let pool_state = find_pool(name);
+        let base = pool_state.base();
+        let remove = |amount_out_raw: U256, kind| {
+            let mut min_amounts_out_raw = vec![U256::ZERO; base.tokens.len()];
+            min_amounts_out_raw[1] = amount_out_raw;
+            vault
+                .remove_liquidity(
+                    &RemoveLiquidityInput {
+                        pool: base.pool_address.clone(),
+                        min_amounts_out_raw,
+                        max_bpt_amount_in_raw: U256::ZERO,
+                        kind,
+                    },
+                    &pool_state,
+                    None,
+                )
+                .unwrap()
+        };
+
+        // Tokens that are not removed are not charged a fee, even when the invariant ratio
+        // rounds up to one on a tiny exit
+        for amount_out_raw in [U256::ONE, U256::from(1_000u64), U256::from(1_000_000u64)] {
+            let custom = remove(amount_out_raw, RemoveLiquidityKind::Custom);
+            let single = remove(amount_out_raw, RemoveLiquidityKind::SingleTokenExactOut);
+            assert_eq!(
+                custom.bpt_amount_in_raw, single.bpt_amount_in_raw,
+                "BPT in mismatch for {} removing {}",
+                name, amount_out_raw
+            );
+            assert_eq!(custom, single);
+        }
+    }
+}
+
+#[test]
+fn test_remove_liquidity_unbalanced_fees_only_on_tokens_out() {
+    let PoolState::Weighted(weighted_state) = find_pool("11155111-7439300-Weighted-USDC-DAI.json")
+    else {
+        panic!("Not a weighted pool");
+    };
+    let pool = WeightedPool::from(weighted_state.clone());
+    let base = &weighted_state.base;
+    let compute_invariant =
+        |balances: &[U256], rounding| pool.compute_invariant(balances, rounding);
+
+    // A 1 wei exit rounds the invariant ratio up to one, so only the token out is taxable
+    let mut exact_amounts_out = vec![U256::ZERO; base.tokens.len()];
+    exact_amounts_out[1] = U256::ONE;
+    let unbalanced = compute_remove_liquidity_unbalanced(
+        &base.balances_live_scaled_18,
+        &exact_amounts_out,
+        &base.total_supply,
+        &base.swap_fee,
+        &pool.get_minimum_invariant_ratio(),
+        &compute_invariant,
+    )
+    .unwrap();
+    let single = compute_remove_liquidity_single_token_exact_out(
+        &base.balances_live_scaled_18,
+        1,
+        &U256::ONE,
+        &base.total_supply,
+        &base.swap_fee,
+        &pool.get_minimum_invariant_ratio(),
+        &compute_invariant,
+    )
+    .unwrap();
+    assert_eq!(unbalanced.swap_fee_amounts[0], U256::ZERO);
+    assert_eq!(unbalanced.swap_fee_amounts, single.swap_fee_amounts);
+    assert_eq!(unbalanced.bpt_amount_in, single.bpt_amount_in);
+}
@@ -0,0 +1,100 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::hooks::types::HookState;
+use balancer_maths_rust::pools::stable::{get_amplification_parameter, AmpUpdate, StableState};
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::convert_to_pool_state;
+use utils::read_test_data;
+
+#[test]
+fn test_get_amplification_parameter() {
+    let start_time = U256::from(1_000);
+    let end_time = U256::from(2_000);
+    let low = U256::from(100_000);
+    let high = U256::from(200_000);
+
+    let amp_at = |current_time: u64, start_value: &U256, end_value: &U256| {
+        get_amplification_parameter(
+            &U256::from(current_time),
+            &start_time,
+            &end_time,
+            start_value,
+            end_value,
+        )
+    };
+
+    // Ramping up
+    assert_eq!(amp_at(500, &low, &high), low);
+    assert_eq!(amp_at(1_000, &low, &high), low);
+    assert_eq!(amp_at(1_250, &low, &high), U256::from(125_000));
+    assert_eq!(amp_at(2_000, &low, &high), high);
+    assert_eq!(amp_at(3_000, &low, &high), high);
+
+    // Ramping down, rounding towards the start value
+    assert_eq!(amp_at(1_333, &high, &low), U256::from(166_700));
+    assert_eq!(amp_at(2_500, &high, &low), low);
+}
+
+#[test]
+fn test_stable_pool_uses_live_amp() {
+    let test_data = read_test_data().expect("Failed to read test data");
+    let vault = Vault::new();
+    let pool_name = "1-22247251-StableSurgeHook.json";
+    let pool_state = match convert_to_pool_state(&test_data.pools[pool_name]) {
+        PoolStateOrBuffer::Pool(pool_state) => *pool_state,
+        PoolStateOrBuffer::Buffer(_) => unreachable!(),
+    };
+    let PoolState::Stable(stable_state) = &pool_state else {
+        panic!("{} is not a stable pool", pool_name);
+    };
+    let Some(HookState::StableSurge(hook_state)) = test_data.hook_state.clone() else {
+        panic!("Missing StableSurge hook state");
+    };
+
+    // Snapshot taken at the start of a ramp to 5x the amp, quoted a quarter of the way in
+    let amp = stable_state.mutable.amp;
+    let amp_update = AmpUpdate {
+        start_value: amp,
+        end_value: amp * U256::from(5),
+        start_time: U256::from(1_000),
+        end_time: U256::from(2_000),
+    };
+    let live_amp = amp * U256::from(2);
+
+    let mut ramping_state = stable_state.clone();
+    ramping_state.mutable.amp_update = Some(amp_update);
+    ramping_state.mutable.current_timestamp = U256::from(1_250);
+    assert_eq!(ramping_state.mutable.amplification_parameter(), live_amp);
+
+    let mut live_state = stable_state.clone();
+    live_state.mutable.amp = live_amp;
+    let mut live_hook_state = hook_state.clone();
+    live_hook_state.amp = live_amp;
+
+    // A large swap, so the StableSurge fee depends on the amp used by the hook
+    let base = &stable_state.base;
+    let swap_input = SwapInput {
+        amount_raw: base.balances_live_scaled_18[0] / base.scaling_factors[0] / U256::from(3),
+        swap_kind: SwapKind::GivenIn,
+        token_in: base.tokens[0].clone(),
+        token_out: base.tokens[1].clone(),
+    };
+    let quote = |stable_state: &StableState, hook_state: &HookState| {
+        vault
+            .swap(
+                &swap_input,
+                &PoolStateOrBuffer::Pool(Box::new(PoolState::Stable(stable_state.clone()))),
+                Some(hook_state),
+            )
+            .unwrap()
+    };
+
+    // The hook follows the pool's ramp, so its own amp is the one at the start of the ramp
+    let ramping_quote = quote(&ramping_state, &HookState::StableSurge(hook_state.clone()));
+    let live_quote = quote(&live_state, &HookState::StableSurge(live_hook_state));
+    assert_eq!(ramping_quote, live_quote);
+
+    let stale_quote = quote(stable_state, &HookState::StableSurge(hook_state));
+    assert_ne!(ramping_quote, stale_quote);
+}
@@ -0,0 +1,204 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::hooks::types::*;
+use balancer_maths_rust::hooks::{DefaultHook, ExitFeeHookState, HookBase, HookConfig};
+use balancer_maths_rust::pools::weighted::{WeightedPool, WeightedState};
+use balancer_maths_rust::vault::swap::swap;
+use std::sync::Mutex;
+
+const WAD: u128 = 1_000_000_000_000_000_000;
+
+/// A hook that records the balances the Vault passes to its after swap call
+struct BalancesRecordingHook {
+    config: HookConfig,
+    default: DefaultHook,
+    balances: Mutex<Option<(U256, U256)>>,
+}
+
+impl BalancesRecordingHook {
+    fn new() -> Self {
+        Self {
+            config: HookConfig {
+                should_call_after_swap: true,
+                ..HookConfig::default()
+            },
+            default: DefaultHook::new(),
+            balances: Mutex::new(None),
+        }
+    }
+}
+
+impl HookBase for BalancesRecordingHook {
+    fn hook_type(&self) -> &str {
+        "BalancesRecording"
+    }
+
+    fn config(&self) -> &HookConfig {
+        &self.config
+    }
+
+    fn on_before_add_liquidity(
+        &self,
+        kind: AddLiquidityKind,
+        max_amounts_in_scaled_18: &[U256],
+        min_bpt_amount_out: &U256,
+        balances_scaled_18: &[U256],
+        hook_state: &HookState,
+    ) -> BeforeAddLiquidityResult {
+        self.default.on_before_add_liquidity(
+            kind,
+            max_amounts_in_scaled_18,
+            min_bpt_amount_out,
+            balances_scaled_18,
+            hook_state,
+        )
+    }
+
+    fn on_after_add_liquidity(
+        &self,
+        kind: AddLiquidityKind,
+        amounts_in_scaled_18: &[U256],
+        amounts_in_raw: &[U256],
+        bpt_amount_out: &U256,
+        balances_scaled_18: &[U256],
+        hook_state: &HookState,
+    ) -> AfterAddLiquidityResult {
+        self.default.on_after_add_liquidity(
+            kind,
+            amounts_in_scaled_18,
+            amounts_in_raw,
+            bpt_amount_out,
+            balances_scaled_18,
+            hook_state,
+        )
+    }
+
+    fn on_before_remove_liquidity(
+        &self,
+        kind: RemoveLiquidityKind,
+        max_bpt_amount_in: &U256,
+        min_amounts_out_scaled_18: &[U256],
+        balances_scaled_18: &[U256],
+        hook_state: &HookState,
+    ) -> BeforeRemoveLiquidityResult {
+        self.default.on_before_remove_liquidity(
+            kind,
+            max_bpt_amount_in,
+            min_amounts_out_scaled_18,
+            balances_scaled_18,
+            hook_state,
+        )
+    }
+
+    fn on_after_remove_liquidity(
+        &self,
+        kind: RemoveLiquidityKind,
+        bpt_amount_in: &U256,
+        amounts_out_scaled_18: &[U256],
+        amounts_out_raw: &[U256],
+        balances_scaled_18: &[U256],
+        hook_state: &HookState,
+    ) -> AfterRemoveLiquidityResult {
+        self.default.on_after_remove_liquidity(
+            kind,
+            bpt_amount_in,
+            amounts_out_scaled_18,
+            amounts_out_raw,
+            balances_scaled_18,
+            hook_state,
+        )
+    }
+
+    fn on_before_swap(&self, swap_params: &SwapParams, hook_state: &HookState) -> BeforeSwapResult {
+        self.default.on_before_swap(swap_params, hook_state)
+    }
+
+    fn on_after_swap(
+        &self,
+        after_swap_params: &AfterSwapParams,
+        hook_state: &HookState,
+    ) -> AfterSwapResult {
+        *self.balances.lock().unwrap() = Some((
+            after_swap_params.token_in_balance_scaled_18,
+            after_swap_params.token_out_balance_scaled_18,
+        ));
+        self.default.on_after_swap(after_swap_params, hook_state)
+    }
+
+    fn on_compute_dynamic_swap_fee(
+        &self,
+        swap_params: &SwapParams,
+        static_swap_fee_percentage: &U256,
+        hook_state: &HookState,
+    ) -> DynamicSwapFeeResult {
+        self.default.on_compute_dynamic_swap_fee(
+            swap_params,
+            static_swap_fee_percentage,
+            hook_state,
+        )
+    }
+}
+
+fn create_pool_state() -> WeightedState {
+    WeightedState {
+        base: BasePoolState {
+            pool_address: "0x03722034317d8fb16845213bd3ce15439f9ce136".to_string(),
+            pool_type: "WEIGHTED".to_string(),
+            tokens: vec![
+                "0x7b79995e5f793A07Bc00c21412e50Ecae098E7f9".to_string(),
+                "0xb19382073c7A0aDdbb56Ac6AF1808Fa49e377B75".to_string(),
+            ],
+            scaling_factors: vec![U256::ONE, U256::ONE],
+            // 10% swap fee, of which half is aggregate fees
+            swap_fee: U256::from(WAD / 10),
+            balances_live_scaled_18: vec![U256::from(5 * WAD), U256::from(5 * WAD)],
+            token_rates: vec![U256::from(WAD), U256::from(WAD)],
+            total_supply: U256::from(5 * WAD),
+            aggregate_swap_fee: U256::from(WAD / 2),
+            supports_unbalanced_liquidity: true,
+            supports_donation: false,
+            hook_type: None,
+            is_pool_paused: false,
+            is_pool_in_recovery_mode: false,
+        },
+        weights: vec![U256::from(WAD / 2), U256::from(WAD / 2)],
+    }
+}
+
+#[test]
+fn test_given_out_swap_balances_keep_swap_fee() {
+    let pool_state = create_pool_state();
+    let balances_before = pool_state.base.balances_live_scaled_18.clone();
+    let hook = BalancesRecordingHook::new();
+    let hook_state = HookState::ExitFee(ExitFeeHookState {
+        hook_type: "ExitFee".to_string(),
+        tokens: pool_state.base.tokens.clone(),
+        remove_liquidity_hook_fee_percentage: U256::ZERO,
+    });
+
+    let amount_out_raw = U256::from(WAD / 10);
+    let amount_in_raw = swap(
+        &SwapInput {
+            swap_kind: SwapKind::GivenOut,
+            amount_raw: amount_out_raw,
+            token_in: pool_state.base.tokens[0].clone(),
+            token_out: pool_state.base.tokens[1].clone(),
+        },
+        &PoolState::Weighted(pool_state.clone()),
+        &WeightedPool::from(pool_state.clone()),
+        &hook,
+        Some(&hook_state),
+    )
+    .unwrap();
+
+    // The amount in, swap fee included, enters the pool, less the aggregate fees taken out of it.
+    // With 18 decimals and unit rates, raw and scaled 18 amounts are the same.
+    let (token_in_balance, token_out_balance) = hook.balances.lock().unwrap().unwrap();
+    let swap_fee = amount_in_raw * U256::from(WAD / 10) / U256::from(WAD);
+    let aggregate_fee = swap_fee / U256::from(2);
+    assert!(
+        (token_in_balance - balances_before[0]).abs_diff(amount_in_raw - aggregate_fee)
+            <= U256::ONE
+    );
+    assert_eq!(token_out_balance, balances_before[1] - amount_out_raw);
+}
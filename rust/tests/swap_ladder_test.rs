@@ -0,0 +1,97 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::errors::PoolError;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use utils::convert_to_pool_state;
+use utils::read_test_data;
+
+#[test]
+fn test_swap_ladder_matches_vault_swaps() {
+    let test_data = read_test_data().expect("Failed to read test data");
+    let vault = Vault::new();
+
+    for swap_test in test_data.swaps {
+        let pool_data = test_data
+            .pools
+            .get(&swap_test.test)
+            .unwrap_or_else(|| panic!("Pool not found for test: {}", swap_test.test));
+        let pool_state_or_buffer = convert_to_pool_state(pool_data);
+
+        let swap_kind = match swap_test.swap_kind {
+            0 => SwapKind::GivenIn,
+            _ => SwapKind::GivenOut,
+        };
+
+        // Ladder includes zero and amounts large enough to hit per-amount errors
+        let amounts_raw = vec![
+            U256::ZERO,
+            swap_test.amount_raw / U256::from(10),
+            swap_test.amount_raw,
+            swap_test.amount_raw * U256::from(3),
+            swap_test.amount_raw * U256::from(1000),
+        ];
+
+        let ladder = vault
+            .swap_ladder(
+                &pool_state_or_buffer,
+                &swap_test.token_in,
+                &swap_test.token_out,
+                swap_kind.clone(),
+                &amounts_raw,
+                test_data.hook_state.as_ref(),
+            )
+            .unwrap_or_else(|e| panic!("Ladder failed for test {}: {:?}", swap_test.test, e));
+
+        assert_eq!(ladder.len(), amounts_raw.len());
+
+        for (amount_raw, result) in amounts_raw.iter().zip(ladder) {
+            let expected = vault.swap(
+                &SwapInput {
+                    amount_raw: *amount_raw,
+                    token_in: swap_test.token_in.clone(),
+                    token_out: swap_test.token_out.clone(),
+                    swap_kind: swap_kind.clone(),
+                },
+                &pool_state_or_buffer,
+                test_data.hook_state.as_ref(),
+            );
+
+            assert_eq!(
+                result, expected,
+                "Ladder mismatch for test: {} (amount: {})",
+                swap_test.test, amount_raw
+            );
+        }
+    }
+}
+
+#[test]
+fn test_swap_ladder_unknown_token() {
+    let test_data = read_test_data().expect("Failed to read test data");
+    let vault = Vault::new();
+
+    let (pool_state_or_buffer, token_out) = test_data
+        .pools
+        .values()
+        .map(convert_to_pool_state)
+        .find_map(|pool| match &pool {
+            PoolStateOrBuffer::Pool(pool_state) => {
+                let token_out = pool_state.base().tokens[1].clone();
+                Some((pool, token_out))
+            }
+            PoolStateOrBuffer::Buffer(_) => None,
+        })
+        .expect("No pool in test data");
+
+    let result = vault.swap_ladder(
+        &pool_state_or_buffer,
+        "0x0000000000000000000000000000000000000000",
+        &token_out,
+        SwapKind::GivenIn,
+        &[U256::from(1), U256::from(2)],
+        test_data.hook_state.as_ref(),
+    );
+
+    assert_eq!(result, Err(PoolError::InputTokenNotFound));
+}
@@ -0,0 +1,257 @@
+use alloy_primitives::U256;
+use balancer_maths_rust::common::errors::PoolError;
+use balancer_maths_rust::common::types::*;
+use balancer_maths_rust::common::utils::{
+    to_scaled_18_apply_rate_round_down, to_scaled_18_apply_rate_round_up,
+};
+use balancer_maths_rust::pools::reclamm::ReClammPool;
+use balancer_maths_rust::pools::reclammv2::ReClammV2Pool;
+use balancer_maths_rust::vault::Vault;
+mod utils;
+use std::str::FromStr;
+use utils::find_pool;
+
+/// Apply a swap to the pool balances, keeping the virtual balances of ReClamm pools as they
+/// were computed for the swap
+fn apply_swap(
+    pool_state: &PoolState,
+    token_in_index: usize,
+    token_out_index: usize,
+    amount_in_raw: U256,
+    amount_out_raw: U256,
+) -> PoolState {
+    let mut pool_state = pool_state.clone();
+    let balances = pool_state.base().balances_live_scaled_18.clone();
+
+    match &mut pool_state {
+        PoolState::ReClamm(re_clamm_state) => {
+            let (virtual_balance_a, virtual_balance_b, _) =
+                ReClammPool::new(re_clamm_state.clone()).current_virtual_balances(&balances);
+            re_clamm_state.mutable.last_virtual_balances =
+                vec![virtual_balance_a, virtual_balance_b];
+            re_clamm_state.mutable.last_timestamp = re_clamm_state.mutable.current_timestamp;
+        }
+        PoolState::ReClammV2(re_clamm_v2_state) => {
+            let (virtual_balance_a, virtual_balance_b, _) =
+                ReClammV2Pool::new(re_clamm_v2_state.clone()).current_virtual_balances(&balances);
+            re_clamm_v2_state.mutable.last_virtual_balances =
+                vec![virtual_balance_a, virtual_balance_b];
+            re_clamm_v2_state.mutable.last_timestamp = re_clamm_v2_state.mutable.current_timestamp;
+        }
+        _ => {}
+    }
+
+    let base = pool_state.base_mut();
+    base.balances_live_scaled_18[token_in_index] += to_scaled_18_apply_rate_round_down(
+        &amount_in_raw,
+        &base.scaling_factors[token_in_index],
+        &base.token_rates[token_in_index],
+    )
+    .unwrap();
+    base.balances_live_scaled_18[token_out_index] -= to_scaled_18_apply_rate_round_up(
+        &amount_out_raw,
+        &base.scaling_factors[token_out_index],
+        &base.token_rates[token_out_index],
+    )
+    .unwrap();
+
+    pool_state
+}
+
+/// Move the price of token 0 in token 1 of a test pool by `bps` basis points, checking that the
+/// swap is exactly what the vault quotes and the smallest that reaches the target
+fn swap_to_price(name: &str, bps: i64) -> SwapToTargetPriceResult {
+    let vault = Vault::new();
+    let pool_state = find_pool(name);
+    let base = pool_state.base();
+    let (base_token, quote_token) = (&base.tokens[0], &base.tokens[1]);
+    let current_price = vault
+        .spot_price(&pool_state, base_token, quote_token)
+        .unwrap();
+    let target_price = current_price * U256::from(10_000 + bps) / U256::from(10_000);
+
+    let result = vault
+        .swap_to_price(&pool_state, base_token, quote_token, &target_price, None)
+        .unwrap_or_else(|error| panic!("Swap to price failed for {}: {:?}", name, error));
+    let price_increases = bps > 0;
+    assert_eq!(result.token_in_index, if price_increases { 1 } else { 0 });
+
+    // Amounts are exactly what the vault quotes
+    let swap_input = SwapInput {
+        amount_raw: result.amount_in_raw,
+        swap_kind: SwapKind::GivenIn,
+        token_in: base.tokens[result.token_in_index].clone(),
+        token_out: base.tokens[result.token_out_index].clone(),
+    };
+    let quote = |swap_input: &SwapInput| {
+        vault.swap(
+            swap_input,
+            &PoolStateOrBuffer::Pool(Box::new(pool_state.clone())),
+            None,
+        )
+    };
+    assert_eq!(quote(&swap_input).unwrap(), result.amount_out_raw);
+
+    let price_after = |amount_in_raw: U256, amount_out_raw: U256| {
+        let next_state = apply_swap(
+            &pool_state,
+            result.token_in_index,
+            result.token_out_index,
+            amount_in_raw,
+            amount_out_raw,
+        );
+        vault
+            .spot_price(&next_state, base_token, quote_token)
+            .unwrap()
+    };
+    let reaches = |price: U256| {
+        if price_increases {
+            price >= target_price
+        } else {
+            price <= target_price
+        }
+    };
+
+    let new_price = price_after(result.amount_in_raw, result.amount_out_raw);
+    assert!(reaches(new_price), "Target price not reached for {}", name);
+    assert!(
+        new_price.abs_diff(target_price) <= target_price / U256::from(1_000_000),
+        "New price {} too far from target {} for {}",
+        new_price,
+        target_price,
+        name
+    );
+
+    // One raw unit less does not reach the target
+    let smaller_amount_in_raw = result.amount_in_raw - U256::ONE;
+    let smaller_amount_out_raw = quote(&SwapInput {
+        amount_raw: smaller_amount_in_raw,
+        ..swap_input
+    })
+    .unwrap();
+    assert!(
+        !reaches(price_after(smaller_amount_in_raw, smaller_amount_out_raw)),
+        "Amount in is not the smallest for {}",
+        name
+    );
+
+    result
+}
+
+fn assert_swap_to_price(name: &str, bps: i64, amount_in_raw: &str, amount_out_raw: &str) {
+    let result = swap_to_price(name, bps);
+    assert_eq!(
+        (result.amount_in_raw, result.amount_out_raw),
+        (
+            U256::from_str(amount_in_raw).unwrap(),
+            U256::from_str(amount_out_raw).unwrap()
+        ),
+        "Swap to price mismatch for {} at {} bps",
+        name,
+        bps
+    );
+}
+
+#[test]
+fn test_swap_to_price_weighted() {
+    let name = "11155111-7439300-Weighted-USDC-DAI.json";
+    assert_swap_to_price(name, -1, "347584", "310473617243196055");
+    assert_swap_to_price(name, 1, "313593377608499083", "344056");
+}
+
+#[test]
+fn test_swap_to_price_stable() {
+    let name = "11155111-7439300-Stable-stataUSDC-stataUSDT.json";
+    assert_swap_to_price(name, -1, "287746576", "252387970");
+    assert_swap_to_price(name, 1, "240881352", "274051546");
+}
+
+#[test]
+fn test_swap_to_price_gyro_eclp() {
+    let name = "11155111-7748718-GyroECLP.json";
+    assert_swap_to_price(name, -1, "243461958593239502", "240979438180914971");
+    assert_swap_to_price(name, 1, "286234617096638901", "283400577506295463");
+}
+
+#[test]
+fn test_swap_to_price_liquidity_bootstrapping() {
+    let name = "11155111-8085514-LBP-BAL-DAI.json";
+    assert_swap_to_price(name, -1, "52048888129116", "47956316759697");
+    assert_swap_to_price(name, 1, "48103809054794", "51891001845021");
+}
+
+#[test]
+fn test_swap_to_price_quantamm() {
+    let name = "1-22524240-QuantAMM.json";
+    assert_swap_to_price(name, -1, "391", "124764305423827");
+    assert_swap_to_price(name, 1, "124961357135481", "376");
+}
+
+#[test]
+fn test_swap_to_price_reclamm() {
+    let name = "8453-31094200-ReClamm-WETH-USDC-In-Range.json";
+    assert_swap_to_price(name, -1, "1312878919970", "3346");
+    assert_swap_to_price(name, 1, "3348", "1312219117466");
+    let name = "8453-32632378-ReClamm-V2.json";
+    assert_swap_to_price(name, -1, "18294944", "21420500");
+    assert_swap_to_price(name, 1, "21424786", "18287627");
+}
+
+#[test]
+fn test_swap_to_price_errors() {
+    let vault = Vault::new();
+    let pool_state = find_pool("11155111-7439300-Weighted-USDC-DAI.json");
+    let tokens = pool_state.base().tokens.clone();
+    let current_price = vault
+        .spot_price(&pool_state, &tokens[0], &tokens[1])
+        .unwrap();
+
+    // Already at the target
+    let result = vault
+        .swap_to_price(&pool_state, &tokens[0], &tokens[1], &current_price, None)
+        .unwrap();
+    assert_eq!(result.amount_in_raw, U256::ZERO);
+    assert_eq!(result.amount_out_raw, U256::ZERO);
+
+    // Out of reach of the max in ratio
+    assert_eq!(
+        vault
+            .swap_to_price(
+                &pool_state,
+                &tokens[0],
+                &tokens[1],
+                &(current_price * U256::from(100)),
+                None
+            )
+            .unwrap_err(),
+        PoolError::MaxInRatioExceeded
+    );
+
+    assert!(matches!(
+        vault.spot_price(&pool_state, &tokens[0], &tokens[0]),
+        Err(PoolError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        vault.swap_to_price(&pool_state, &tokens[0], "0xunknown", &current_price, None),
+        Err(PoolError::InvalidInput(_))
+    ));
+
+    // Gyro ECLP targets past the price bound of the pool
+    let pool_state = find_pool("1-23511249-GyroECLP-Barter.json");
+    let tokens = pool_state.base().tokens.clone();
+    let current_price = vault
+        .spot_price(&pool_state, &tokens[0], &tokens[1])
+        .unwrap();
+    assert_eq!(
+        vault
+            .swap_to_price(
+                &pool_state,
+                &tokens[0],
+                &tokens[1],
+                &(current_price * U256::from(9_999) / U256::from(10_000)),
+                None
+            )
+            .unwrap_err(),
+        PoolError::InvalidInput("Asset bounds exceeded".to_string())
+    );
+}
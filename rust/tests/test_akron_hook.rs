@@ -28,7 +28,10 @@ fn create_test_pool_state() -> WeightedState {
         ],
         total_supply: U256::from_str("83925520418320097254").unwrap(),
         supports_unbalanced_liquidity: false,
+        supports_donation: false,
         hook_type: Some("Akron".to_string()),
+        is_pool_paused: false,
+        is_pool_in_recovery_mode: false,
     };
 
     WeightedState {
@@ -30,10 +30,15 @@ fn create_stable_pool_state_with_hook() -> StableState {
         aggregate_swap_fee: U256::from(0u64),
         total_supply: U256::from_str("40000000000000000000000").unwrap(),
         supports_unbalanced_liquidity: true,
+        supports_donation: false,
         hook_type: Some("DirectionalFee".to_string()),
+        is_pool_paused: false,
+        is_pool_in_recovery_mode: false,
     };
     let mutable = StableMutable {
         amp: U256::from(1000000u64),
+        amp_update: None,
+        current_timestamp: U256::ZERO,
     };
     StableState { base, mutable }
 }
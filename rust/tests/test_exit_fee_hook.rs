@@ -38,7 +38,10 @@ fn create_test_pool_state() -> WeightedState {
             total_supply: U256::from(158113883008415798u64),
             aggregate_swap_fee: U256::ZERO,
             supports_unbalanced_liquidity: true,
+            supports_donation: false,
             hook_type: Some("ExitFee".to_string()),
+            is_pool_paused: false,
+            is_pool_in_recovery_mode: false,
         },
         weights: vec![
             U256::from(500000000000000000u64),
@@ -106,7 +106,10 @@ fn create_pool_state_or_buffer(test_pool: &TestPool) -> PoolStateOrBuffer {
         aggregate_swap_fee: test_pool.aggregate_swap_fee,
         total_supply: test_pool.total_supply,
         supports_unbalanced_liquidity: test_pool.supports_unbalanced_liquidity,
+        supports_donation: false,
         hook_type: None,
+        is_pool_paused: false,
+        is_pool_in_recovery_mode: false,
     };
 
     let re_clamm_mutable = ReClammMutable {
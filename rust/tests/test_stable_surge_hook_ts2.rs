@@ -35,11 +35,16 @@ fn create_test_pool_state() -> StableState {
         aggregate_swap_fee: U256::from(500000000000000000u64),
         total_supply: U256::from_str("9332159723859490160669").unwrap(),
         supports_unbalanced_liquidity: true,
+        supports_donation: false,
         hook_type: Some("StableSurge".to_string()),
+        is_pool_paused: false,
+        is_pool_in_recovery_mode: false,
     };
 
     let stable_mutable = StableMutable {
         amp: U256::from(500000u64),
+        amp_update: None,
+        current_timestamp: U256::ZERO,
     };
 
     StableState {
@@ -5,5 +5,6 @@ pub mod read_test_data;
 pub mod test_helpers;
 
 pub use map_hook_state::*;
+#[allow(unused_imports)]
 pub use read_test_data::*;
 pub use test_helpers::*;
@@ -233,6 +233,10 @@ pub struct BufferMutable {
     pub max_deposit: Option<U256>,
     #[serde(rename = "maxMint")]
     pub max_mint: Option<U256>,
+    #[serde(rename = "maxWithdraw")]
+    pub max_withdraw: Option<U256>,
+    #[serde(rename = "maxRedeem")]
+    pub max_redeem: Option<U256>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -459,6 +463,12 @@ struct RawPool {
     pub aggregate_swap_fee: Option<String>,
     #[serde(rename = "supportsUnbalancedLiquidity")]
     pub supports_unbalanced_liquidity: Option<bool>,
+    #[serde(rename = "supportsDonation")]
+    pub supports_donation: Option<bool>,
+    #[serde(rename = "isPoolPaused")]
+    pub is_pool_paused: Option<bool>,
+    #[serde(rename = "isPoolInRecoveryMode")]
+    pub is_pool_in_recovery_mode: Option<bool>,
     // Weighted pool specific fields
     pub weights: Option<Vec<String>>,
     // Stable pool specific fields
@@ -527,6 +537,10 @@ struct RawPool {
     pub max_deposit: Option<String>,
     #[serde(rename = "maxMint")]
     pub max_mint: Option<String>,
+    #[serde(rename = "maxWithdraw")]
+    pub max_withdraw: Option<String>,
+    #[serde(rename = "maxRedeem")]
+    pub max_redeem: Option<String>,
     // ReClamm specific fields
     #[serde(rename = "lastVirtualBalances")]
     pub last_virtual_balances: Option<Vec<String>>,
@@ -785,7 +799,10 @@ fn map_pool(raw_pool: RawPool) -> Result<SupportedPool, Box<dyn std::error::Erro
                     supports_unbalanced_liquidity: raw_pool
                         .supports_unbalanced_liquidity
                         .unwrap_or(true),
+                    supports_donation: raw_pool.supports_donation.unwrap_or(false),
                     hook_type: None,
+                    is_pool_paused: raw_pool.is_pool_paused.unwrap_or(false),
+                    is_pool_in_recovery_mode: raw_pool.is_pool_in_recovery_mode.unwrap_or(false),
                 },
                 weights,
             };
@@ -834,7 +851,10 @@ fn map_pool(raw_pool: RawPool) -> Result<SupportedPool, Box<dyn std::error::Erro
                     supports_unbalanced_liquidity: raw_pool
                         .supports_unbalanced_liquidity
                         .unwrap_or(true),
+                    supports_donation: raw_pool.supports_donation.unwrap_or(false),
                     hook_type: None,
+                    is_pool_paused: raw_pool.is_pool_paused.unwrap_or(false),
+                    is_pool_in_recovery_mode: raw_pool.is_pool_in_recovery_mode.unwrap_or(false),
                 },
                 mutable: StableMutable { amp },
             };
@@ -949,7 +969,10 @@ fn map_pool(raw_pool: RawPool) -> Result<SupportedPool, Box<dyn std::error::Erro
                     supports_unbalanced_liquidity: raw_pool
                         .supports_unbalanced_liquidity
                         .unwrap_or(true),
+                    supports_donation: raw_pool.supports_donation.unwrap_or(false),
                     hook_type: None,
+                    is_pool_paused: raw_pool.is_pool_paused.unwrap_or(false),
+                    is_pool_in_recovery_mode: raw_pool.is_pool_in_recovery_mode.unwrap_or(false),
                 },
                 immutable: GyroECLPImmutable {
                     alpha,
@@ -1048,7 +1071,10 @@ fn map_pool(raw_pool: RawPool) -> Result<SupportedPool, Box<dyn std::error::Erro
                     supports_unbalanced_liquidity: raw_pool
                         .supports_unbalanced_liquidity
                         .unwrap_or(true),
+                    supports_donation: raw_pool.supports_donation.unwrap_or(false),
                     hook_type: None,
+                    is_pool_paused: raw_pool.is_pool_paused.unwrap_or(false),
+                    is_pool_in_recovery_mode: raw_pool.is_pool_in_recovery_mode.unwrap_or(false),
                 },
                 mutable: QuantAmmMutable {
                     first_four_weights_and_multipliers,
@@ -1151,7 +1177,10 @@ fn map_pool(raw_pool: RawPool) -> Result<SupportedPool, Box<dyn std::error::Erro
                     supports_unbalanced_liquidity: raw_pool
                         .supports_unbalanced_liquidity
                         .unwrap_or(true),
+                    supports_donation: raw_pool.supports_donation.unwrap_or(false),
                     hook_type: None,
+                    is_pool_paused: raw_pool.is_pool_paused.unwrap_or(false),
+                    is_pool_in_recovery_mode: raw_pool.is_pool_in_recovery_mode.unwrap_or(false),
                 },
                 mutable: LiquidityBootstrappingMutable {
                     is_swap_enabled,
@@ -1251,7 +1280,10 @@ fn map_pool(raw_pool: RawPool) -> Result<SupportedPool, Box<dyn std::error::Erro
                     supports_unbalanced_liquidity: raw_pool
                         .supports_unbalanced_liquidity
                         .unwrap_or(false),
+                    supports_donation: raw_pool.supports_donation.unwrap_or(false),
                     hook_type: None,
+                    is_pool_paused: raw_pool.is_pool_paused.unwrap_or(false),
+                    is_pool_in_recovery_mode: raw_pool.is_pool_in_recovery_mode.unwrap_or(false),
                 },
                 mutable: FixedPriceLBPMutable {
                     is_swap_enabled,
@@ -1361,7 +1393,10 @@ fn map_pool(raw_pool: RawPool) -> Result<SupportedPool, Box<dyn std::error::Erro
                     supports_unbalanced_liquidity: raw_pool
                         .supports_unbalanced_liquidity
                         .unwrap_or(true),
+                    supports_donation: raw_pool.supports_donation.unwrap_or(false),
                     hook_type: None,
+                    is_pool_paused: raw_pool.is_pool_paused.unwrap_or(false),
+                    is_pool_in_recovery_mode: raw_pool.is_pool_in_recovery_mode.unwrap_or(false),
                 },
                 mutable: ReClammMutable {
                     last_virtual_balances,
@@ -1475,7 +1510,10 @@ fn map_pool(raw_pool: RawPool) -> Result<SupportedPool, Box<dyn std::error::Erro
                     supports_unbalanced_liquidity: raw_pool
                         .supports_unbalanced_liquidity
                         .unwrap_or(true),
+                    supports_donation: raw_pool.supports_donation.unwrap_or(false),
                     hook_type: None,
+                    is_pool_paused: raw_pool.is_pool_paused.unwrap_or(false),
+                    is_pool_in_recovery_mode: raw_pool.is_pool_in_recovery_mode.unwrap_or(false),
                 },
                 mutable: ReClammV2Mutable {
                     last_virtual_balances,
@@ -1522,6 +1560,18 @@ fn map_pool(raw_pool: RawPool) -> Result<SupportedPool, Box<dyn std::error::Erro
                 .map(|m| m.parse::<U256>())
                 .transpose()?;
 
+            let max_withdraw = raw_pool
+                .max_withdraw
+                .as_ref()
+                .map(|w| w.parse::<U256>())
+                .transpose()?;
+
+            let max_redeem = raw_pool
+                .max_redeem
+                .as_ref()
+                .map(|r| r.parse::<U256>())
+                .transpose()?;
+
             // Buffer pools have minimal required fields, use defaults for missing ones
             let buffer_state = BufferState {
                 base: BasePoolState {
@@ -1538,12 +1588,17 @@ fn map_pool(raw_pool: RawPool) -> Result<SupportedPool, Box<dyn std::error::Erro
                     total_supply: U256::from(0u64),
                     aggregate_swap_fee: U256::from(0u64),
                     supports_unbalanced_liquidity: true,
+                    supports_donation: raw_pool.supports_donation.unwrap_or(false),
                     hook_type: None,
+                    is_pool_paused: raw_pool.is_pool_paused.unwrap_or(false),
+                    is_pool_in_recovery_mode: raw_pool.is_pool_in_recovery_mode.unwrap_or(false),
                 },
                 mutable: BufferMutable {
                     rate,
                     max_deposit,
                     max_mint,
+                    max_withdraw,
+                    max_redeem,
                 },
                 immutable: BufferImmutable {
                     pool_address: raw_pool.pool_address.clone(),
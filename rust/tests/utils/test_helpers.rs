@@ -1,6 +1,7 @@
 //! Shared test helper functions for pool state conversion and utilities
 
-use crate::utils::read_test_data::SupportedPool;
+use crate::utils::read_test_data::{read_test_data, SupportedPool};
+use alloy_primitives::U256;
 use balancer_maths_rust::common::types::{PoolState, PoolStateOrBuffer};
 use balancer_maths_rust::pools::buffer::{BufferImmutable, BufferMutable, BufferState};
 use balancer_maths_rust::pools::fixed_price_lbp::{
@@ -11,8 +12,12 @@ use balancer_maths_rust::pools::liquidity_bootstrapping::{
     LiquidityBootstrappingImmutable, LiquidityBootstrappingMutable, LiquidityBootstrappingState,
 };
 use balancer_maths_rust::pools::quantamm::{QuantAmmImmutable, QuantAmmMutable, QuantAmmState};
-use balancer_maths_rust::pools::reclamm::{ReClammImmutable, ReClammMutable, ReClammState};
-use balancer_maths_rust::pools::reclammv2::{ReClammV2Immutable, ReClammV2Mutable, ReClammV2State};
+use balancer_maths_rust::pools::reclamm::{
+    ReClammImmutable, ReClammMutable, ReClammPool, ReClammState, ReClammStatus,
+};
+use balancer_maths_rust::pools::reclammv2::{
+    ReClammV2Immutable, ReClammV2Mutable, ReClammV2Pool, ReClammV2State,
+};
 use balancer_maths_rust::pools::stable::{StableMutable, StableState};
 use balancer_maths_rust::pools::weighted::WeightedState;
 
@@ -32,6 +37,8 @@ pub fn convert_to_pool_state(pool: &SupportedPool) -> PoolStateOrBuffer {
                 base: stable_pool.state.base.clone(),
                 mutable: StableMutable {
                     amp: stable_pool.state.mutable.amp,
+                    amp_update: None,
+                    current_timestamp: U256::ZERO,
                 },
             };
             PoolStateOrBuffer::Pool(Box::new(PoolState::Stable(stable_state)))
@@ -214,10 +221,16 @@ pub fn convert_to_pool_state(pool: &SupportedPool) -> PoolStateOrBuffer {
                     rate: buffer_pool.state.mutable.rate,
                     max_deposit: buffer_pool.state.mutable.max_deposit,
                     max_mint: buffer_pool.state.mutable.max_mint,
+                    max_withdraw: buffer_pool.state.mutable.max_withdraw,
+                    max_redeem: buffer_pool.state.mutable.max_redeem,
+                    underlying_balance: U256::ZERO,
+                    wrapped_balance: U256::ZERO,
                 },
                 immutable: BufferImmutable {
                     pool_address: buffer_pool.state.immutable.pool_address.clone(),
                     tokens: buffer_pool.state.immutable.tokens.clone(),
+                    wrapped_decimals: None,
+                    underlying_decimals: None,
                 },
             };
             PoolStateOrBuffer::Buffer(Box::new(buffer_state))
@@ -245,3 +258,51 @@ pub fn get_pool_address(pool: &SupportedPool) -> String {
         // Add other pool types here as they are implemented
     }
 }
+
+/// Load a pool from the test data by file name
+#[allow(dead_code)]
+pub fn find_pool(name: &str) -> PoolState {
+    let test_data = read_test_data().expect("Failed to read test data");
+    match convert_to_pool_state(&test_data.pools[name]) {
+        PoolStateOrBuffer::Pool(pool_state) => *pool_state,
+        PoolStateOrBuffer::Buffer(_) => panic!("{} is a buffer", name),
+    }
+}
+
+/// Load a GyroECLP pool from the test data by file name
+#[allow(dead_code)]
+pub fn find_eclp_pool(name: &str) -> GyroECLPState {
+    match find_pool(name) {
+        PoolState::GyroECLP(state) => state,
+        _ => panic!("{} is not an ECLP pool", name),
+    }
+}
+
+/// Get the status of a ReClamm or ReClammV2 pool
+#[allow(dead_code)]
+pub fn reclamm_status(pool_state: &PoolState) -> ReClammStatus {
+    match pool_state {
+        PoolState::ReClamm(state) => ReClammPool::new(state.clone()).status(),
+        PoolState::ReClammV2(state) => ReClammV2Pool::new(state.clone()).status(),
+        _ => panic!("Not a ReClamm pool"),
+    }
+}
+
+/// Load the Liquidity Bootstrapping pool from the test data
+#[allow(dead_code)]
+pub fn find_lbp() -> LiquidityBootstrappingState {
+    match find_pool("11155111-8085514-LBP-BAL-DAI.json") {
+        PoolState::LiquidityBootstrapping(state) => state,
+        _ => panic!("Not an LBP"),
+    }
+}
+
+/// Load the waEthUSDC buffer from the test data
+#[allow(dead_code)]
+pub fn find_buffer() -> BufferState {
+    let test_data = read_test_data().expect("Failed to read test data");
+    match convert_to_pool_state(&test_data.pools["1-21671845-Buffer-waEthUSDC.json"]) {
+        PoolStateOrBuffer::Buffer(buffer_state) => *buffer_state,
+        PoolStateOrBuffer::Pool(_) => panic!("Not a buffer"),
+    }
+}